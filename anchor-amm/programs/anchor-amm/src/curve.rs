@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AmmError;
+
+// Integer x * y = k math, shared by deposit, withdraw and swap. Rounding
+// always favours the pool.
+
+/// Tokens of each side needed to mint `lp` against the current reserves
+pub fn deposit_amounts(lp: u64, x: u64, y: u64, supply: u64) -> Result<(u64, u64)> {
+    Ok((mul_div_up(lp, x, supply)?, mul_div_up(lp, y, supply)?))
+}
+
+/// Tokens of each side returned for burning `lp`
+pub fn withdraw_amounts(lp: u64, x: u64, y: u64, supply: u64) -> Result<(u64, u64)> {
+    Ok((mul_div(lp, x, supply)?, mul_div(lp, y, supply)?))
+}
+
+/// Output of swapping `amount_in` into a pool holding `reserve_in` and
+/// `reserve_out`, after the fee in basis points
+pub fn swap_out(amount_in: u64, reserve_in: u64, reserve_out: u64, fee: u16) -> Result<u64> {
+    let amount_in = amount_in as u128 * (10_000 - fee as u128) / 10_000;
+    let out = amount_in * reserve_out as u128 / (reserve_in as u128 + amount_in);
+    u64::try_from(out).map_err(|_| AmmError::Overflow.into())
+}
+
+fn mul_div(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    let result = a as u128 * b as u128 / denominator as u128;
+    u64::try_from(result).map_err(|_| AmmError::Overflow.into())
+}
+
+fn mul_div_up(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    let result = (a as u128 * b as u128).div_ceil(denominator as u128);
+    u64::try_from(result).map_err(|_| AmmError::Overflow.into())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum AmmError {
+    #[msg("Fee is above the maximum")]
+    InvalidFee,
+    #[msg("Mints must be distinct and ordered, mint_x < mint_y")]
+    InvalidMintOrder,
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("The pool is locked")]
+    PoolLocked,
+    #[msg("Price moved beyond the slippage limit")]
+    SlippageExceeded,
+    #[msg("The pool has no liquidity")]
+    NoLiquidity,
+    #[msg("Only the pool authority can do this")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
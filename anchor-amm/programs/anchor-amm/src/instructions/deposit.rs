@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount, Transfer};
+
+use crate::curve::deposit_amounts;
+use crate::error::AmmError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump
+    )]
+    pub mint_lp: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_x,
+        associated_token::authority = config,
+    )]
+    pub vault_x: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_y,
+        associated_token::authority = config,
+    )]
+    pub vault_y: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_x, token::authority = user)]
+    pub user_x: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_y, token::authority = user)]
+    pub user_y: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint_lp, token::authority = user)]
+    pub user_lp: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX, config.mint_x.as_ref(), config.mint_y.as_ref()],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64, max_x: u64, max_y: u64) -> Result<()> {
+        require!(!self.config.locked, AmmError::PoolLocked);
+        require_gt!(amount, 0, AmmError::InvalidAmount);
+
+        // The first deposit sets the price; later ones match the reserves
+        let (x, y) = match self.mint_lp.supply {
+            0 => (max_x, max_y),
+            supply => deposit_amounts(amount, self.vault_x.amount, self.vault_y.amount, supply)?,
+        };
+        require!(x > 0 && y > 0, AmmError::InvalidAmount);
+        require!(x <= max_x && y <= max_y, AmmError::SlippageExceeded);
+
+        self.transfer_in(&self.user_x, &self.vault_x, x)?;
+        self.transfer_in(&self.user_y, &self.vault_y, y)?;
+
+        let seeds = self.config.signer_seeds();
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = MintTo {
+            mint: self.mint_lp.to_account_info(),
+            to: self.user_lp.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::mint_to(cpi_ctx, amount)
+    }
+
+    fn transfer_in(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::error::AmmError;
+use crate::state::{Config, LP_DECIMALS, MAX_FEE_BPS};
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub initializer: Signer<'info>,
+
+    pub mint_x: Account<'info, Mint>,
+    pub mint_y: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = initializer,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [Config::SEED_PREFIX, mint_x.key().as_ref(), mint_y.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = initializer,
+        seeds = [Config::LP_SEED, config.key().as_ref()],
+        bump,
+        mint::decimals = LP_DECIMALS,
+        mint::authority = config,
+    )]
+    pub mint_lp: Account<'info, Mint>,
+
+    // The vaults' addresses are known from the mints, so anyone can create
+    // them first; an existing one is accepted once its mint and owner check out
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        associated_token::mint = mint_x,
+        associated_token::authority = config,
+    )]
+    pub vault_x: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = initializer,
+        associated_token::mint = mint_y,
+        associated_token::authority = config,
+    )]
+    pub vault_y: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(
+        &mut self,
+        fee: u16,
+        authority: Pubkey,
+        bumps: &InitializeBumps,
+    ) -> Result<()> {
+        require!(fee <= MAX_FEE_BPS, AmmError::InvalidFee);
+        // One pool per pair: the ordering makes (x, y) and (y, x) the same pool
+        require!(
+            self.mint_x.key() < self.mint_y.key(),
+            AmmError::InvalidMintOrder
+        );
+
+        self.config.set_inner(Config {
+            authority,
+            mint_x: self.mint_x.key(),
+            mint_y: self.mint_y.key(),
+            fee,
+            locked: false,
+            config_bump: bumps.config,
+            lp_bump: bumps.mint_lp,
+        });
+
+        Ok(())
+    }
+}
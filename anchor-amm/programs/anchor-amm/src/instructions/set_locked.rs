@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::error::AmmError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetLocked<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX, config.mint_x.as_ref(), config.mint_y.as_ref()],
+        bump = config.config_bump,
+        has_one = authority @ AmmError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetLocked<'info> {
+    pub fn set_locked(&mut self, locked: bool) -> Result<()> {
+        self.config.locked = locked;
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::curve::swap_out;
+use crate::error::AmmError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_x,
+        associated_token::authority = config,
+    )]
+    pub vault_x: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_y,
+        associated_token::authority = config,
+    )]
+    pub vault_y: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_x, token::authority = user)]
+    pub user_x: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_y, token::authority = user)]
+    pub user_y: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX, config.mint_x.as_ref(), config.mint_y.as_ref()],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Swap<'info> {
+    pub fn swap(&mut self, is_x: bool, amount: u64, min_out: u64) -> Result<()> {
+        require!(!self.config.locked, AmmError::PoolLocked);
+        require_gt!(amount, 0, AmmError::InvalidAmount);
+
+        let (user_in, vault_in, vault_out, user_out) = if is_x {
+            (&self.user_x, &self.vault_x, &self.vault_y, &self.user_y)
+        } else {
+            (&self.user_y, &self.vault_y, &self.vault_x, &self.user_x)
+        };
+        require!(
+            vault_in.amount > 0 && vault_out.amount > 0,
+            AmmError::NoLiquidity
+        );
+
+        let out = swap_out(amount, vault_in.amount, vault_out.amount, self.config.fee)?;
+        require_gt!(out, 0, AmmError::InvalidAmount);
+        require_gte!(out, min_out, AmmError::SlippageExceeded);
+
+        let cpi_accounts = Transfer {
+            from: user_in.to_account_info(),
+            to: vault_in.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let seeds = self.config.signer_seeds();
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: vault_out.to_account_info(),
+            to: user_out.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, out)
+    }
+}
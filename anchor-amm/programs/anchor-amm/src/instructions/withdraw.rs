@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+
+use crate::curve::withdraw_amounts;
+use crate::error::AmmError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::LP_SEED, config.key().as_ref()],
+        bump = config.lp_bump
+    )]
+    pub mint_lp: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_x,
+        associated_token::authority = config,
+    )]
+    pub vault_x: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = config.mint_y,
+        associated_token::authority = config,
+    )]
+    pub vault_y: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_x, token::authority = user)]
+    pub user_x: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = config.mint_y, token::authority = user)]
+    pub user_y: Account<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint_lp, token::authority = user)]
+    pub user_lp: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX, config.mint_x.as_ref(), config.mint_y.as_ref()],
+        bump = config.config_bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Withdraw<'info> {
+    // Withdrawals stay open while the pool is locked, so LPs can always exit
+    pub fn withdraw(&mut self, amount: u64, min_x: u64, min_y: u64) -> Result<()> {
+        require_gt!(amount, 0, AmmError::InvalidAmount);
+        require_gt!(self.mint_lp.supply, 0, AmmError::NoLiquidity);
+
+        let (x, y) = withdraw_amounts(
+            amount,
+            self.vault_x.amount,
+            self.vault_y.amount,
+            self.mint_lp.supply,
+        )?;
+        require!(x >= min_x && y >= min_y, AmmError::SlippageExceeded);
+
+        let cpi_accounts = Burn {
+            mint: self.mint_lp.to_account_info(),
+            from: self.user_lp.to_account_info(),
+            authority: self.user.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token::burn(cpi_ctx, amount)?;
+
+        self.transfer_out(&self.vault_x, &self.user_x, x)?;
+        self.transfer_out(&self.vault_y, &self.user_y, y)
+    }
+
+    fn transfer_out(
+        &self,
+        from: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let seeds = self.config.signer_seeds();
+        let signer_seeds = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: from.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+pub mod curve;
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+
+declare_id!("DE3XiAnbndLf6sRcrZ7Z8cdMyHzU9qL3gg6h1egsny8B");
+
+// Instructions use 1-byte discriminators and the same accounts and arguments
+// as the Pinocchio AMM, so either program accepts the same transactions.
+#[program]
+pub mod anchor_amm {
+    use super::*;
+
+    /// Create the pool, its LP mint and vaults for a mint pair
+    #[instruction(discriminator = 0)]
+    pub fn initialize(ctx: Context<Initialize>, fee: u16, authority: Pubkey) -> Result<()> {
+        ctx.accounts.initialize(fee, authority, &ctx.bumps)
+    }
+
+    /// Mint `amount` LP tokens for at most `max_x` and `max_y` deposited
+    #[instruction(discriminator = 1)]
+    pub fn deposit(ctx: Context<Deposit>, amount: u64, max_x: u64, max_y: u64) -> Result<()> {
+        ctx.accounts.deposit(amount, max_x, max_y)
+    }
+
+    /// Burn `amount` LP tokens for at least `min_x` and `min_y` back
+    #[instruction(discriminator = 2)]
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64, min_x: u64, min_y: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount, min_x, min_y)
+    }
+
+    /// Swap `amount` of x for y (or y for x), receiving at least `min_out`
+    #[instruction(discriminator = 3)]
+    pub fn swap(ctx: Context<Swap>, is_x: bool, amount: u64, min_out: u64) -> Result<()> {
+        ctx.accounts.swap(is_x, amount, min_out)
+    }
+
+    /// Authority-only: stop or resume deposits and swaps
+    #[instruction(discriminator = 4)]
+    pub fn set_locked(ctx: Context<SetLocked>, locked: bool) -> Result<()> {
+        ctx.accounts.set_locked(locked)
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+/// Upper bound on the swap fee, 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+/// Decimals of every pool's LP mint
+pub const LP_DECIMALS: u8 = 6;
+
+/// A pool for one mint pair, stored at ["config", mint_x, mint_y]
+///
+/// The Pinocchio AMM stores the same fields in the same order, without
+/// Anchor's 8-byte discriminator.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    /// May lock and unlock the pool; the default pubkey means nobody can
+    pub authority: Pubkey,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    /// Swap fee in basis points, left in the pool for LPs
+    pub fee: u16,
+    /// A locked pool takes no deposits or swaps; LPs can still withdraw
+    pub locked: bool,
+    pub config_bump: u8,
+    pub lp_bump: u8,
+}
+
+impl Config {
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+    pub const LP_SEED: &'static [u8] = b"lp";
+
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            Self::SEED_PREFIX,
+            self.mint_x.as_ref(),
+            self.mint_y.as_ref(),
+            std::slice::from_ref(&self.config_bump),
+        ]
+    }
+}
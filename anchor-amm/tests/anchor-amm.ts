@@ -0,0 +1,240 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { AnchorAmm } from "../target/types/anchor_amm";
+
+describe("anchor-amm", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const program = anchor.workspace.anchorAmm as Program<AnchorAmm>;
+
+  const authority = Keypair.generate();
+  const user = Keypair.generate();
+  const fee = 30;
+
+  let mintX: PublicKey;
+  let mintY: PublicKey;
+  let config: PublicKey;
+  let mintLp: PublicKey;
+  let userX: PublicKey;
+  let userY: PublicKey;
+  let userLp: PublicKey;
+
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  const vaults = () => ({
+    vaultX: getAssociatedTokenAddressSync(mintX, config, true),
+    vaultY: getAssociatedTokenAddressSync(mintY, config, true),
+  });
+
+  const liquidityAccounts = () => ({
+    user: user.publicKey,
+    mintLp,
+    ...vaults(),
+    userX,
+    userY,
+    userLp,
+    config,
+  });
+
+  const swap = (isX: boolean, amount: number, minOut: number) =>
+    program.methods
+      .swap(isX, new BN(amount), new BN(minOut))
+      .accountsPartial({
+        user: user.publicKey,
+        ...vaults(),
+        userX,
+        userY,
+        config,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [authority, user]) {
+      const sig = await connection.requestAirdrop(
+        kp.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig);
+    }
+
+    // Pools are keyed by the ordered pair, mint_x < mint_y
+    const mints = [
+      await createMint(connection, user, user.publicKey, null, 6),
+      await createMint(connection, user, user.publicKey, null, 6),
+    ].sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()));
+    [mintX, mintY] = mints;
+
+    [config] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), mintX.toBuffer(), mintY.toBuffer()],
+      program.programId
+    );
+    [mintLp] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp"), config.toBuffer()],
+      program.programId
+    );
+
+    userX = await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintX,
+      user.publicKey
+    );
+    userY = await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintY,
+      user.publicKey
+    );
+    await mintTo(connection, user, mintX, userX, user, 10_000_000);
+    await mintTo(connection, user, mintY, userY, user, 10_000_000);
+  });
+
+  it("Rejects mints out of order", async () => {
+    try {
+      await program.methods
+        .initialize(fee, authority.publicKey)
+        .accounts({ initializer: user.publicKey, mintX: mintY, mintY: mintX })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidMintOrder");
+    }
+  });
+
+  it("Initializes a pool", async () => {
+    // Someone creating a vault first doesn't block the pool
+    await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintX,
+      config,
+      undefined,
+      undefined,
+      undefined,
+      true
+    );
+
+    await program.methods
+      .initialize(fee, authority.publicKey)
+      .accounts({ initializer: user.publicKey, mintX, mintY })
+      .signers([user])
+      .rpc();
+
+    const state = await program.account.config.fetch(config);
+    assert.equal(state.fee, fee);
+    assert.isFalse(state.locked);
+    assert.ok(state.authority.equals(authority.publicKey));
+
+    userLp = await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintLp,
+      user.publicKey
+    );
+  });
+
+  it("Seeds the pool with the first deposit", async () => {
+    await program.methods
+      .deposit(new BN(1_000_000), new BN(1_000_000), new BN(4_000_000))
+      .accountsPartial(liquidityAccounts())
+      .signers([user])
+      .rpc();
+
+    const { vaultX, vaultY } = vaults();
+    assert.equal(await balance(vaultX), 1_000_000);
+    assert.equal(await balance(vaultY), 4_000_000);
+    assert.equal(await balance(userLp), 1_000_000);
+  });
+
+  it("Swaps x for y along x * y = k, less the fee", async () => {
+    const before = await balance(userY);
+    const amountIn = 10_000;
+    const afterFee = Math.floor((amountIn * (10_000 - fee)) / 10_000);
+    const expected = Math.floor(
+      (afterFee * 4_000_000) / (1_000_000 + afterFee)
+    );
+
+    await swap(true, amountIn, expected);
+    assert.equal((await balance(userY)) - before, expected);
+  });
+
+  it("Rejects a swap below the minimum out", async () => {
+    try {
+      await swap(false, 10_000, 10_000);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "SlippageExceeded");
+    }
+  });
+
+  it("Deposits in proportion to the reserves", async () => {
+    const { vaultX, vaultY } = vaults();
+    const [x, y] = [await balance(vaultX), await balance(vaultY)];
+
+    // Half the LP supply again needs half of each reserve, rounded up
+    await program.methods
+      .deposit(new BN(500_000), new BN(x), new BN(y))
+      .accountsPartial(liquidityAccounts())
+      .signers([user])
+      .rpc();
+
+    assert.equal(await balance(vaultX), x + Math.ceil(x / 2));
+    assert.equal(await balance(vaultY), y + Math.ceil(y / 2));
+    assert.equal(await balance(userLp), 1_500_000);
+  });
+
+  it("Only the authority can lock the pool", async () => {
+    try {
+      await program.methods
+        .setLocked(true)
+        .accounts({ authority: user.publicKey, config })
+        .signers([user])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+
+    await program.methods
+      .setLocked(true)
+      .accounts({ authority: authority.publicKey, config })
+      .signers([authority])
+      .rpc();
+
+    try {
+      await swap(true, 1_000, 0);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "PoolLocked");
+    }
+  });
+
+  it("Still lets LPs withdraw from a locked pool", async () => {
+    const { vaultX, vaultY } = vaults();
+    const [x, y] = [await balance(vaultX), await balance(vaultY)];
+
+    await program.methods
+      .withdraw(new BN(1_500_000), new BN(x), new BN(y))
+      .accountsPartial(liquidityAccounts())
+      .signers([user])
+      .rpc();
+
+    assert.equal(await balance(vaultX), 0);
+    assert.equal(await balance(vaultY), 0);
+    assert.equal(await balance(userLp), 0);
+  });
+});
@@ -0,0 +1,230 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+// Both AMMs take the same instructions: a 1-byte discriminator, Borsh args
+// and the same accounts in the same order. The Pinocchio AMM is loaded from
+// ../pinocchio-amm/target/deploy via Anchor.toml.
+const ANCHOR_AMM = new PublicKey(
+  "DE3XiAnbndLf6sRcrZ7Z8cdMyHzU9qL3gg6h1egsny8B"
+);
+const PINOCCHIO_AMM = new PublicKey(
+  "2m1VvswgrjLWy2Sa3UfeKHGGWs3HUskhMTwZRQjk1uVq"
+);
+
+const u16 = (value: number) => {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value);
+  return buf;
+};
+
+const u64 = (value: number) => {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(BigInt(value));
+  return buf;
+};
+
+describe("compute units: anchor vs pinocchio", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+
+  const user = Keypair.generate();
+  let mintX: PublicKey;
+  let mintY: PublicKey;
+  let userX: PublicKey;
+  let userY: PublicKey;
+
+  const usage: Record<string, Record<string, number>> = {};
+
+  const pool = (programId: PublicKey) => {
+    const [config] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config"), mintX.toBuffer(), mintY.toBuffer()],
+      programId
+    );
+    const [mintLp] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lp"), config.toBuffer()],
+      programId
+    );
+    return {
+      config,
+      mintLp,
+      vaultX: getAssociatedTokenAddressSync(mintX, config, true),
+      vaultY: getAssociatedTokenAddressSync(mintY, config, true),
+      userLp: getAssociatedTokenAddressSync(mintLp, user.publicKey),
+    };
+  };
+
+  const meta = (pubkey: PublicKey, isWritable = false, isSigner = false) => ({
+    pubkey,
+    isSigner,
+    isWritable,
+  });
+
+  // Send one instruction and record the compute units it consumed
+  const run = async (
+    programId: PublicKey,
+    name: string,
+    keys: ReturnType<typeof meta>[],
+    data: Buffer
+  ) => {
+    const tx = new Transaction().add(
+      new TransactionInstruction({ programId, keys, data })
+    );
+    const sig = await provider.sendAndConfirm(tx, [user]);
+    const result = await connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const label = programId.equals(ANCHOR_AMM) ? "anchor" : "pinocchio";
+    usage[name] = usage[name] ?? {};
+    usage[name][label] = result.meta.computeUnitsConsumed;
+  };
+
+  const exercise = async (programId: PublicKey) => {
+    const { config, mintLp, vaultX, vaultY, userLp } = pool(programId);
+
+    await run(
+      programId,
+      "initialize",
+      [
+        meta(user.publicKey, true, true),
+        meta(mintX),
+        meta(mintY),
+        meta(config, true),
+        meta(mintLp, true),
+        meta(vaultX, true),
+        meta(vaultY, true),
+        meta(TOKEN_PROGRAM_ID),
+        meta(ASSOCIATED_TOKEN_PROGRAM_ID),
+        meta(SystemProgram.programId),
+      ],
+      Buffer.concat([Buffer.from([0]), u16(30), user.publicKey.toBuffer()])
+    );
+
+    await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintLp,
+      user.publicKey
+    );
+
+    const liquidityKeys = [
+      meta(user.publicKey, false, true),
+      meta(mintLp, true),
+      meta(vaultX, true),
+      meta(vaultY, true),
+      meta(userX, true),
+      meta(userY, true),
+      meta(userLp, true),
+      meta(config),
+      meta(TOKEN_PROGRAM_ID),
+    ];
+
+    await run(
+      programId,
+      "deposit",
+      liquidityKeys,
+      Buffer.concat([
+        Buffer.from([1]),
+        u64(1_000_000),
+        u64(1_000_000),
+        u64(4_000_000),
+      ])
+    );
+
+    const before = Number((await getAccount(connection, userY)).amount);
+    await run(
+      programId,
+      "swap",
+      [
+        meta(user.publicKey, false, true),
+        meta(vaultX, true),
+        meta(vaultY, true),
+        meta(userX, true),
+        meta(userY, true),
+        meta(config),
+        meta(TOKEN_PROGRAM_ID),
+      ],
+      Buffer.concat([Buffer.from([3, 1]), u64(10_000), u64(0)])
+    );
+    const received =
+      Number((await getAccount(connection, userY)).amount) - before;
+
+    await run(
+      programId,
+      "withdraw",
+      liquidityKeys,
+      Buffer.concat([Buffer.from([2]), u64(1_000_000), u64(0), u64(0)])
+    );
+
+    await run(
+      programId,
+      "set_locked",
+      [meta(user.publicKey, false, true), meta(config, true)],
+      Buffer.from([4, 1])
+    );
+
+    return received;
+  };
+
+  before(async () => {
+    const sig = await connection.requestAirdrop(
+      user.publicKey,
+      10 * LAMPORTS_PER_SOL
+    );
+    await connection.confirmTransaction(sig);
+
+    [mintX, mintY] = [
+      await createMint(connection, user, user.publicKey, null, 6),
+      await createMint(connection, user, user.publicKey, null, 6),
+    ].sort((a, b) => Buffer.compare(a.toBuffer(), b.toBuffer()));
+
+    userX = await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintX,
+      user.publicKey
+    );
+    userY = await createAssociatedTokenAccount(
+      connection,
+      user,
+      mintY,
+      user.publicKey
+    );
+    await mintTo(connection, user, mintX, userX, user, 10_000_000);
+    await mintTo(connection, user, mintY, userY, user, 10_000_000);
+  });
+
+  it("Runs the same instructions on both programs", async () => {
+    const anchorOut = await exercise(ANCHOR_AMM);
+    const pinocchioOut = await exercise(PINOCCHIO_AMM);
+
+    // Identical pools and swaps must give identical results
+    assert.equal(pinocchioOut, anchorOut);
+  });
+
+  it("Pinocchio uses fewer compute units for every instruction", () => {
+    console.table(usage);
+
+    for (const [name, { anchor, pinocchio }] of Object.entries(usage)) {
+      assert.isBelow(pinocchio, anchor, name);
+    }
+  });
+});
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::error::BondingCurveError;
+
+/// Decimals of every token launched on a curve
+pub const TOKEN_DECIMALS: u8 = 6;
+/// Base units in one whole token; prices are quoted in lamports per whole token
+pub const TOKEN_UNIT: u128 = 10u128.pow(TOKEN_DECIMALS as u32);
+/// Fixed-point scale used for exponential growth
+const ONE: u128 = 1_000_000_000_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum CurveKind {
+    /// The price starts at `base_price` and rises by `slope` for every whole
+    /// token sold
+    Linear { base_price: u64, slope: u64 },
+    /// The price starts at `base_price` and grows by `growth_bps` for every
+    /// whole token sold
+    Exponential { base_price: u64, growth_bps: u16 },
+    /// x * y = k over `virtual_sol` and `virtual_tokens` reserves, as if a
+    /// pool had been seeded with them. The curve sells at most
+    /// `virtual_tokens` minus one base unit.
+    ConstantProduct {
+        virtual_sol: u64,
+        virtual_tokens: u64,
+    },
+}
+
+impl CurveKind {
+    pub fn validate(&self) -> Result<()> {
+        let valid = match *self {
+            Self::Linear { base_price, slope } => base_price > 0 || slope > 0,
+            Self::Exponential {
+                base_price,
+                growth_bps,
+            } => base_price > 0 && growth_bps > 0,
+            Self::ConstantProduct {
+                virtual_sol,
+                virtual_tokens,
+            } => virtual_sol > 0 && virtual_tokens > 0,
+        };
+        require!(valid, BondingCurveError::InvalidCurve);
+        Ok(())
+    }
+
+    /// Lamports the reserve holds once `supply` base units have been sold
+    ///
+    /// Buys pay and sells refund the difference between two points on this
+    /// function, so the reserve always equals `reserve_at(supply)` whatever
+    /// order trades happen in.
+    pub fn reserve_at(&self, supply: u64) -> Result<u64> {
+        let supply = supply as u128;
+        let reserve = match *self {
+            Self::Linear { base_price, slope } => {
+                // base * s + slope * s^2 / 2, with s in whole tokens
+                let base = mul_div(base_price as u128, supply, TOKEN_UNIT)?;
+                let ramp = mul_div(
+                    (slope as u128)
+                        .checked_mul(supply)
+                        .ok_or(BondingCurveError::Overflow)?,
+                    supply,
+                    2 * TOKEN_UNIT * TOKEN_UNIT,
+                )?;
+                base.checked_add(ramp).ok_or(BondingCurveError::Overflow)?
+            }
+            Self::Exponential {
+                base_price,
+                growth_bps,
+            } => {
+                // Each whole token costs `growth` times the one before, so the
+                // whole tokens sum to a geometric series. A partial token is
+                // priced like the next whole one.
+                let growth = ONE + growth_bps as u128 * (ONE / 10_000);
+                let whole = supply / TOKEN_UNIT;
+                let partial = supply % TOKEN_UNIT;
+
+                let scale = pow(growth, whole)?;
+                let series = mul_div(base_price as u128, scale - ONE, growth - ONE)?;
+                let next_price = mul_div(base_price as u128, scale, ONE)?;
+                series
+                    .checked_add(mul_div(next_price, partial, TOKEN_UNIT)?)
+                    .ok_or(BondingCurveError::Overflow)?
+            }
+            Self::ConstantProduct {
+                virtual_sol,
+                virtual_tokens,
+            } => {
+                // x0 * y0 / (y0 - s) - x0, which simplifies to x0 * s / (y0 - s)
+                let tokens_left = (virtual_tokens as u128)
+                    .checked_sub(supply)
+                    .filter(|left| *left > 0)
+                    .ok_or(BondingCurveError::SupplyExhausted)?;
+                mul_div(virtual_sol as u128, supply, tokens_left)?
+            }
+        };
+
+        u64::try_from(reserve).map_err(|_| BondingCurveError::Overflow.into())
+    }
+}
+
+fn mul_div(a: u128, b: u128, denominator: u128) -> Result<u128> {
+    a.checked_mul(b)
+        .map(|product| product / denominator)
+        .ok_or(BondingCurveError::Overflow.into())
+}
+
+/// `base ^ exp` in `ONE` fixed point, by repeated squaring
+fn pow(mut base: u128, mut exp: u128) -> Result<u128> {
+    let mut result = ONE;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_div(result, base, ONE)?;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = mul_div(base, base, ONE)?;
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum BondingCurveError {
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Curve parameters are out of range")]
+    InvalidCurve,
+    #[msg("Fee is above the maximum")]
+    InvalidFee,
+    #[msg("Graduation threshold must be greater than 0")]
+    InvalidThreshold,
+    #[msg("The curve has no more tokens to sell")]
+    SupplyExhausted,
+    #[msg("Price moved beyond the slippage limit")]
+    SlippageExceeded,
+    #[msg("The curve has graduated and no longer trades")]
+    CurveGraduated,
+    #[msg("The curve hasn't graduated yet")]
+    NotGraduated,
+    #[msg("Liquidity has already been released")]
+    LiquidityAlreadyReleased,
+    #[msg("Liquidity can only be released to the curve's destination")]
+    InvalidLiquidityDestination,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::error::BondingCurveError;
+use crate::state::BondingCurve;
+use crate::transfer::{mint_from_curve, pay_from_signer};
+
+#[derive(Accounts)]
+pub struct Buy<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump = curve.bump,
+        has_one = mint,
+        has_one = fee_recipient
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::RESERVE_SEED, curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    pub reserve: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub fee_recipient: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Buy<'info> {
+    pub fn buy(&mut self, amount: u64, max_sol_cost: u64) -> Result<()> {
+        require_gt!(amount, 0, BondingCurveError::InvalidAmount);
+        require!(!self.curve.graduated, BondingCurveError::CurveGraduated);
+
+        let supply = self
+            .curve
+            .supply
+            .checked_add(amount)
+            .ok_or(BondingCurveError::Overflow)?;
+        let reserve_lamports = self.curve.kind.reserve_at(supply)?;
+        let cost = reserve_lamports
+            .checked_sub(self.curve.reserve_lamports)
+            .ok_or(BondingCurveError::Overflow)?;
+        let fee = self.curve.fee_on(cost)?;
+        let total = cost.checked_add(fee).ok_or(BondingCurveError::Overflow)?;
+        require_gte!(max_sol_cost, total, BondingCurveError::SlippageExceeded);
+
+        pay_from_signer(
+            &self.buyer,
+            self.reserve.to_account_info(),
+            &self.system_program,
+            cost,
+        )?;
+        pay_from_signer(
+            &self.buyer,
+            self.fee_recipient.to_account_info(),
+            &self.system_program,
+            fee,
+        )?;
+        mint_from_curve(
+            &self.curve,
+            &self.mint,
+            &self.buyer_token_account,
+            &self.token_program,
+            amount,
+        )?;
+
+        let curve = &mut self.curve;
+        curve.supply = supply;
+        curve.reserve_lamports = reserve_lamports;
+        msg!(
+            "{} bought {} for {} lamports",
+            self.buyer.key(),
+            amount,
+            total
+        );
+
+        if curve.reserve_lamports >= curve.graduation_threshold {
+            curve.graduated = true;
+            msg!("Curve graduated with {} lamports", curve.reserve_lamports);
+        }
+
+        Ok(())
+    }
+}
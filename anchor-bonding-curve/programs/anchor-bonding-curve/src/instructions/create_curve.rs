@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::curve::TOKEN_DECIMALS;
+use crate::state::{BondingCurve, CurveParams};
+
+#[derive(Accounts)]
+#[instruction(curve_id: u64)]
+pub struct CreateCurve<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // The mint is its own mint authority, so only this program can mint
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = TOKEN_DECIMALS,
+        mint::authority = mint,
+        mint::token_program = token_program,
+        seeds = [
+            BondingCurve::MINT_SEED,
+            creator.key().as_ref(),
+            curve_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BondingCurve::INIT_SPACE,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::RESERVE_SEED, curve.key().as_ref()],
+        bump
+    )]
+    pub reserve: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateCurve<'info> {
+    pub fn create_curve(
+        &mut self,
+        curve_id: u64,
+        params: CurveParams,
+        bumps: &CreateCurveBumps,
+    ) -> Result<()> {
+        params.validate()?;
+
+        // Fund the reserve's rent up front so the first buy can be any size
+        let rent = Rent::get()?.minimum_balance(0);
+        let cpi_accounts = Transfer {
+            from: self.creator.to_account_info(),
+            to: self.reserve.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, rent)?;
+
+        self.curve.set_inner(BondingCurve {
+            creator: self.creator.key(),
+            curve_id,
+            mint: self.mint.key(),
+            kind: params.kind,
+            fee_bps: params.fee_bps,
+            fee_recipient: params.fee_recipient,
+            graduation_threshold: params.graduation_threshold,
+            liquidity_tokens: params.liquidity_tokens,
+            liquidity_destination: params.liquidity_destination,
+            supply: 0,
+            reserve_lamports: 0,
+            graduated: false,
+            liquidity_released: false,
+            bump: bumps.curve,
+            mint_bump: bumps.mint,
+            reserve_bump: bumps.reserve,
+        });
+
+        msg!("Curve created for mint {}", self.mint.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+pub mod buy;
+pub mod create_curve;
+pub mod release_liquidity;
+pub mod sell;
+
+pub use buy::*;
+pub use create_curve::*;
+pub use release_liquidity::*;
+pub use sell::*;
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, spl_token_2022::instruction::AuthorityType, Mint, SetAuthority, TokenAccount,
+    TokenInterface,
+};
+
+use crate::error::BondingCurveError;
+use crate::state::BondingCurve;
+use crate::transfer::{mint_from_curve, pay_from_reserve};
+
+#[derive(Accounts)]
+pub struct ReleaseLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump = curve.bump,
+        has_one = mint,
+        has_one = liquidity_destination @ BondingCurveError::InvalidLiquidityDestination
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::RESERVE_SEED, curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    pub reserve: SystemAccount<'info>,
+
+    /// CHECK: Receives the reserve's SOL; fixed when the curve was created
+    /// and may be a program-owned pool PDA
+    #[account(mut)]
+    pub liquidity_destination: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = liquidity_destination,
+        token::token_program = token_program,
+    )]
+    pub liquidity_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReleaseLiquidity<'info> {
+    pub fn release_liquidity(&mut self) -> Result<()> {
+        require!(self.curve.graduated, BondingCurveError::NotGraduated);
+        require!(
+            !self.curve.liquidity_released,
+            BondingCurveError::LiquidityAlreadyReleased
+        );
+
+        let lamports = self.curve.reserve_lamports;
+        pay_from_reserve(
+            &self.curve,
+            &self.reserve,
+            self.liquidity_destination.to_account_info(),
+            &self.system_program,
+            lamports,
+        )?;
+
+        if self.curve.liquidity_tokens > 0 {
+            mint_from_curve(
+                &self.curve,
+                &self.mint,
+                &self.liquidity_token_account,
+                &self.token_program,
+                self.curve.liquidity_tokens,
+            )?;
+        }
+
+        // Give up the mint authority so the supply is fixed from here on
+        let curve_id = self.curve.curve_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            BondingCurve::MINT_SEED,
+            self.curve.creator.as_ref(),
+            &curve_id,
+            &[self.curve.mint_bump],
+        ]];
+        let cpi_accounts = SetAuthority {
+            current_authority: self.mint.to_account_info(),
+            account_or_mint: self.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+        self.curve.reserve_lamports = 0;
+        self.curve.liquidity_released = true;
+        msg!(
+            "Released {} lamports and {} tokens to {}",
+            lamports,
+            self.curve.liquidity_tokens,
+            self.liquidity_destination.key()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::error::BondingCurveError;
+use crate::state::BondingCurve;
+use crate::transfer::pay_from_reserve;
+
+#[derive(Accounts)]
+pub struct Sell<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::SEED_PREFIX, mint.key().as_ref()],
+        bump = curve.bump,
+        has_one = mint,
+        has_one = fee_recipient
+    )]
+    pub curve: Account<'info, BondingCurve>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BondingCurve::RESERVE_SEED, curve.key().as_ref()],
+        bump = curve.reserve_bump
+    )]
+    pub reserve: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub fee_recipient: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Sell<'info> {
+    pub fn sell(&mut self, amount: u64, min_sol_out: u64) -> Result<()> {
+        require_gt!(amount, 0, BondingCurveError::InvalidAmount);
+        require!(!self.curve.graduated, BondingCurveError::CurveGraduated);
+
+        let supply = self
+            .curve
+            .supply
+            .checked_sub(amount)
+            .ok_or(BondingCurveError::InvalidAmount)?;
+        let reserve_lamports = self.curve.kind.reserve_at(supply)?;
+        let refund = self
+            .curve
+            .reserve_lamports
+            .checked_sub(reserve_lamports)
+            .ok_or(BondingCurveError::Overflow)?;
+        let fee = self.curve.fee_on(refund)?;
+        let proceeds = refund - fee;
+        require_gte!(proceeds, min_sol_out, BondingCurveError::SlippageExceeded);
+
+        let cpi_accounts = Burn {
+            mint: self.mint.to_account_info(),
+            from: self.seller_token_account.to_account_info(),
+            authority: self.seller.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token_interface::burn(cpi_ctx, amount)?;
+
+        pay_from_reserve(
+            &self.curve,
+            &self.reserve,
+            self.seller.to_account_info(),
+            &self.system_program,
+            proceeds,
+        )?;
+        pay_from_reserve(
+            &self.curve,
+            &self.reserve,
+            self.fee_recipient.to_account_info(),
+            &self.system_program,
+            fee,
+        )?;
+
+        self.curve.supply = supply;
+        self.curve.reserve_lamports = reserve_lamports;
+        msg!(
+            "{} sold {} for {} lamports",
+            self.seller.key(),
+            amount,
+            proceeds
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+pub mod curve;
+pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod transfer;
+
+use instructions::*;
+use state::CurveParams;
+
+declare_id!("9xeLGvMAkaB17te76qktfCq6uyZTQsP5yvydynwM7LAJ");
+
+#[program]
+pub mod anchor_bonding_curve {
+    use super::*;
+
+    /// Launch a token whose mint authority is the program, priced by a curve
+    pub fn create_curve(
+        ctx: Context<CreateCurve>,
+        curve_id: u64,
+        params: CurveParams,
+    ) -> Result<()> {
+        ctx.accounts.create_curve(curve_id, params, &ctx.bumps)
+    }
+
+    /// Mint `amount` tokens for SOL paid into the reserve, plus the fee
+    pub fn buy(ctx: Context<Buy>, amount: u64, max_sol_cost: u64) -> Result<()> {
+        ctx.accounts.buy(amount, max_sol_cost)
+    }
+
+    /// Burn `amount` tokens for SOL out of the reserve, less the fee
+    pub fn sell(ctx: Context<Sell>, amount: u64, min_sol_out: u64) -> Result<()> {
+        ctx.accounts.sell(amount, min_sol_out)
+    }
+
+    /// Anyone, after graduation: move the reserve and the liquidity
+    /// allocation to the destination fixed at creation and fix the token
+    /// supply
+    pub fn release_liquidity(ctx: Context<ReleaseLiquidity>) -> Result<()> {
+        ctx.accounts.release_liquidity()
+    }
+}
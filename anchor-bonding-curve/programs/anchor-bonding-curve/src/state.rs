@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::curve::CurveKind;
+use crate::error::BondingCurveError;
+
+/// Upper bound on the trading fee, 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+#[account]
+#[derive(InitSpace)]
+pub struct BondingCurve {
+    pub creator: Pubkey,
+    pub curve_id: u64,
+    pub mint: Pubkey,
+    pub kind: CurveKind,
+    /// Fee on the SOL side of every buy and sell, paid to `fee_recipient`
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    /// Reserve size at which the curve graduates and stops trading
+    pub graduation_threshold: u64,
+    /// Tokens minted alongside the reserve when liquidity is released
+    pub liquidity_tokens: u64,
+    /// Only account `release_liquidity` pays out to, e.g. an AMM pool PDA
+    pub liquidity_destination: Pubkey,
+    /// Tokens sold by the curve and still in circulation
+    pub supply: u64,
+    /// SOL backing `supply`, excluding the reserve account's rent
+    pub reserve_lamports: u64,
+    pub graduated: bool,
+    pub liquidity_released: bool,
+    pub bump: u8,
+    pub mint_bump: u8,
+    pub reserve_bump: u8,
+}
+
+impl BondingCurve {
+    pub const SEED_PREFIX: &'static [u8] = b"curve";
+    pub const MINT_SEED: &'static [u8] = b"mint";
+    pub const RESERVE_SEED: &'static [u8] = b"reserve";
+
+    pub fn fee_on(&self, amount: u64) -> Result<u64> {
+        let fee = amount as u128 * self.fee_bps as u128 / 10_000;
+        u64::try_from(fee).map_err(|_| BondingCurveError::Overflow.into())
+    }
+}
+
+/// Settings chosen by the creator when launching a curve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct CurveParams {
+    pub kind: CurveKind,
+    pub fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub graduation_threshold: u64,
+    pub liquidity_tokens: u64,
+    pub liquidity_destination: Pubkey,
+}
+
+impl CurveParams {
+    pub fn validate(&self) -> Result<()> {
+        self.kind.validate()?;
+        require!(self.fee_bps <= MAX_FEE_BPS, BondingCurveError::InvalidFee);
+        require_gt!(
+            self.graduation_threshold,
+            0,
+            BondingCurveError::InvalidThreshold
+        );
+        Ok(())
+    }
+}
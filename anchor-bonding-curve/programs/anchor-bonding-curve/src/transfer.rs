@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::state::BondingCurve;
+
+/// Mint `amount` of the curve's token to `to`, signed by the mint PDA
+pub fn mint_from_curve<'info>(
+    curve: &BondingCurve,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let curve_id = curve.curve_id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        BondingCurve::MINT_SEED,
+        curve.creator.as_ref(),
+        &curve_id,
+        &[curve.mint_bump],
+    ]];
+
+    let cpi_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: mint.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token_interface::mint_to(cpi_ctx, amount)
+}
+
+/// Pay `amount` lamports out of the reserve, signed by the reserve PDA
+pub fn pay_from_reserve<'info>(
+    curve: &Account<'info, BondingCurve>,
+    reserve: &SystemAccount<'info>,
+    to: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let curve_key = curve.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        BondingCurve::RESERVE_SEED,
+        curve_key.as_ref(),
+        &[curve.reserve_bump],
+    ]];
+
+    let cpi_accounts = Transfer {
+        from: reserve.to_account_info(),
+        to,
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer_seeds);
+    system_program::transfer(cpi_ctx, amount)
+}
+
+/// Pay `amount` lamports from a signer
+pub fn pay_from_signer<'info>(
+    from: &Signer<'info>,
+    to: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = Transfer {
+        from: from.to_account_info(),
+        to,
+    };
+    let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+    system_program::transfer(cpi_ctx, amount)
+}
@@ -0,0 +1,256 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getMint,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { AnchorBondingCurve } from "../target/types/anchor_bonding_curve";
+
+// Tokens have 6 decimals; prices are in lamports per whole token
+const TOKEN = 1_000_000;
+
+describe("anchor-bonding-curve", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const program = anchor.workspace
+    .anchorBondingCurve as Program<AnchorBondingCurve>;
+
+  const creator = Keypair.generate();
+  const buyer = Keypair.generate();
+  // Fixed at creation as the only account liquidity can be released to
+  const destination = Keypair.generate();
+  let nextCurveId = 0;
+
+  const pdas = (curveId: number) => {
+    const [mint] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("mint"),
+        creator.publicKey.toBuffer(),
+        new BN(curveId).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    const [curve] = PublicKey.findProgramAddressSync(
+      [Buffer.from("curve"), mint.toBuffer()],
+      program.programId
+    );
+    const [reserve] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reserve"), curve.toBuffer()],
+      program.programId
+    );
+    return { mint, curve, reserve };
+  };
+
+  const createCurve = async (kind: any, feeBps = 0, threshold = 1_000) => {
+    const curveId = nextCurveId++;
+    await program.methods
+      .createCurve(new BN(curveId), {
+        kind,
+        feeBps,
+        feeRecipient: creator.publicKey,
+        graduationThreshold: new BN(threshold * LAMPORTS_PER_SOL),
+        liquidityTokens: new BN(100 * TOKEN),
+        liquidityDestination: destination.publicKey,
+      })
+      .accounts({ creator: creator.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+      .signers([creator])
+      .rpc();
+    return pdas(curveId);
+  };
+
+  const buy = (mint: PublicKey, amount: number, maxCost: number) =>
+    program.methods
+      .buy(new BN(amount), new BN(maxCost))
+      .accounts({
+        buyer: buyer.publicKey,
+        mint,
+        feeRecipient: creator.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+
+  const sell = (mint: PublicKey, amount: number, minOut: number) =>
+    program.methods
+      .sell(new BN(amount), new BN(minOut))
+      .accounts({
+        seller: buyer.publicKey,
+        mint,
+        feeRecipient: creator.publicKey,
+        sellerTokenAccount: getAssociatedTokenAddressSync(
+          mint,
+          buyer.publicKey
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([buyer])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [creator, buyer]) {
+      const sig = await connection.requestAirdrop(
+        kp.publicKey,
+        10 * LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig);
+    }
+  });
+
+  describe("linear curve", () => {
+    // 0.01 SOL for the first token, 0.001 SOL more for each one after
+    const linear = {
+      linear: {
+        basePrice: new BN(LAMPORTS_PER_SOL / 100),
+        slope: new BN(LAMPORTS_PER_SOL / 1_000),
+      },
+    };
+    let accounts: ReturnType<typeof pdas>;
+
+    before(async () => {
+      accounts = await createCurve(linear, 100, 1);
+    });
+
+    it("Buys along the curve and charges the fee", async () => {
+      const feesBefore = await connection.getBalance(creator.publicKey);
+
+      // 10 tokens: 0.1 SOL base + 0.05 SOL slope
+      const cost = 150_000_000;
+      await buy(accounts.mint, 10 * TOKEN, cost + cost / 100);
+
+      const curve = await program.account.bondingCurve.fetch(accounts.curve);
+      assert.equal(curve.supply.toNumber(), 10 * TOKEN);
+      assert.equal(curve.reserveLamports.toNumber(), cost);
+      assert.equal(
+        (await connection.getBalance(creator.publicKey)) - feesBefore,
+        cost / 100
+      );
+
+      const tokens = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(accounts.mint, buyer.publicKey)
+      );
+      assert.equal(Number(tokens.amount), 10 * TOKEN);
+    });
+
+    it("Rejects a buy above the slippage limit", async () => {
+      try {
+        await buy(accounts.mint, TOKEN, 1);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "SlippageExceeded");
+      }
+    });
+
+    it("Sells back down the curve", async () => {
+      await sell(accounts.mint, 5 * TOKEN, 0);
+
+      // Back to 5 tokens: 0.05 SOL base + 0.0125 SOL slope
+      const curve = await program.account.bondingCurve.fetch(accounts.curve);
+      assert.equal(curve.supply.toNumber(), 5 * TOKEN);
+      assert.equal(curve.reserveLamports.toNumber(), 62_500_000);
+    });
+
+    it("Graduates, freezes trading and releases liquidity", async () => {
+      // 40 tokens hold 1.2 SOL, over the 1 SOL threshold
+      await buy(accounts.mint, 35 * TOKEN, 2 * LAMPORTS_PER_SOL);
+      const curve = await program.account.bondingCurve.fetch(accounts.curve);
+      assert.isTrue(curve.graduated);
+
+      try {
+        await sell(accounts.mint, TOKEN, 0);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "CurveGraduated");
+      }
+
+      const release = async (to: PublicKey) =>
+        program.methods
+          .releaseLiquidity()
+          .accountsPartial({
+            curve: accounts.curve,
+            mint: accounts.mint,
+            liquidityDestination: to,
+            liquidityTokenAccount: await createAssociatedTokenAccount(
+              connection,
+              creator,
+              accounts.mint,
+              to
+            ),
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+
+      // Anyone can release, but only to the destination fixed at creation
+      try {
+        await release(Keypair.generate().publicKey);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidLiquidityDestination");
+      }
+
+      const sig = await connection.requestAirdrop(
+        destination.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig);
+      await release(destination.publicKey);
+      const liquidityTokenAccount = getAssociatedTokenAddressSync(
+        accounts.mint,
+        destination.publicKey
+      );
+
+      assert.equal(
+        await connection.getBalance(destination.publicKey),
+        LAMPORTS_PER_SOL + 1_200_000_000
+      );
+      const liquidity = await getAccount(connection, liquidityTokenAccount);
+      assert.equal(Number(liquidity.amount), 100 * TOKEN);
+      const mint = await getMint(connection, accounts.mint);
+      assert.isNull(mint.mintAuthority);
+    });
+  });
+
+  it("Prices an exponential curve token by token", async () => {
+    const base = LAMPORTS_PER_SOL / 100;
+    const { mint, curve } = await createCurve({
+      exponential: { basePrice: new BN(base), growthBps: 100 },
+    });
+
+    await buy(mint, TOKEN, base);
+    // The second token costs 1% more than the first
+    await buy(mint, TOKEN, base + base / 100);
+
+    const state = await program.account.bondingCurve.fetch(curve);
+    assert.equal(state.reserveLamports.toNumber(), 2 * base + base / 100);
+  });
+
+  it("Prices a constant-product curve from its virtual reserves", async () => {
+    const { mint, curve } = await createCurve({
+      constantProduct: {
+        virtualSol: new BN(30 * LAMPORTS_PER_SOL),
+        virtualTokens: new BN(1_000_000 * TOKEN),
+      },
+    });
+
+    // x0 * s / (y0 - s) for 1000 of the 1,000,000 virtual tokens
+    const cost = Math.floor((30 * LAMPORTS_PER_SOL * 1_000) / 999_000);
+    await buy(mint, 1_000 * TOKEN, cost);
+
+    const state = await program.account.bondingCurve.fetch(curve);
+    assert.equal(state.reserveLamports.toNumber(), cost);
+
+    try {
+      await buy(mint, 1_000_000 * TOKEN, LAMPORTS_PER_SOL);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "SupplyExhausted");
+    }
+  });
+});
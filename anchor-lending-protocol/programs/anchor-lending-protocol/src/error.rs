@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Only the market authority can do this")]
+    Unauthorized,
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Invalid reserve config")]
+    InvalidReserveConfig,
+    #[msg("Math overflow occurred")]
+    MathOverflow,
+    #[msg("Invalid price from oracle")]
+    InvalidPrice,
+    #[msg("Invalid timestamp")]
+    InvalidTimestamp,
+    #[msg("Oracle price is too old")]
+    StalePrice,
+    #[msg("Collateral and borrow reserves must be distinct reserves of one market")]
+    InvalidReservePair,
+    #[msg("Not enough liquidity in the reserve")]
+    InsufficientLiquidity,
+    #[msg("Insufficient collateral")]
+    InsufficientCollateral,
+    #[msg("Exceeds maximum LTV ratio")]
+    ExceedsMaxLtv,
+    #[msg("No debt to repay")]
+    NothingToRepay,
+    #[msg("Position not liquidatable - health factor above threshold")]
+    PositionNotLiquidatable,
+    #[msg("Liquidation repays more than the close factor allows")]
+    ExceedsCloseFactor,
+    #[msg("Mints with a transfer fee can't be listed")]
+    UnsupportedMint,
+}
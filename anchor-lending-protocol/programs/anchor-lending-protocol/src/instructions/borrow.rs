@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{MockOracle, Obligation, Reserve};
+use crate::transfer::pay_from_vault;
+use crate::utils::calculate_max_borrowable_amount;
+
+#[derive(Accounts)]
+pub struct Borrow<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            Obligation::SEED_PREFIX,
+            collateral_reserve.key().as_ref(),
+            borrow_reserve.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = obligation.bump
+    )]
+    pub obligation: Box<Account<'info, Obligation>>,
+
+    #[account(mut)]
+    pub collateral_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = collateral_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub collateral_oracle: Box<Account<'info, MockOracle>>,
+
+    #[account(mut, has_one = mint)]
+    pub borrow_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = borrow_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub borrow_oracle: Box<Account<'info, MockOracle>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::VAULT_SEED, borrow_reserve.key().as_ref()],
+        bump = borrow_reserve.vault_bump
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Borrow<'info> {
+    pub fn borrow(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, ErrorCode::InvalidAmount);
+        require_gte!(
+            self.borrow_reserve.available_liquidity,
+            amount,
+            ErrorCode::InsufficientLiquidity
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.collateral_reserve.accrue(now)?;
+        self.borrow_reserve.accrue(now)?;
+
+        let obligation = &mut self.obligation;
+        obligation.sync_debt(&self.borrow_reserve)?;
+        let debt = obligation
+            .borrowed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let (collateral_value, debt_value) = obligation.values(
+            &self.collateral_reserve,
+            &self.collateral_oracle,
+            &self.borrow_oracle,
+            debt,
+            now,
+        )?;
+        let max_borrowable = calculate_max_borrowable_amount(
+            collateral_value,
+            self.collateral_reserve.config.max_ltv_bps,
+        )?;
+        require_gte!(max_borrowable, debt_value, ErrorCode::ExceedsMaxLtv);
+        obligation.borrowed_amount = debt;
+
+        pay_from_vault(
+            &self.borrow_reserve,
+            &self.mint,
+            &self.vault,
+            &self.user_token_account,
+            &self.token_program,
+            amount,
+        )?;
+
+        let reserve = &mut self.borrow_reserve;
+        reserve.available_liquidity -= amount;
+        reserve.borrowed_amount = reserve
+            .borrowed_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("{} borrowed {}, debt {}", self.owner.key(), amount, debt);
+        Ok(())
+    }
+}
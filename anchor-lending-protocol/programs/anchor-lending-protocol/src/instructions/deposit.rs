@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::Reserve;
+use crate::transfer::{deposit_to_vault, mint_receipts};
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::SEED_PREFIX, reserve.market.as_ref(), mint.key().as_ref()],
+        bump = reserve.bump,
+        has_one = mint
+    )]
+    pub reserve: Box<Account<'info, Reserve>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::VAULT_SEED, reserve.key().as_ref()],
+        bump = reserve.vault_bump
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::RECEIPT_MINT_SEED, reserve.key().as_ref()],
+        bump = reserve.receipt_mint_bump
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, ErrorCode::InvalidAmount);
+
+        // Price receipts against the liquidity including interest up to now
+        self.reserve.accrue(Clock::get()?.unix_timestamp)?;
+        let receipt_amount = self.reserve.liquidity_to_receipts(amount)?;
+        require_gt!(receipt_amount, 0, ErrorCode::InvalidAmount);
+
+        deposit_to_vault(
+            &self.mint,
+            &self.user_token_account,
+            &self.vault,
+            &self.user,
+            &self.token_program,
+            amount,
+        )?;
+        mint_receipts(
+            &self.reserve,
+            &self.receipt_mint,
+            &self.user_receipt_account,
+            &self.token_program,
+            receipt_amount,
+        )?;
+
+        let reserve = &mut self.reserve;
+        reserve.available_liquidity = reserve
+            .available_liquidity
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        reserve.receipt_supply = reserve
+            .receipt_supply
+            .checked_add(receipt_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "{} deposited {} for {} receipt tokens",
+            self.user.key(),
+            amount,
+            receipt_amount
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{Obligation, Reserve};
+use crate::transfer::deposit_to_vault;
+
+#[derive(Accounts)]
+pub struct DepositCollateral<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub collateral_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(
+        constraint = borrow_reserve.market == collateral_reserve.market
+            && borrow_reserve.key() != collateral_reserve.key()
+            @ ErrorCode::InvalidReservePair
+    )]
+    pub borrow_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Obligation::INIT_SPACE,
+        seeds = [
+            Obligation::SEED_PREFIX,
+            collateral_reserve.key().as_ref(),
+            borrow_reserve.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub obligation: Box<Account<'info, Obligation>>,
+
+    #[account(
+        seeds = [Reserve::RECEIPT_MINT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.receipt_mint_bump
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::COLLATERAL_VAULT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.collateral_vault_bump
+    )]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositCollateral<'info> {
+    pub fn deposit_collateral(
+        &mut self,
+        receipt_amount: u64,
+        bumps: &DepositCollateralBumps,
+    ) -> Result<()> {
+        require_gt!(receipt_amount, 0, ErrorCode::InvalidAmount);
+
+        let obligation = &mut self.obligation;
+        if obligation.owner == Pubkey::default() {
+            obligation.owner = self.owner.key();
+            obligation.collateral_reserve = self.collateral_reserve.key();
+            obligation.borrow_reserve = self.borrow_reserve.key();
+            obligation.borrow_index = self.borrow_reserve.borrow_index;
+            obligation.bump = bumps.obligation;
+        }
+
+        deposit_to_vault(
+            &self.receipt_mint,
+            &self.user_receipt_account,
+            &self.collateral_vault,
+            &self.owner,
+            &self.token_program,
+            receipt_amount,
+        )?;
+
+        let obligation = &mut self.obligation;
+        obligation.collateral_amount = obligation
+            .collateral_amount
+            .checked_add(receipt_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "{} locked {} receipt tokens as collateral",
+            self.owner.key(),
+            receipt_amount
+        );
+        Ok(())
+    }
+}
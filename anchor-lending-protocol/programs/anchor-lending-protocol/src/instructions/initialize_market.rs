@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Market;
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Market::INIT_SPACE,
+        seeds = [Market::SEED_PREFIX, authority.key().as_ref()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMarket<'info> {
+    pub fn initialize_market(&mut self, bumps: &InitializeMarketBumps) -> Result<()> {
+        self.market.set_inner(Market {
+            authority: self.authority.key(),
+            bump: bumps.market,
+        });
+
+        Ok(())
+    }
+}
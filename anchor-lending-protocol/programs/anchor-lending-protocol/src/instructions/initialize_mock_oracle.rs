@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::error::ErrorCode;
+use crate::state::MockOracle;
+
+#[derive(Accounts)]
+pub struct InitializeMockOracle<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MockOracle::INIT_SPACE,
+        seeds = [
+            MockOracle::SEED_PREFIX,
+            authority.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub oracle: Account<'info, MockOracle>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMockOracle<'info> {
+    pub fn initialize_mock_oracle(
+        &mut self,
+        price: i64,
+        exponent: i32,
+        bumps: &InitializeMockOracleBumps,
+    ) -> Result<()> {
+        require_gt!(price, 0, ErrorCode::InvalidPrice);
+
+        self.oracle.set_inner(MockOracle {
+            authority: self.authority.key(),
+            mint: self.mint.key(),
+            price,
+            exponent,
+            last_updated: Clock::get()?.unix_timestamp,
+            bump: bumps.oracle,
+        });
+
+        Ok(())
+    }
+}
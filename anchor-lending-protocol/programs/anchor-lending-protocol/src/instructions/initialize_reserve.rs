@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{Market, MockOracle, Reserve, ReserveConfig, INDEX_PRECISION};
+
+#[derive(Accounts)]
+pub struct InitializeReserve<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Market::SEED_PREFIX, authority.key().as_ref()],
+        bump = market.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(constraint = oracle.mint == mint.key() @ ErrorCode::InvalidPrice)]
+    pub oracle: Account<'info, MockOracle>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Reserve::INIT_SPACE,
+        seeds = [Reserve::SEED_PREFIX, market.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub reserve: Box<Account<'info, Reserve>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [Reserve::VAULT_SEED, reserve.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = reserve,
+        token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [Reserve::RECEIPT_MINT_SEED, reserve.key().as_ref()],
+        bump,
+        mint::decimals = mint.decimals,
+        mint::authority = reserve,
+        mint::token_program = token_program,
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [Reserve::COLLATERAL_VAULT_SEED, reserve.key().as_ref()],
+        bump,
+        token::mint = receipt_mint,
+        token::authority = reserve,
+        token::token_program = token_program,
+    )]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeReserve<'info> {
+    pub fn initialize_reserve(
+        &mut self,
+        config: ReserveConfig,
+        bumps: &InitializeReserveBumps,
+    ) -> Result<()> {
+        config.validate()?;
+        // The reserve books deposits and repayments at their face amount,
+        // which a transfer fee would leave the vault short of
+        require!(
+            !has_transfer_fee(&self.mint.to_account_info())?,
+            ErrorCode::UnsupportedMint
+        );
+
+        self.reserve.set_inner(Reserve {
+            market: self.market.key(),
+            mint: self.mint.key(),
+            oracle: self.oracle.key(),
+            config,
+            available_liquidity: 0,
+            borrowed_amount: 0,
+            borrow_index: INDEX_PRECISION,
+            receipt_supply: 0,
+            last_update: Clock::get()?.unix_timestamp,
+            bump: bumps.reserve,
+            vault_bump: bumps.vault,
+            receipt_mint_bump: bumps.receipt_mint,
+            collateral_vault_bump: bumps.collateral_vault,
+        });
+
+        Ok(())
+    }
+}
+
+fn has_transfer_fee(mint: &AccountInfo) -> Result<bool> {
+    if *mint.owner != spl_token_2022::ID {
+        return Ok(false);
+    }
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(mint.get_extension::<TransferFeeConfig>().is_ok())
+}
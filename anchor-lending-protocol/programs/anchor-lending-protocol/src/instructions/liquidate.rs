@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{MockOracle, Obligation, Reserve, BPS, CLOSE_FACTOR_BPS};
+use crate::transfer::{deposit_to_vault, pay_from_vault};
+use crate::utils::{
+    calculate_collateral_value, calculate_health_factor, calculate_liquidation_amounts,
+};
+
+#[derive(Accounts)]
+pub struct Liquidate<'info> {
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+
+    #[account(mut, has_one = collateral_reserve, has_one = borrow_reserve)]
+    pub obligation: Box<Account<'info, Obligation>>,
+
+    #[account(mut)]
+    pub collateral_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = collateral_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub collateral_oracle: Box<Account<'info, MockOracle>>,
+
+    #[account(mut, has_one = mint)]
+    pub borrow_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = borrow_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub borrow_oracle: Box<Account<'info, MockOracle>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::VAULT_SEED, borrow_reserve.key().as_ref()],
+        bump = borrow_reserve.vault_bump
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = liquidator,
+        token::token_program = token_program,
+    )]
+    pub liquidator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [Reserve::RECEIPT_MINT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.receipt_mint_bump
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::COLLATERAL_VAULT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.collateral_vault_bump
+    )]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = liquidator,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = liquidator,
+        associated_token::token_program = token_program,
+    )]
+    pub liquidator_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Liquidate<'info> {
+    pub fn liquidate(&mut self, repay_amount: u64) -> Result<()> {
+        require_gt!(repay_amount, 0, ErrorCode::InvalidAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        self.collateral_reserve.accrue(now)?;
+        self.borrow_reserve.accrue(now)?;
+
+        let obligation = &mut self.obligation;
+        obligation.sync_debt(&self.borrow_reserve)?;
+        let debt = obligation.borrowed_amount;
+        require_gt!(debt, 0, ErrorCode::NothingToRepay);
+
+        let (collateral_value, debt_value) = obligation.values(
+            &self.collateral_reserve,
+            &self.collateral_oracle,
+            &self.borrow_oracle,
+            debt,
+            now,
+        )?;
+        let health_factor = calculate_health_factor(
+            collateral_value,
+            debt_value,
+            self.collateral_reserve.config.liquidation_ltv_bps,
+        )?;
+        require_gt!(
+            BPS,
+            health_factor as u64,
+            ErrorCode::PositionNotLiquidatable
+        );
+
+        let max_repay = (debt as u128 * CLOSE_FACTOR_BPS as u128 / BPS as u128) as u64;
+        require_gte!(max_repay, repay_amount, ErrorCode::ExceedsCloseFactor);
+
+        // Collateral worth the repaid debt plus the liquidation bonus,
+        // capped at everything the obligation has locked
+        let (price, exponent) = self.borrow_oracle.price(now)?;
+        let repay_value = calculate_collateral_value(repay_amount, price, exponent)?;
+        let (price, exponent) = self.collateral_oracle.price(now)?;
+        let collateral = self
+            .collateral_reserve
+            .receipts_to_liquidity(obligation.collateral_amount)?;
+        let (seized, _) = calculate_liquidation_amounts(
+            repay_value,
+            collateral,
+            self.collateral_reserve.config.liquidation_bonus_bps,
+            price,
+            exponent,
+        )?;
+        let seized_receipts = self
+            .collateral_reserve
+            .liquidity_to_receipts(seized)?
+            .min(obligation.collateral_amount);
+
+        deposit_to_vault(
+            &self.mint,
+            &self.liquidator_token_account,
+            &self.vault,
+            &self.liquidator,
+            &self.token_program,
+            repay_amount,
+        )?;
+        pay_from_vault(
+            &self.collateral_reserve,
+            &self.receipt_mint,
+            &self.collateral_vault,
+            &self.liquidator_receipt_account,
+            &self.token_program,
+            seized_receipts,
+        )?;
+
+        let obligation = &mut self.obligation;
+        obligation.borrowed_amount -= repay_amount;
+        obligation.collateral_amount -= seized_receipts;
+
+        let reserve = &mut self.borrow_reserve;
+        reserve.borrowed_amount = reserve.borrowed_amount.saturating_sub(repay_amount);
+        reserve.available_liquidity = reserve
+            .available_liquidity
+            .checked_add(repay_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "{} repaid {} of {}'s debt and seized {} receipt tokens",
+            self.liquidator.key(),
+            repay_amount,
+            obligation.owner,
+            seized_receipts
+        );
+        Ok(())
+    }
+}
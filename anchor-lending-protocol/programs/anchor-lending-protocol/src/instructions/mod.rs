@@ -0,0 +1,25 @@
+pub mod borrow;
+pub mod deposit;
+pub mod deposit_collateral;
+pub mod initialize_market;
+pub mod initialize_mock_oracle;
+pub mod initialize_reserve;
+pub mod liquidate;
+pub mod repay;
+pub mod set_mock_price;
+pub mod update_reserve_config;
+pub mod withdraw;
+pub mod withdraw_collateral;
+
+pub use borrow::*;
+pub use deposit::*;
+pub use deposit_collateral::*;
+pub use initialize_market::*;
+pub use initialize_mock_oracle::*;
+pub use initialize_reserve::*;
+pub use liquidate::*;
+pub use repay::*;
+pub use set_mock_price::*;
+pub use update_reserve_config::*;
+pub use withdraw::*;
+pub use withdraw_collateral::*;
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{Obligation, Reserve};
+use crate::transfer::deposit_to_vault;
+
+/// Anyone may repay an obligation's debt, not only its owner
+#[derive(Accounts)]
+pub struct Repay<'info> {
+    pub payer: Signer<'info>,
+
+    #[account(mut, has_one = borrow_reserve)]
+    pub obligation: Box<Account<'info, Obligation>>,
+
+    #[account(mut, has_one = mint)]
+    pub borrow_reserve: Box<Account<'info, Reserve>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::VAULT_SEED, borrow_reserve.key().as_ref()],
+        bump = borrow_reserve.vault_bump
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payer,
+        token::token_program = token_program,
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Repay<'info> {
+    pub fn repay(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, ErrorCode::InvalidAmount);
+
+        self.borrow_reserve.accrue(Clock::get()?.unix_timestamp)?;
+        self.obligation.sync_debt(&self.borrow_reserve)?;
+        require_gt!(
+            self.obligation.borrowed_amount,
+            0,
+            ErrorCode::NothingToRepay
+        );
+
+        let repaid = amount.min(self.obligation.borrowed_amount);
+        deposit_to_vault(
+            &self.mint,
+            &self.payer_token_account,
+            &self.vault,
+            &self.payer,
+            &self.token_program,
+            repaid,
+        )?;
+
+        self.obligation.borrowed_amount -= repaid;
+        // Per-obligation debt rounds up, so the reserve's total can fall
+        // short of the sum of its obligations by a few units
+        let reserve = &mut self.borrow_reserve;
+        reserve.borrowed_amount = reserve.borrowed_amount.saturating_sub(repaid);
+        reserve.available_liquidity = reserve
+            .available_liquidity
+            .checked_add(repaid)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!(
+            "{} repaid {}, debt {}",
+            self.payer.key(),
+            repaid,
+            self.obligation.borrowed_amount
+        );
+        Ok(())
+    }
+}
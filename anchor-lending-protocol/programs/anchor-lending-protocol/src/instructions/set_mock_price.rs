@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::MockOracle;
+
+#[derive(Accounts)]
+pub struct SetMockPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            MockOracle::SEED_PREFIX,
+            authority.key().as_ref(),
+            oracle.mint.as_ref()
+        ],
+        bump = oracle.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub oracle: Account<'info, MockOracle>,
+}
+
+impl<'info> SetMockPrice<'info> {
+    pub fn set_mock_price(&mut self, price: i64) -> Result<()> {
+        require_gt!(price, 0, ErrorCode::InvalidPrice);
+
+        self.oracle.price = price;
+        self.oracle.last_updated = Clock::get()?.unix_timestamp;
+
+        msg!("Price of {} set to {}", self.oracle.mint, price);
+        Ok(())
+    }
+}
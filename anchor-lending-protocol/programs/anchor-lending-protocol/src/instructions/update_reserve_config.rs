@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{Market, Reserve, ReserveConfig};
+
+#[derive(Accounts)]
+pub struct UpdateReserveConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [Market::SEED_PREFIX, authority.key().as_ref()],
+        bump = market.bump,
+        has_one = authority @ ErrorCode::Unauthorized
+    )]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::SEED_PREFIX, market.key().as_ref(), reserve.mint.as_ref()],
+        bump = reserve.bump,
+        has_one = market
+    )]
+    pub reserve: Account<'info, Reserve>,
+}
+
+impl<'info> UpdateReserveConfig<'info> {
+    pub fn update_reserve_config(&mut self, config: ReserveConfig) -> Result<()> {
+        config.validate()?;
+
+        // Interest up to now accrues on the old curve
+        self.reserve.accrue(Clock::get()?.unix_timestamp)?;
+        self.reserve.config = config;
+
+        msg!("Updated config of reserve {}", self.reserve.key());
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::Reserve;
+use crate::transfer::{burn_receipts, pay_from_vault};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::SEED_PREFIX, reserve.market.as_ref(), mint.key().as_ref()],
+        bump = reserve.bump,
+        has_one = mint
+    )]
+    pub reserve: Box<Account<'info, Reserve>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::VAULT_SEED, reserve.key().as_ref()],
+        bump = reserve.vault_bump
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::RECEIPT_MINT_SEED, reserve.key().as_ref()],
+        bump = reserve.receipt_mint_bump
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Withdraw<'info> {
+    pub fn withdraw(&mut self, receipt_amount: u64) -> Result<()> {
+        require_gt!(receipt_amount, 0, ErrorCode::InvalidAmount);
+
+        self.reserve.accrue(Clock::get()?.unix_timestamp)?;
+        let amount = self.reserve.receipts_to_liquidity(receipt_amount)?;
+        require_gt!(amount, 0, ErrorCode::InvalidAmount);
+        require_gte!(
+            self.reserve.available_liquidity,
+            amount,
+            ErrorCode::InsufficientLiquidity
+        );
+
+        burn_receipts(
+            &self.receipt_mint,
+            &self.user_receipt_account,
+            &self.user,
+            &self.token_program,
+            receipt_amount,
+        )?;
+        pay_from_vault(
+            &self.reserve,
+            &self.mint,
+            &self.vault,
+            &self.user_token_account,
+            &self.token_program,
+            amount,
+        )?;
+
+        let reserve = &mut self.reserve;
+        reserve.available_liquidity -= amount;
+        reserve.receipt_supply -= receipt_amount;
+
+        msg!(
+            "{} redeemed {} receipt tokens for {}",
+            self.user.key(),
+            receipt_amount,
+            amount
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::ErrorCode;
+use crate::state::{MockOracle, Obligation, Reserve};
+use crate::transfer::pay_from_vault;
+use crate::utils::calculate_max_borrowable_amount;
+
+#[derive(Accounts)]
+pub struct WithdrawCollateral<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            Obligation::SEED_PREFIX,
+            collateral_reserve.key().as_ref(),
+            borrow_reserve.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = obligation.bump
+    )]
+    pub obligation: Box<Account<'info, Obligation>>,
+
+    #[account(mut)]
+    pub collateral_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = collateral_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub collateral_oracle: Box<Account<'info, MockOracle>>,
+
+    #[account(mut)]
+    pub borrow_reserve: Box<Account<'info, Reserve>>,
+
+    #[account(address = borrow_reserve.oracle @ ErrorCode::InvalidPrice)]
+    pub borrow_oracle: Box<Account<'info, MockOracle>>,
+
+    #[account(
+        seeds = [Reserve::RECEIPT_MINT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.receipt_mint_bump
+    )]
+    pub receipt_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub user_receipt_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [Reserve::COLLATERAL_VAULT_SEED, collateral_reserve.key().as_ref()],
+        bump = collateral_reserve.collateral_vault_bump
+    )]
+    pub collateral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawCollateral<'info> {
+    pub fn withdraw_collateral(&mut self, receipt_amount: u64) -> Result<()> {
+        require_gt!(receipt_amount, 0, ErrorCode::InvalidAmount);
+        require_gte!(
+            self.obligation.collateral_amount,
+            receipt_amount,
+            ErrorCode::InsufficientCollateral
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.collateral_reserve.accrue(now)?;
+        self.borrow_reserve.accrue(now)?;
+
+        let obligation = &mut self.obligation;
+        obligation.sync_debt(&self.borrow_reserve)?;
+        obligation.collateral_amount -= receipt_amount;
+
+        // Without debt there's nothing to value, so a stale oracle can't
+        // lock collateral in
+        if obligation.borrowed_amount > 0 {
+            let (collateral_value, debt_value) = obligation.values(
+                &self.collateral_reserve,
+                &self.collateral_oracle,
+                &self.borrow_oracle,
+                obligation.borrowed_amount,
+                now,
+            )?;
+            let max_borrowable = calculate_max_borrowable_amount(
+                collateral_value,
+                self.collateral_reserve.config.max_ltv_bps,
+            )?;
+            require_gte!(max_borrowable, debt_value, ErrorCode::ExceedsMaxLtv);
+        }
+
+        pay_from_vault(
+            &self.collateral_reserve,
+            &self.receipt_mint,
+            &self.collateral_vault,
+            &self.user_receipt_account,
+            &self.token_program,
+            receipt_amount,
+        )?;
+
+        msg!(
+            "{} unlocked {} receipt tokens",
+            self.owner.key(),
+            receipt_amount
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod transfer;
+pub mod utils;
+
+use instructions::*;
+use state::ReserveConfig;
+
+declare_id!("HBz9uCq6JpwKMVxzGvffpZNS97hhXqYLQoDXaBHdhNSw");
+
+#[program]
+pub mod anchor_lending_protocol {
+    use super::*;
+
+    /// Create a lending market administered by the signer
+    pub fn initialize_market(ctx: Context<InitializeMarket>) -> Result<()> {
+        ctx.accounts.initialize_market(&ctx.bumps)
+    }
+
+    /// Create a mock price feed for `mint`; stands in for Pyth in tests
+    pub fn initialize_mock_oracle(
+        ctx: Context<InitializeMockOracle>,
+        price: i64,
+        exponent: i32,
+    ) -> Result<()> {
+        ctx.accounts
+            .initialize_mock_oracle(price, exponent, &ctx.bumps)
+    }
+
+    /// Oracle-authority-only: publish a new mock price
+    pub fn set_mock_price(ctx: Context<SetMockPrice>, price: i64) -> Result<()> {
+        ctx.accounts.set_mock_price(price)
+    }
+
+    /// Market-authority-only: list `mint` as a reserve of the market
+    pub fn initialize_reserve(
+        ctx: Context<InitializeReserve>,
+        config: ReserveConfig,
+    ) -> Result<()> {
+        ctx.accounts.initialize_reserve(config, &ctx.bumps)
+    }
+
+    /// Market-authority-only: change a reserve's risk and rate parameters
+    pub fn update_reserve_config(
+        ctx: Context<UpdateReserveConfig>,
+        config: ReserveConfig,
+    ) -> Result<()> {
+        ctx.accounts.update_reserve_config(config)
+    }
+
+    /// Supply liquidity to a reserve in exchange for its receipt tokens
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)
+    }
+
+    /// Redeem receipt tokens for the liquidity they're worth
+    pub fn withdraw(ctx: Context<Withdraw>, receipt_amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(receipt_amount)
+    }
+
+    /// Lock receipt tokens as collateral in an obligation
+    pub fn deposit_collateral(ctx: Context<DepositCollateral>, receipt_amount: u64) -> Result<()> {
+        ctx.accounts.deposit_collateral(receipt_amount, &ctx.bumps)
+    }
+
+    /// Unlock collateral, as long as the obligation stays healthy
+    pub fn withdraw_collateral(
+        ctx: Context<WithdrawCollateral>,
+        receipt_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_collateral(receipt_amount)
+    }
+
+    /// Borrow from the obligation's borrow reserve, up to its max LTV
+    pub fn borrow(ctx: Context<Borrow>, amount: u64) -> Result<()> {
+        ctx.accounts.borrow(amount)
+    }
+
+    /// Repay up to `amount` of an obligation's debt
+    pub fn repay(ctx: Context<Repay>, amount: u64) -> Result<()> {
+        ctx.accounts.repay(amount)
+    }
+
+    /// Repay part of an unhealthy obligation's debt and seize its
+    /// collateral at a bonus
+    pub fn liquidate(ctx: Context<Liquidate>, repay_amount: u64) -> Result<()> {
+        ctx.accounts.liquidate(repay_amount)
+    }
+}
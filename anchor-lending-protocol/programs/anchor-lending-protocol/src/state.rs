@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::utils::{accrue_interest, calculate_collateral_value};
+
+pub const BPS: u64 = 10_000;
+/// Fixed-point scale of `Reserve::borrow_index`
+pub const INDEX_PRECISION: u64 = 1_000_000_000_000;
+/// Share of an unhealthy obligation's debt one liquidation may repay
+pub const CLOSE_FACTOR_BPS: u64 = 5_000;
+pub const MAX_LIQUIDATION_BONUS_BPS: u16 = 2_000;
+/// Oldest oracle price, in seconds, accepted for valuations
+pub const MAXIMUM_AGE: i64 = 180;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Market {
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl Market {
+    pub const SEED_PREFIX: &'static [u8] = b"market";
+}
+
+/// Risk and interest-rate parameters of a reserve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct ReserveConfig {
+    /// Borrowing power of this reserve's collateral
+    pub max_ltv_bps: u16,
+    /// Debt-to-collateral ratio past which an obligation can be liquidated
+    pub liquidation_ltv_bps: u16,
+    /// Extra collateral a liquidator receives on top of what they repay
+    pub liquidation_bonus_bps: u16,
+    /// Borrow APR at 0% utilization
+    pub base_rate_bps: u16,
+    /// Utilization at the kink of the rate curve
+    pub optimal_utilization_bps: u16,
+    /// APR added between 0% and optimal utilization
+    pub slope1_bps: u16,
+    /// APR added between optimal and 100% utilization
+    pub slope2_bps: u16,
+}
+
+impl ReserveConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.max_ltv_bps < self.liquidation_ltv_bps
+                && self.liquidation_ltv_bps as u64 <= BPS
+                && self.liquidation_bonus_bps <= MAX_LIQUIDATION_BONUS_BPS
+                && self.optimal_utilization_bps > 0
+                && self.optimal_utilization_bps as u64 <= BPS,
+            ErrorCode::InvalidReserveConfig
+        );
+        // The top of the curve must fit the u16 rate `accrue_interest` takes
+        let max_rate = self.base_rate_bps as u64 + self.slope1_bps as u64 + self.slope2_bps as u64;
+        require!(max_rate <= u16::MAX as u64, ErrorCode::InvalidReserveConfig);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reserve {
+    pub market: Pubkey,
+    pub mint: Pubkey,
+    pub oracle: Pubkey,
+    pub config: ReserveConfig,
+    /// Liquidity sitting in the vault, ready to be borrowed or withdrawn
+    pub available_liquidity: u64,
+    /// Outstanding borrows, including accrued interest
+    pub borrowed_amount: u64,
+    /// Growth of one unit of debt since the reserve was created, scaled by
+    /// `INDEX_PRECISION`
+    pub borrow_index: u64,
+    /// Receipt tokens in circulation, including those locked as collateral
+    pub receipt_supply: u64,
+    pub last_update: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub receipt_mint_bump: u8,
+    pub collateral_vault_bump: u8,
+}
+
+impl Reserve {
+    pub const SEED_PREFIX: &'static [u8] = b"reserve";
+    pub const VAULT_SEED: &'static [u8] = b"vault";
+    pub const RECEIPT_MINT_SEED: &'static [u8] = b"receipt";
+    pub const COLLATERAL_VAULT_SEED: &'static [u8] = b"collateral";
+
+    /// Liquidity owed to receipt holders: what's in the vault plus what's
+    /// lent out
+    pub fn total_liquidity(&self) -> Result<u64> {
+        self.available_liquidity
+            .checked_add(self.borrowed_amount)
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    pub fn utilization_bps(&self) -> Result<u64> {
+        let total = self.total_liquidity()?;
+        if total == 0 {
+            return Ok(0);
+        }
+        Ok((self.borrowed_amount as u128 * BPS as u128 / total as u128) as u64)
+    }
+
+    /// Borrow APR on the kinked curve: it climbs by `slope1` up to the
+    /// optimal utilization, then by the steeper `slope2` up to 100%
+    pub fn borrow_rate_bps(&self) -> Result<u16> {
+        let config = &self.config;
+        let utilization = self.utilization_bps()?;
+        let optimal = config.optimal_utilization_bps as u64;
+
+        let rate = if utilization <= optimal {
+            config.base_rate_bps as u64 + config.slope1_bps as u64 * utilization / optimal
+        } else {
+            config.base_rate_bps as u64
+                + config.slope1_bps as u64
+                + config.slope2_bps as u64 * (utilization - optimal) / (BPS - optimal)
+        };
+
+        u16::try_from(rate).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Accrue interest since `last_update` at the current utilization's
+    /// rate. The interest raises `total_liquidity`, and with it the value
+    /// of every receipt token.
+    pub fn accrue(&mut self, now: i64) -> Result<()> {
+        if now <= self.last_update {
+            return Ok(());
+        }
+
+        let rate = self.borrow_rate_bps()?;
+        self.borrowed_amount = accrue_interest(self.borrowed_amount, rate, self.last_update, now)?;
+        self.borrow_index = accrue_interest(self.borrow_index, rate, self.last_update, now)?;
+        self.last_update = now;
+
+        Ok(())
+    }
+
+    /// Receipt tokens minted for depositing `amount` of liquidity
+    pub fn liquidity_to_receipts(&self, amount: u64) -> Result<u64> {
+        if self.receipt_supply == 0 {
+            return Ok(amount);
+        }
+        mul_div(amount, self.receipt_supply, self.total_liquidity()?)
+    }
+
+    /// Liquidity `receipt_amount` receipt tokens can be redeemed for
+    pub fn receipts_to_liquidity(&self, receipt_amount: u64) -> Result<u64> {
+        if self.receipt_supply == 0 {
+            return Ok(receipt_amount);
+        }
+        mul_div(receipt_amount, self.total_liquidity()?, self.receipt_supply)
+    }
+
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            Self::SEED_PREFIX,
+            self.market.as_ref(),
+            self.mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+/// A borrower's isolated position: receipt tokens of one reserve locked as
+/// collateral against debt in another reserve of the same market
+#[account]
+#[derive(InitSpace)]
+pub struct Obligation {
+    pub owner: Pubkey,
+    pub collateral_reserve: Pubkey,
+    pub borrow_reserve: Pubkey,
+    /// Collateral reserve receipt tokens held in its collateral vault
+    pub collateral_amount: u64,
+    /// Debt as of the last sync; grows with the borrow reserve's index
+    pub borrowed_amount: u64,
+    /// The borrow reserve's `borrow_index` at the last sync
+    pub borrow_index: u64,
+    pub bump: u8,
+}
+
+impl Obligation {
+    pub const SEED_PREFIX: &'static [u8] = b"obligation";
+
+    /// Bring `borrowed_amount` up to date with the borrow reserve's index.
+    /// Rounds up, so the borrower never owes less than they took.
+    pub fn sync_debt(&mut self, borrow_reserve: &Reserve) -> Result<()> {
+        if self.borrowed_amount > 0 && self.borrow_index != borrow_reserve.borrow_index {
+            let debt = (self.borrowed_amount as u128)
+                .checked_mul(borrow_reserve.borrow_index as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .div_ceil(self.borrow_index as u128);
+            self.borrowed_amount = u64::try_from(debt).map_err(|_| ErrorCode::MathOverflow)?;
+        }
+        self.borrow_index = borrow_reserve.borrow_index;
+        Ok(())
+    }
+
+    /// Value of the collateral and of `debt`, both in the oracles' quote
+    /// currency
+    pub fn values(
+        &self,
+        collateral_reserve: &Reserve,
+        collateral_oracle: &MockOracle,
+        borrow_oracle: &MockOracle,
+        debt: u64,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        let (price, exponent) = collateral_oracle.price(now)?;
+        let collateral = collateral_reserve.receipts_to_liquidity(self.collateral_amount)?;
+        let collateral_value = calculate_collateral_value(collateral, price, exponent)?;
+
+        let (price, exponent) = borrow_oracle.price(now)?;
+        let debt_value = calculate_collateral_value(debt, price, exponent)?;
+
+        Ok((collateral_value, debt_value))
+    }
+}
+
+/// Price feed the tests control. `price * 10^exponent` is the value of one
+/// base unit of `mint` in the market's quote currency; a production
+/// deployment would read Pyth instead.
+#[account]
+#[derive(InitSpace)]
+pub struct MockOracle {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub price: i64,
+    pub exponent: i32,
+    pub last_updated: i64,
+    pub bump: u8,
+}
+
+impl MockOracle {
+    pub const SEED_PREFIX: &'static [u8] = b"mock_oracle";
+
+    pub fn price(&self, now: i64) -> Result<(i64, i32)> {
+        require!(
+            now.saturating_sub(self.last_updated) <= MAXIMUM_AGE,
+            ErrorCode::StalePrice
+        );
+        Ok((self.price, self.exponent))
+    }
+}
+
+fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    u64::try_from(result).map_err(|_| ErrorCode::MathOverflow.into())
+}
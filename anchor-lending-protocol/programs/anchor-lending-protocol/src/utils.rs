@@ -0,0 +1,80 @@
+//! Collateral valuation, LTV, interest and liquidation math, shared through
+//! the `risk-math` crate and raised here as this program's errors
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use risk_math::MathError;
+
+impl From<MathError> for ErrorCode {
+    fn from(error: MathError) -> Self {
+        match error {
+            MathError::MathOverflow => ErrorCode::MathOverflow,
+            MathError::InvalidPrice => ErrorCode::InvalidPrice,
+            MathError::InvalidTimestamp => ErrorCode::InvalidTimestamp,
+        }
+    }
+}
+
+fn lift<T>(result: risk_math::Result<T>) -> Result<T> {
+    result.map_err(|error| ErrorCode::from(error).into())
+}
+
+pub fn calculate_collateral_value(
+    collateral_amount: u64,
+    price: i64,
+    exponent: i32,
+) -> Result<u64> {
+    lift(risk_math::calculate_collateral_value(
+        collateral_amount,
+        price,
+        exponent,
+    ))
+}
+
+pub fn calculate_max_borrowable_amount(collateral_value_usd: u64, max_ltv_bps: u16) -> Result<u64> {
+    lift(risk_math::calculate_max_borrowable_amount(
+        collateral_value_usd,
+        max_ltv_bps,
+    ))
+}
+
+pub fn accrue_interest(
+    current_debt: u64,
+    borrow_rate_bps: u16,
+    last_update_timestamp: i64,
+    current_timestamp: i64,
+) -> Result<u64> {
+    lift(risk_math::accrue_interest(
+        current_debt,
+        borrow_rate_bps,
+        last_update_timestamp,
+        current_timestamp,
+    ))
+}
+
+pub fn calculate_health_factor(
+    collateral_value_usd: u64,
+    debt: u64,
+    liquidation_ltv_bps: u16,
+) -> Result<u16> {
+    lift(risk_math::calculate_health_factor(
+        collateral_value_usd,
+        debt,
+        liquidation_ltv_bps,
+    ))
+}
+
+pub fn calculate_liquidation_amounts(
+    debt: u64,
+    collateral_amount: u64,
+    liquidation_bonus_bps: u16,
+    collateral_price: i64,
+    price_exponent: i32,
+) -> Result<(u64, u64)> {
+    lift(risk_math::calculate_liquidation_amounts(
+        debt,
+        collateral_amount,
+        liquidation_bonus_bps,
+        collateral_price,
+        price_exponent,
+    ))
+}
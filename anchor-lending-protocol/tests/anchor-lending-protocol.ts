@@ -0,0 +1,388 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { AnchorLendingProtocol } from "../target/types/anchor_lending_protocol";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe("anchor-lending-protocol", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const program = anchor.workspace
+    .anchorLendingProtocol as Program<AnchorLendingProtocol>;
+
+  const admin = Keypair.generate();
+  const lender = Keypair.generate();
+  const borrower = Keypair.generate();
+  const liquidator = Keypair.generate();
+
+  // Both tokens have 6 decimals. Prices are per base unit, in base units
+  // of USDC: one COL is worth 100 USDC until the crash.
+  const UNIT = 1_000_000;
+  const config = {
+    maxLtvBps: 7_500,
+    liquidationLtvBps: 8_000,
+    liquidationBonusBps: 500,
+    baseRateBps: 200,
+    optimalUtilizationBps: 8_000,
+    slope1Bps: 400,
+    slope2Bps: 6_000,
+  };
+
+  type Asset = {
+    mint: PublicKey;
+    oracle: PublicKey;
+    reserve: PublicKey;
+    receiptMint: PublicKey;
+  };
+  let col: Asset;
+  let usdc: Asset;
+  let market: PublicKey;
+  let obligation: PublicKey;
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const tokenAccount = (mint: PublicKey, owner: Keypair) =>
+    getAssociatedTokenAddressSync(mint, owner.publicKey);
+
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  const setPrice = (asset: Asset, price: number) =>
+    program.methods
+      .setMockPrice(new BN(price))
+      .accountsPartial({ authority: admin.publicKey, oracle: asset.oracle })
+      .signers([admin])
+      .rpc();
+
+  const deposit = (user: Keypair, asset: Asset, amount: number) =>
+    program.methods
+      .deposit(new BN(amount))
+      .accountsPartial({
+        user: user.publicKey,
+        reserve: asset.reserve,
+        mint: asset.mint,
+        userTokenAccount: tokenAccount(asset.mint, user),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const withdraw = (user: Keypair, asset: Asset, receiptAmount: number) =>
+    program.methods
+      .withdraw(new BN(receiptAmount))
+      .accountsPartial({
+        user: user.publicKey,
+        reserve: asset.reserve,
+        mint: asset.mint,
+        userTokenAccount: tokenAccount(asset.mint, user),
+        userReceiptAccount: tokenAccount(asset.receiptMint, user),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const withdrawCollateral = (receiptAmount: number) =>
+    program.methods
+      .withdrawCollateral(new BN(receiptAmount))
+      .accountsPartial({
+        owner: borrower.publicKey,
+        obligation,
+        collateralReserve: col.reserve,
+        collateralOracle: col.oracle,
+        borrowReserve: usdc.reserve,
+        borrowOracle: usdc.oracle,
+        userReceiptAccount: tokenAccount(col.receiptMint, borrower),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([borrower])
+      .rpc();
+
+  const borrow = (amount: number) =>
+    program.methods
+      .borrow(new BN(amount))
+      .accountsPartial({
+        owner: borrower.publicKey,
+        obligation,
+        collateralReserve: col.reserve,
+        collateralOracle: col.oracle,
+        borrowReserve: usdc.reserve,
+        borrowOracle: usdc.oracle,
+        mint: usdc.mint,
+        userTokenAccount: tokenAccount(usdc.mint, borrower),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([borrower])
+      .rpc();
+
+  const liquidate = (repayAmount: number) =>
+    program.methods
+      .liquidate(new BN(repayAmount))
+      .accountsPartial({
+        liquidator: liquidator.publicKey,
+        obligation,
+        collateralReserve: col.reserve,
+        collateralOracle: col.oracle,
+        borrowReserve: usdc.reserve,
+        borrowOracle: usdc.oracle,
+        mint: usdc.mint,
+        liquidatorTokenAccount: tokenAccount(usdc.mint, liquidator),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([liquidator])
+      .rpc();
+
+  const setUpAsset = async (price: number): Promise<Asset> => {
+    const mint = await createMint(connection, admin, admin.publicKey, null, 6);
+    await program.methods
+      .initializeMockOracle(new BN(price), -6)
+      .accountsPartial({ authority: admin.publicKey, mint })
+      .signers([admin])
+      .rpc();
+    const oracle = pda(
+      Buffer.from("mock_oracle"),
+      admin.publicKey.toBuffer(),
+      mint.toBuffer()
+    );
+
+    await program.methods
+      .initializeReserve(config)
+      .accountsPartial({
+        authority: admin.publicKey,
+        market,
+        mint,
+        oracle,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    const reserve = pda(
+      Buffer.from("reserve"),
+      market.toBuffer(),
+      mint.toBuffer()
+    );
+    const receiptMint = pda(Buffer.from("receipt"), reserve.toBuffer());
+
+    for (const kp of [lender, borrower, liquidator]) {
+      const account = await createAssociatedTokenAccount(
+        connection,
+        admin,
+        mint,
+        kp.publicKey
+      );
+      await mintTo(connection, admin, mint, account, admin, 20_000 * UNIT);
+    }
+
+    return { mint, oracle, reserve, receiptMint };
+  };
+
+  before(async () => {
+    for (const kp of [admin, lender, borrower, liquidator]) {
+      const sig = await connection.requestAirdrop(
+        kp.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig);
+    }
+
+    await program.methods
+      .initializeMarket()
+      .accountsPartial({ authority: admin.publicKey })
+      .signers([admin])
+      .rpc();
+    market = pda(Buffer.from("market"), admin.publicKey.toBuffer());
+
+    col = await setUpAsset(100 * UNIT);
+    usdc = await setUpAsset(UNIT);
+    obligation = pda(
+      Buffer.from("obligation"),
+      col.reserve.toBuffer(),
+      usdc.reserve.toBuffer(),
+      borrower.publicKey.toBuffer()
+    );
+  });
+
+  it("Rejects a max LTV at or above the liquidation LTV", async () => {
+    try {
+      await program.methods
+        .updateReserveConfig({ ...config, maxLtvBps: 8_000 })
+        .accountsPartial({
+          authority: admin.publicKey,
+          market,
+          reserve: usdc.reserve,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "InvalidReserveConfig");
+    }
+  });
+
+  it("Mints receipt tokens 1:1 into an empty reserve", async () => {
+    await deposit(lender, usdc, 10_000 * UNIT);
+    await deposit(borrower, col, 100 * UNIT);
+
+    assert.equal(
+      await balance(tokenAccount(usdc.receiptMint, lender)),
+      10_000 * UNIT
+    );
+    const reserve = await program.account.reserve.fetch(usdc.reserve);
+    assert.equal(reserve.availableLiquidity.toNumber(), 10_000 * UNIT);
+    assert.equal(reserve.receiptSupply.toNumber(), 10_000 * UNIT);
+  });
+
+  it("Locks receipt tokens as collateral", async () => {
+    await program.methods
+      .depositCollateral(new BN(100 * UNIT))
+      .accountsPartial({
+        owner: borrower.publicKey,
+        collateralReserve: col.reserve,
+        borrowReserve: usdc.reserve,
+        userReceiptAccount: tokenAccount(col.receiptMint, borrower),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([borrower])
+      .rpc();
+
+    const state = await program.account.obligation.fetch(obligation);
+    assert.equal(state.collateralAmount.toNumber(), 100 * UNIT);
+    assert.equal(await balance(tokenAccount(col.receiptMint, borrower)), 0);
+  });
+
+  it("Rejects borrowing past the max LTV", async () => {
+    // 100 COL at 100 USDC with a 75% max LTV
+    try {
+      await borrow(7_501 * UNIT);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "ExceedsMaxLtv");
+    }
+  });
+
+  it("Borrows against collateral", async () => {
+    const before = await balance(tokenAccount(usdc.mint, borrower));
+    await borrow(5_000 * UNIT);
+
+    assert.equal(
+      await balance(tokenAccount(usdc.mint, borrower)),
+      before + 5_000 * UNIT
+    );
+    const reserve = await program.account.reserve.fetch(usdc.reserve);
+    assert.equal(reserve.availableLiquidity.toNumber(), 5_000 * UNIT);
+    assert.equal(reserve.borrowedAmount.toNumber(), 5_000 * UNIT);
+  });
+
+  it("Accrues interest into the receipt token's value", async () => {
+    await sleep(3000);
+
+    // A later deposit gets fewer receipts than it pays in liquidity
+    const receipts = tokenAccount(usdc.receiptMint, lender);
+    const before = await balance(receipts);
+    await deposit(lender, usdc, UNIT);
+    assert.isBelow((await balance(receipts)) - before, UNIT);
+
+    const reserve = await program.account.reserve.fetch(usdc.reserve);
+    assert.isAbove(reserve.borrowedAmount.toNumber(), 5_000 * UNIT);
+    assert.isTrue(reserve.borrowIndex.gt(new BN(1_000_000_000_000)));
+  });
+
+  it("Rejects withdrawing collateral the debt still needs", async () => {
+    try {
+      await withdrawCollateral(50 * UNIT);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "ExceedsMaxLtv");
+    }
+  });
+
+  it("Rejects liquidating a healthy obligation", async () => {
+    try {
+      await liquidate(1_000 * UNIT);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "PositionNotLiquidatable");
+    }
+  });
+
+  it("Liquidates an unhealthy obligation at a bonus", async () => {
+    // 6,000 USDC of collateral against 5,000 of debt is past the 80%
+    // liquidation LTV
+    await setPrice(col, 60 * UNIT);
+
+    try {
+      await liquidate(2_600 * UNIT);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "ExceedsCloseFactor");
+    }
+
+    await liquidate(1_000 * UNIT);
+
+    // 1,000 USDC plus the 5% bonus, paid in COL at 60 USDC
+    const seized = 17_500_000;
+    assert.equal(
+      await balance(tokenAccount(col.receiptMint, liquidator)),
+      seized
+    );
+    const state = await program.account.obligation.fetch(obligation);
+    assert.equal(state.collateralAmount.toNumber(), 100 * UNIT - seized);
+    assert.isBelow(state.borrowedAmount.toNumber(), 4_001 * UNIT);
+
+    await withdraw(liquidator, col, seized);
+    assert.equal(
+      await balance(tokenAccount(col.mint, liquidator)),
+      20_000 * UNIT + seized
+    );
+  });
+
+  it("Repays the debt and releases the collateral", async () => {
+    const state = await program.account.obligation.fetch(obligation);
+    const collateral = state.collateralAmount.toNumber();
+
+    // Overpaying only takes what's owed
+    await program.methods
+      .repay(new BN(5_000 * UNIT))
+      .accountsPartial({
+        payer: borrower.publicKey,
+        obligation,
+        borrowReserve: usdc.reserve,
+        mint: usdc.mint,
+        payerTokenAccount: tokenAccount(usdc.mint, borrower),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([borrower])
+      .rpc();
+    const repaid = await program.account.obligation.fetch(obligation);
+    assert.equal(repaid.borrowedAmount.toNumber(), 0);
+
+    await withdrawCollateral(collateral);
+    await withdraw(borrower, col, collateral);
+    assert.equal(
+      await balance(tokenAccount(col.mint, borrower)),
+      20_000 * UNIT - 100 * UNIT + collateral
+    );
+  });
+
+  it("Pays lenders back with interest", async () => {
+    const before = await balance(tokenAccount(usdc.mint, lender));
+    const receipts = await balance(tokenAccount(usdc.receiptMint, lender));
+    await withdraw(lender, usdc, receipts);
+
+    // Deposited 10,001 USDC in total
+    const returned = (await balance(tokenAccount(usdc.mint, lender))) - before;
+    assert.isAbove(returned, 10_001 * UNIT);
+  });
+});
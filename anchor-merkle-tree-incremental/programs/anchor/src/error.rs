@@ -8,4 +8,8 @@ pub enum MerkleError {
     TreeFull,
     #[msg("Invalid merkle proof")]
     InvalidProof,
+    #[msg("Batch must contain at least one leaf")]
+    EmptyBatch,
+    #[msg("Hook program is not executable")]
+    InvalidHookProgram,
 }
@@ -1,3 +1,4 @@
+use anchor_lang::prelude::Pubkey;
 use sha2_const_stable::Sha256 as ConstSha256;
 
 const fn make_zero_hashes() -> [[u8; 32]; 21] {
@@ -24,3 +25,31 @@ pub const ZERO_HASHES: [[u8; 32]; 21] = make_zero_hashes();
 pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
     solana_sha256_hasher::hashv(&[left.as_ref(), right.as_ref()]).to_bytes()
 }
+
+pub fn compute_root(leaf: &[u8; 32], index: u32, proof: &[[u8; 32]], depth: usize) -> [u8; 32] {
+    let mut current_hash = *leaf;
+    let mut current_index = index;
+
+    for sibling in proof.iter().take(depth) {
+        current_hash = if current_index.is_multiple_of(2) {
+            hash_pair(&current_hash, sibling)
+        } else {
+            hash_pair(sibling, &current_hash)
+        };
+        current_index >>= 1;
+    }
+
+    current_hash
+}
+
+/// Leaf that only `owner` can consume: `consume` rebuilds it from the signer,
+/// so a copied proof is useless to anyone else.
+pub fn owned_leaf(owner: &Pubkey, data: &[u8; 32]) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[b"leaf", owner.as_ref(), data.as_ref()]).to_bytes()
+}
+
+/// Seed of the nullifier PDA for `leaf` in `domain`. The prefix keeps it from
+/// colliding with an interior node hash of the same two inputs.
+pub fn nullifier_hash(leaf: &[u8; 32], domain: &[u8; 32]) -> [u8; 32] {
+    solana_sha256_hasher::hashv(&[b"nullifier", leaf.as_ref(), domain.as_ref()]).to_bytes()
+}
@@ -0,0 +1,109 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+use crate::{
+    error::MerkleError,
+    hash::{compute_root, nullifier_hash, owned_leaf},
+    state::{MerkleTree, Nullifier},
+};
+
+#[derive(Accounts)]
+#[instruction(leaf_data: [u8; 32], domain: [u8; 32])]
+pub struct Consume<'info> {
+    /// Consumer; the leaf must be `owned_leaf(payer, leaf_data)`.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub merkle_tree: Account<'info, MerkleTree>,
+    #[account(
+        init,
+        payer = payer,
+        space = Nullifier::SPACE,
+        seeds = [
+            b"nullifier",
+            merkle_tree.key().as_ref(),
+            nullifier_hash(&owned_leaf(&payer.key(), &leaf_data), &domain).as_ref(),
+        ],
+        bump,
+    )]
+    pub nullifier: Account<'info, Nullifier>,
+    /// CHECK: invoked after the nullifier is written, with the nullifier PDA
+    /// as its first (signer) account followed by `remaining_accounts`.
+    pub hook_program: Option<UncheckedAccount<'info>>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Proves `owned_leaf(payer, leaf_data)` is in the tree and records it as
+/// spent for `domain`.
+///
+/// The leaf commits to its owner, so only the owner's signature produces a
+/// matching leaf; someone replaying the proof from the mempool builds a
+/// different leaf and fails the proof instead of burning the nullifier.
+///
+/// `init` on the nullifier PDA is what makes this one-time: a second consume
+/// of the same `(leaf, domain)` fails in the system program because the
+/// account already exists.
+///
+/// When `hook_program` is passed it is called with `hook_data`. The nullifier
+/// PDA is signed for by this program, so the hook can trust a signer
+/// nullifier owned by this program as proof that consumption just happened.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Consume<'info>>,
+    leaf_data: [u8; 32],
+    domain: [u8; 32],
+    index: u32,
+    proof: [[u8; 32]; 20],
+    hook_data: Vec<u8>,
+) -> Result<()> {
+    let tree = &ctx.accounts.merkle_tree;
+    require!(index < tree.next_index, MerkleError::InvalidProof);
+
+    let leaf = owned_leaf(&ctx.accounts.payer.key(), &leaf_data);
+    let root = compute_root(&leaf, index, &proof, tree.depth as usize);
+    require!(root == tree.current_root, MerkleError::InvalidProof);
+
+    let tree_key = tree.key();
+    let hash = nullifier_hash(&leaf, &domain);
+    let nullifier = &mut ctx.accounts.nullifier;
+    nullifier.merkle_tree = tree_key;
+    nullifier.nullifier_hash = hash;
+    nullifier.consumed_by = ctx.accounts.payer.key();
+    nullifier.slot = Clock::get()?.slot;
+    nullifier.bump = ctx.bumps.nullifier;
+
+    let Some(hook_program) = &ctx.accounts.hook_program else {
+        return Ok(());
+    };
+    require!(hook_program.executable, MerkleError::InvalidHookProgram);
+
+    // Persist the nullifier now so the hook can read it during the CPI.
+    ctx.accounts.nullifier.exit(&crate::ID)?;
+
+    let nullifier_info = ctx.accounts.nullifier.to_account_info();
+    let mut accounts = vec![AccountMeta::new_readonly(nullifier_info.key(), true)];
+    let mut account_infos = vec![nullifier_info];
+    for account in ctx.remaining_accounts {
+        accounts.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(hook_program.to_account_info());
+
+    let bump = [ctx.bumps.nullifier];
+    let signer_seeds: &[&[u8]] = &[b"nullifier", tree_key.as_ref(), hash.as_ref(), &bump];
+    invoke_signed(
+        &Instruction {
+            program_id: hook_program.key(),
+            accounts,
+            data: hook_data,
+        },
+        &account_infos,
+        &[signer_seeds],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MerkleError,
+    hash::{hash_pair, ZERO_HASHES},
+    state::MerkleTree,
+};
+
+#[derive(Accounts)]
+pub struct InsertBatch<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"merkle", authority.key().as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub merkle_tree: Account<'info, MerkleTree>,
+}
+
+/// Appends `leaves` starting at `next_index`, hashing each shared parent once.
+///
+/// Level `i` only holds the nodes touched by the batch (`lo..=hi`). A left
+/// sibling outside the batch comes from `filled_subtrees[i]`, a missing right
+/// sibling is `ZERO_HASHES[i]`, and the rightmost even node of every level is
+/// written back to `filled_subtrees` so later inserts continue from it.
+pub fn handler(ctx: Context<InsertBatch>, leaves: Vec<[u8; 32]>) -> Result<()> {
+    require!(!leaves.is_empty(), MerkleError::EmptyBatch);
+    require!(
+        leaves.iter().all(|leaf| *leaf != [0u8; 32]),
+        MerkleError::ZeroLeaf
+    );
+
+    let tree = &mut ctx.accounts.merkle_tree;
+    let max_leaves = 1u64 << tree.depth;
+    let end = tree.next_index as u64 + leaves.len() as u64;
+    require!(end <= max_leaves, MerkleError::TreeFull);
+
+    let depth = tree.depth as usize;
+    let mut nodes = leaves;
+    let mut lo = tree.next_index;
+
+    for (i, zero) in ZERO_HASHES.iter().enumerate().take(depth) {
+        let hi = lo + nodes.len() as u32 - 1;
+        let left_edge = tree.filled_subtrees[i];
+
+        let frontier = hi & !1;
+        if frontier >= lo {
+            tree.filled_subtrees[i] = nodes[(frontier - lo) as usize];
+        }
+
+        let parent_lo = lo >> 1;
+        let parent_hi = hi >> 1;
+        for parent in parent_lo..=parent_hi {
+            let left_index = parent << 1;
+            let right_index = left_index + 1;
+
+            let left = if left_index < lo {
+                left_edge
+            } else {
+                nodes[(left_index - lo) as usize]
+            };
+            let right = if right_index <= hi {
+                nodes[(right_index - lo) as usize]
+            } else {
+                *zero
+            };
+
+            nodes[(parent - parent_lo) as usize] = hash_pair(&left, &right);
+        }
+
+        nodes.truncate((parent_hi - parent_lo + 1) as usize);
+        lo = parent_lo;
+    }
+
+    tree.current_root = nodes[0];
+    tree.next_index = end as u32;
+    Ok(())
+}
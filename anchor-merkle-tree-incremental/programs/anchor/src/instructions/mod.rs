@@ -1,7 +1,13 @@
+pub mod consume;
 pub mod initialize;
 pub mod insert;
+pub mod insert_batch;
+pub mod replace_leaf;
 pub mod verify;
 
+pub use consume::*;
 pub use initialize::*;
 pub use insert::*;
+pub use insert_batch::*;
+pub use replace_leaf::*;
 pub use verify::*;
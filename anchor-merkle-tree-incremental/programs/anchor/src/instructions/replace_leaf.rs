@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::MerkleError,
+    hash::{compute_root, hash_pair},
+    state::MerkleTree,
+};
+
+#[derive(Accounts)]
+pub struct ReplaceLeaf<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"merkle", authority.key().as_ref()],
+        bump,
+        has_one = authority,
+    )]
+    pub merkle_tree: Account<'info, MerkleTree>,
+}
+
+/// Swaps `old_leaf` at `index` for `new_leaf` after checking `proof` against
+/// the current root. `remove_leaf` calls this with `ZERO_HASHES[0]`, the
+/// value of an empty slot.
+pub fn handler(
+    ctx: Context<ReplaceLeaf>,
+    index: u32,
+    old_leaf: [u8; 32],
+    new_leaf: [u8; 32],
+    proof: [[u8; 32]; 20],
+) -> Result<()> {
+    require!(new_leaf != [0u8; 32], MerkleError::ZeroLeaf);
+
+    let tree = &mut ctx.accounts.merkle_tree;
+    require!(index < tree.next_index, MerkleError::InvalidProof);
+
+    let depth = tree.depth as usize;
+    let old_root = compute_root(&old_leaf, index, &proof, depth);
+    require!(old_root == tree.current_root, MerkleError::InvalidProof);
+
+    // filled_subtrees[i] caches the rightmost even node of level i; if the
+    // updated path runs through it, the cache must follow or the next insert
+    // would hash against the stale value.
+    let last = tree.next_index - 1;
+    let mut current_hash = new_leaf;
+    let mut current_index = index;
+
+    for (i, sibling) in proof.iter().enumerate().take(depth) {
+        if current_index == (last >> i) & !1 {
+            tree.filled_subtrees[i] = current_hash;
+        }
+        current_hash = if current_index.is_multiple_of(2) {
+            hash_pair(&current_hash, sibling)
+        } else {
+            hash_pair(sibling, &current_hash)
+        };
+        current_index >>= 1;
+    }
+
+    tree.current_root = current_hash;
+    Ok(())
+}
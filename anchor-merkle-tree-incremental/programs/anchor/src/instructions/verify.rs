@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{error::MerkleError, hash::hash_pair, state::MerkleTree};
+use crate::{error::MerkleError, hash::compute_root, state::MerkleTree};
 
 #[derive(Accounts)]
 pub struct Verify<'info> {
@@ -15,19 +15,8 @@ pub fn handler(
 ) -> Result<()> {
     let tree = &ctx.accounts.merkle_tree;
     require!(index < tree.next_index, MerkleError::InvalidProof);
-    let mut current_hash = leaf;
-    let mut current_index = index;
-    let depth = tree.depth as usize;
 
-    for i in 0..depth {
-        current_hash = if current_index % 2 == 0 {
-            hash_pair(&current_hash, &proof[i])
-        } else {
-            hash_pair(&proof[i], &current_hash)
-        };
-        current_index >>= 1;
-    }
-
-    require!(current_hash == tree.current_root, MerkleError::InvalidProof);
+    let root = compute_root(&leaf, index, &proof, tree.depth as usize);
+    require!(root == tree.current_root, MerkleError::InvalidProof);
     Ok(())
 }
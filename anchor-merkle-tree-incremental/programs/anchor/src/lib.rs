@@ -4,6 +4,7 @@ pub mod instructions;
 pub mod state;
 
 use anchor_lang::prelude::*;
+use hash::ZERO_HASHES;
 use instructions::*;
 
 declare_id!("FvqTgpWEMR5ZT4jikL4XeQhX8qsghPWEyKKoP3Esdukn");
@@ -20,6 +21,40 @@ pub mod merkle {
         instructions::insert::handler(ctx, leaf)
     }
 
+    pub fn insert_batch(ctx: Context<InsertBatch>, leaves: Vec<[u8; 32]>) -> Result<()> {
+        instructions::insert_batch::handler(ctx, leaves)
+    }
+
+    pub fn replace_leaf(
+        ctx: Context<ReplaceLeaf>,
+        index: u32,
+        old_leaf: [u8; 32],
+        new_leaf: [u8; 32],
+        proof: [[u8; 32]; 20],
+    ) -> Result<()> {
+        instructions::replace_leaf::handler(ctx, index, old_leaf, new_leaf, proof)
+    }
+
+    pub fn remove_leaf(
+        ctx: Context<ReplaceLeaf>,
+        index: u32,
+        old_leaf: [u8; 32],
+        proof: [[u8; 32]; 20],
+    ) -> Result<()> {
+        instructions::replace_leaf::handler(ctx, index, old_leaf, ZERO_HASHES[0], proof)
+    }
+
+    pub fn consume<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Consume<'info>>,
+        leaf_data: [u8; 32],
+        domain: [u8; 32],
+        index: u32,
+        proof: [[u8; 32]; 20],
+        hook_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::consume::handler(ctx, leaf_data, domain, index, proof, hook_data)
+    }
+
     pub fn verify(ctx: Context<Verify>, leaf: [u8; 32], index: u32, proof: [[u8; 32]; 20]) -> Result<()> {
         instructions::verify::handler(ctx, leaf, index, proof)
     }
@@ -15,3 +15,20 @@ impl MerkleTree {
     // 8 (discriminator) + 32 + 1 + 4 + 32 + (32 * 20)
     pub const SPACE: usize = 8 + 32 + 1 + 4 + 32 + (32 * DEPTH);
 }
+
+/// Marks `leaf` as spent for one `domain`. Its existence is the record; the
+/// PDA is `[b"nullifier", merkle_tree, nullifier_hash(leaf, domain)]`, where
+/// `leaf` is `owned_leaf(consumed_by, leaf_data)`.
+#[account]
+pub struct Nullifier {
+    pub merkle_tree: Pubkey,
+    pub nullifier_hash: [u8; 32],
+    pub consumed_by: Pubkey,
+    pub slot: u64,
+    pub bump: u8,
+}
+
+impl Nullifier {
+    // 8 (discriminator) + 32 + 32 + 32 + 8 + 1
+    pub const SPACE: usize = 8 + 32 + 32 + 32 + 8 + 1;
+}
@@ -1,6 +1,8 @@
-use anchor::hash::{hash_pair, ZERO_HASHES};
+use anchor::hash::{hash_pair, nullifier_hash, owned_leaf, ZERO_HASHES};
 use anchor::state::MerkleTree;
+use merkle_tree_client::MerkleTree as ClientTree;
 use mollusk_svm::{program, result::Check, Mollusk};
+use proptest::prelude::*;
 use solana_account::Account;
 use solana_instruction::{AccountMeta, Instruction};
 use solana_program_error::ProgramError;
@@ -9,10 +11,16 @@ use solana_pubkey::Pubkey;
 const IX_INITIALIZE: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
 const IX_INSERT: [u8; 8] = [205, 174, 245, 70, 145, 250, 39, 168];
 const IX_VERIFY: [u8; 8] = [133, 161, 141, 48, 120, 198, 88, 150];
+const IX_INSERT_BATCH: [u8; 8] = [111, 61, 13, 213, 159, 132, 216, 185];
+const IX_REPLACE_LEAF: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+const IX_REMOVE_LEAF: [u8; 8] = [216, 163, 144, 125, 67, 113, 134, 160];
+const IX_CONSUME: [u8; 8] = [65, 178, 141, 13, 95, 57, 76, 154];
 
 const ERR_ZERO_LEAF: ProgramError = ProgramError::Custom(6000);
 const ERR_INVALID_PROOF: ProgramError = ProgramError::Custom(6002);
+const ERR_EMPTY_BATCH: ProgramError = ProgramError::Custom(6003);
 const ERR_CONSTRAINT_SEEDS: ProgramError = ProgramError::Custom(2006);
+const ERR_ACCOUNT_ALREADY_IN_USE: ProgramError = ProgramError::Custom(0);
 
 fn setup() -> (Mollusk, Pubkey) {
     let program_id = Pubkey::new_from_array(anchor::ID.to_bytes());
@@ -69,6 +77,119 @@ fn ix_verify(
     }
 }
 
+fn ix_insert_batch(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    pda: &Pubkey,
+    leaves: &[[u8; 32]],
+) -> Instruction {
+    let mut data = IX_INSERT_BATCH.to_vec();
+    data.extend_from_slice(&(leaves.len() as u32).to_le_bytes());
+    for leaf in leaves {
+        data.extend_from_slice(leaf);
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*pda, false),
+        ],
+        data,
+    }
+}
+
+fn ix_replace_leaf(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    pda: &Pubkey,
+    index: u32,
+    old_leaf: [u8; 32],
+    new_leaf: Option<[u8; 32]>,
+    proof: &[[u8; 32]; 20],
+) -> Instruction {
+    let mut data = match new_leaf {
+        Some(_) => IX_REPLACE_LEAF.to_vec(),
+        None => IX_REMOVE_LEAF.to_vec(),
+    };
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&old_leaf);
+    if let Some(new_leaf) = new_leaf {
+        data.extend_from_slice(&new_leaf);
+    }
+    for p in proof {
+        data.extend_from_slice(p);
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*pda, false),
+        ],
+        data,
+    }
+}
+
+fn owned(owner: &Pubkey, data: &[u8; 32]) -> [u8; 32] {
+    owned_leaf(&anchor_lang::prelude::Pubkey::new_from_array(owner.to_bytes()), data)
+}
+
+fn nullifier_pda(tree: &Pubkey, leaf: &[u8; 32], domain: &[u8; 32], program_id: &Pubkey) -> Pubkey {
+    let hash = nullifier_hash(leaf, domain);
+    Pubkey::find_program_address(&[b"nullifier", tree.as_ref(), hash.as_ref()], program_id).0
+}
+
+/// `consume` without a hook: the optional `hook_program` slot is filled with
+/// the program id, which is how Anchor encodes `None`.
+fn ix_consume(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    pda: &Pubkey,
+    leaf_data: [u8; 32],
+    domain: [u8; 32],
+    index: u32,
+    proof: &[[u8; 32]; 20],
+) -> Instruction {
+    let leaf = owned(payer, &leaf_data);
+    let mut data = IX_CONSUME.to_vec();
+    data.extend_from_slice(&leaf_data);
+    data.extend_from_slice(&domain);
+    data.extend_from_slice(&index.to_le_bytes());
+    for p in proof {
+        data.extend_from_slice(p);
+    }
+    data.extend_from_slice(&0u32.to_le_bytes());
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*pda, false),
+            AccountMeta::new(nullifier_pda(pda, &leaf, &domain, program_id), false),
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new_readonly(Pubkey::default(), false),
+        ],
+        data,
+    }
+}
+
+fn reference_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut tree = ClientTree::new();
+    tree.insert_batch(leaves).unwrap();
+    tree.root()
+}
+
+fn reference_proof(leaves: &[[u8; 32]], index: usize) -> [[u8; 32]; 20] {
+    let mut tree = ClientTree::new();
+    tree.insert_batch(leaves).unwrap();
+    tree.proof(index as u32).unwrap()
+}
+
+fn leaf(n: u8) -> [u8; 32] {
+    let mut leaf = [0u8; 32];
+    leaf[0] = n;
+    leaf[31] = 0xAA;
+    leaf
+}
+
 fn expected_root_after_insert(leaf: [u8; 32]) -> [u8; 32] {
     let mut h = leaf;
     for i in 0..20usize {
@@ -254,3 +375,369 @@ fn test_wrong_authority_rejected() {
     );
     let _ = sa;
 }
+
+#[test]
+fn test_insert_batch_matches_sequential_inserts() {
+    let (mollusk, program_id) = setup();
+    let authority = Pubkey::new_unique();
+    let (pda, _) = merkle_pda(&authority, &program_id);
+
+    let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+    let authority_acc = after_init[0].1.clone();
+    let mut pda_acc = after_init[1].1.clone();
+
+    let leaves: Vec<[u8; 32]> = (1..=11).map(leaf).collect();
+
+    // Start off an odd index so the first batch node pairs with a cached
+    // left sibling.
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert(&program_id, &authority, &pda, leaves[0]),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &leaves[1..8]),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    println!("insert_batch CU: {}", result.compute_units_consumed);
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let (_, _, next_index, root) = parse_tree(&pda_acc.data);
+    assert_eq!(next_index, 8, "next_index must advance by batch size");
+    assert_eq!(root, reference_root(&leaves[..8]), "batch root mismatch");
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &leaves[8..10]),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert(&program_id, &authority, &pda, leaves[10]),
+        &vec![(authority, authority_acc), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let (_, _, next_index, root) = parse_tree(&pda_acc.data);
+    assert_eq!(next_index, 11);
+    assert_eq!(root, reference_root(&leaves), "insert after batch mismatch");
+
+    mollusk.process_and_validate_instruction(
+        &ix_verify(
+            &program_id,
+            &pda,
+            leaves[5],
+            5,
+            &reference_proof(&leaves, 5),
+        ),
+        &vec![(pda, pda_acc)],
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_insert_batch_rejects_empty_and_zero_leaves() {
+    let (mollusk, program_id) = setup();
+    let authority = Pubkey::new_unique();
+    let (pda, _) = merkle_pda(&authority, &program_id);
+
+    let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+    let authority_acc = after_init[0].1.clone();
+    let pda_acc = after_init[1].1.clone();
+
+    mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &[]),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc.clone())],
+        &[Check::err(ERR_EMPTY_BATCH)],
+    );
+
+    mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &[leaf(1), [0u8; 32]]),
+        &vec![(authority, authority_acc), (pda, pda_acc)],
+        &[Check::err(ERR_ZERO_LEAF)],
+    );
+}
+
+#[test]
+fn test_replace_leaf_then_insert() {
+    let (mollusk, program_id) = setup();
+    let authority = Pubkey::new_unique();
+    let (pda, _) = merkle_pda(&authority, &program_id);
+
+    let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+    let authority_acc = after_init[0].1.clone();
+    let mut pda_acc = after_init[1].1.clone();
+
+    let mut leaves: Vec<[u8; 32]> = (1..=6).map(leaf).collect();
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &leaves),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    // Leaf 4 sits under the cached frontier node, so a later insert only
+    // lands on the right root if the cache was updated too.
+    let proof = reference_proof(&leaves, 4);
+    mollusk.process_and_validate_instruction(
+        &ix_replace_leaf(
+            &program_id,
+            &authority,
+            &pda,
+            4,
+            leaf(99),
+            Some(leaf(50)),
+            &proof,
+        ),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc.clone())],
+        &[Check::err(ERR_INVALID_PROOF)],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_replace_leaf(
+            &program_id,
+            &authority,
+            &pda,
+            4,
+            leaves[4],
+            Some(leaf(50)),
+            &proof,
+        ),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    println!("replace_leaf CU: {}", result.compute_units_consumed);
+    pda_acc = result.resulting_accounts[1].1.clone();
+    leaves[4] = leaf(50);
+
+    let (_, _, next_index, root) = parse_tree(&pda_acc.data);
+    assert_eq!(next_index, 6, "replace must not change next_index");
+    assert_eq!(root, reference_root(&leaves), "replace root mismatch");
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert(&program_id, &authority, &pda, leaf(7)),
+        &vec![(authority, authority_acc), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    leaves.push(leaf(7));
+
+    let (_, _, _, root) = parse_tree(&result.resulting_accounts[1].1.data);
+    assert_eq!(
+        root,
+        reference_root(&leaves),
+        "insert after replace mismatch"
+    );
+}
+
+#[test]
+fn test_remove_leaf() {
+    let (mollusk, program_id) = setup();
+    let authority = Pubkey::new_unique();
+    let (pda, _) = merkle_pda(&authority, &program_id);
+
+    let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+    let authority_acc = after_init[0].1.clone();
+    let mut pda_acc = after_init[1].1.clone();
+
+    let mut leaves: Vec<[u8; 32]> = (1..=3).map(leaf).collect();
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &leaves),
+        &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let proof = reference_proof(&leaves, 1);
+    let result = mollusk.process_and_validate_instruction(
+        &ix_replace_leaf(&program_id, &authority, &pda, 1, leaves[1], None, &proof),
+        &vec![(authority, authority_acc), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+    leaves[1] = ZERO_HASHES[0];
+
+    let (_, _, _, root) = parse_tree(&pda_acc.data);
+    assert_eq!(root, reference_root(&leaves), "remove root mismatch");
+
+    // The removed leaf no longer proves membership.
+    mollusk.process_and_validate_instruction(
+        &ix_verify(&program_id, &pda, leaf(2), 1, &proof),
+        &vec![(pda, pda_acc)],
+        &[Check::err(ERR_INVALID_PROOF)],
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    /// Drives random single and batch inserts plus one replace through the
+    /// program and checks the account against the off-chain client.
+    #[test]
+    fn prop_client_matches_on_chain_root(
+        batches in prop::collection::vec(
+            prop::collection::vec(
+                any::<[u8; 32]>().prop_filter("zero leaf", |l| *l != [0u8; 32]),
+                1..6,
+            ),
+            1..6,
+        ),
+        replace_at in any::<prop::sample::Index>(),
+        new_leaf in any::<[u8; 32]>().prop_filter("zero leaf", |l| *l != [0u8; 32]),
+    ) {
+        let (mollusk, program_id) = setup();
+        let authority = Pubkey::new_unique();
+        let (pda, _) = merkle_pda(&authority, &program_id);
+
+        let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+        let authority_acc = after_init[0].1.clone();
+        let mut pda_acc = after_init[1].1.clone();
+        let mut client = ClientTree::new();
+
+        for batch in &batches {
+            let ix = if batch.len() == 1 {
+                ix_insert(&program_id, &authority, &pda, batch[0])
+            } else {
+                ix_insert_batch(&program_id, &authority, &pda, batch)
+            };
+            let result = mollusk.process_and_validate_instruction(
+                &ix,
+                &vec![(authority, authority_acc.clone()), (pda, pda_acc)],
+                &[Check::success()],
+            );
+            pda_acc = result.resulting_accounts[1].1.clone();
+            client.insert_batch(batch).unwrap();
+        }
+
+        let index = replace_at.index(client.next_index() as usize) as u32;
+        let old_leaf = client.leaf(index).unwrap();
+        let proof = client.proof(index).unwrap();
+        let result = mollusk.process_and_validate_instruction(
+            &ix_replace_leaf(&program_id, &authority, &pda, index, old_leaf, Some(new_leaf), &proof),
+            &vec![(authority, authority_acc), (pda, pda_acc)],
+            &[Check::success()],
+        );
+        pda_acc = result.resulting_accounts[1].1.clone();
+        client.replace_leaf(index, new_leaf).unwrap();
+
+        let (_, _, next_index, root) = parse_tree(&pda_acc.data);
+        prop_assert_eq!(next_index, client.next_index());
+        prop_assert_eq!(root, client.root());
+        let filled: Vec<[u8; 32]> = pda_acc.data[77..77 + 32 * 20]
+            .chunks_exact(32)
+            .map(|c| c.try_into().unwrap())
+            .collect();
+        prop_assert_eq!(&filled[..], &client.filled_subtrees()[..]);
+
+        for i in 0..client.next_index() {
+            mollusk.process_and_validate_instruction(
+                &ix_verify(&program_id, &pda, client.leaf(i).unwrap(), i, &client.proof(i).unwrap()),
+                &vec![(pda, pda_acc.clone())],
+                &[Check::success()],
+            );
+        }
+    }
+}
+
+#[test]
+fn test_consume_is_one_time_per_domain() {
+    let (mollusk, program_id) = setup();
+    let (sp, sa) = program::keyed_account_for_system_program();
+    let program_account = program::create_program_account_loader_v3(&program_id);
+    let authority = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let (pda, _) = merkle_pda(&authority, &program_id);
+
+    let after_init = do_initialize(&mollusk, &program_id, &authority, &pda);
+    let authority_acc = after_init[0].1.clone();
+    let mut pda_acc = after_init[1].1.clone();
+
+    // Leaf 2 belongs to `payer`
+    let data = leaf(3);
+    let mut leaves: Vec<[u8; 32]> = (1..=4).map(leaf).collect();
+    leaves[2] = owned(&payer, &data);
+    let result = mollusk.process_and_validate_instruction(
+        &ix_insert_batch(&program_id, &authority, &pda, &leaves),
+        &vec![(authority, authority_acc), (pda, pda_acc)],
+        &[Check::success()],
+    );
+    pda_acc = result.resulting_accounts[1].1.clone();
+
+    let airdrop = [1u8; 32];
+    let vote = [2u8; 32];
+    let proof = reference_proof(&leaves, 2);
+    let nullifier = nullifier_pda(&pda, &leaves[2], &airdrop, &program_id);
+    let accounts = |consumer: Pubkey, nullifier: Pubkey, nullifier_acc: Account| {
+        vec![
+            (consumer, Account::new(10_000_000_000, 0, &sp)),
+            (pda, pda_acc.clone()),
+            (nullifier, nullifier_acc),
+            (program_id, program_account.clone()),
+            (sp, sa.clone()),
+        ]
+    };
+
+    mollusk.process_and_validate_instruction(
+        &ix_consume(&program_id, &payer, &pda, leaf(9), airdrop, 2, &proof),
+        &accounts(
+            payer,
+            nullifier_pda(&pda, &owned(&payer, &leaf(9)), &airdrop, &program_id),
+            Account::new(0, 0, &sp),
+        ),
+        &[Check::err(ERR_INVALID_PROOF)],
+    );
+
+    // Someone replaying the owner's data and proof builds a different leaf.
+    let thief = Pubkey::new_unique();
+    mollusk.process_and_validate_instruction(
+        &ix_consume(&program_id, &thief, &pda, data, airdrop, 2, &proof),
+        &accounts(
+            thief,
+            nullifier_pda(&pda, &owned(&thief, &data), &airdrop, &program_id),
+            Account::new(0, 0, &sp),
+        ),
+        &[Check::err(ERR_INVALID_PROOF)],
+    );
+
+    let result = mollusk.process_and_validate_instruction(
+        &ix_consume(&program_id, &payer, &pda, data, airdrop, 2, &proof),
+        &accounts(payer, nullifier, Account::new(0, 0, &sp)),
+        &[Check::success()],
+    );
+    println!("consume CU: {}", result.compute_units_consumed);
+    let nullifier_acc = result.resulting_accounts[2].1.clone();
+    assert_eq!(nullifier_acc.owner, program_id, "nullifier not created");
+    assert_eq!(&nullifier_acc.data[8..40], pda.as_ref(), "tree mismatch");
+    assert_eq!(
+        nullifier_acc.data[40..72],
+        nullifier_hash(&leaves[2], &airdrop),
+        "nullifier hash mismatch"
+    );
+    assert_eq!(
+        &nullifier_acc.data[72..104],
+        payer.as_ref(),
+        "consumer mismatch"
+    );
+
+    // Same leaf, same domain: the nullifier already exists.
+    mollusk.process_and_validate_instruction(
+        &ix_consume(&program_id, &payer, &pda, data, airdrop, 2, &proof),
+        &accounts(payer, nullifier, nullifier_acc),
+        &[Check::err(ERR_ACCOUNT_ALREADY_IN_USE)],
+    );
+
+    // Another domain has its own nullifier.
+    mollusk.process_and_validate_instruction(
+        &ix_consume(&program_id, &payer, &pda, data, vote, 2, &proof),
+        &accounts(
+            payer,
+            nullifier_pda(&pda, &leaves[2], &vote, &program_id),
+            Account::new(0, 0, &sp),
+        ),
+        &[Check::success()],
+    );
+}
@@ -11,12 +11,28 @@ pub enum ErrorCode {
     RandomnessNotRevealed,
     #[msg("Clock error")]
     ClockError,
+    #[msg("Randomness has already been committed for this lottery")]
+    RandomnessAlreadyCommitted,
+    #[msg("Randomness has not been committed for this lottery")]
+    RandomnessNotCommitted,
+    #[msg("Randomness account does not match the committed account")]
+    RandomnessAccountMismatch,
+    #[msg("Randomness must be committed in the same slot as the Switchboard commit")]
+    RandomnessNotFresh,
+    #[msg("Randomness was re-committed after the lottery commitment")]
+    RandomnessSeedMismatch,
+    #[msg("Randomness must be revealed after the commit slot")]
+    RandomnessRevealedBeforeCommit,
+    #[msg("Randomness account is not owned by the Switchboard program")]
+    InvalidRandomnessOwner,
 
     // Lottery state errors
     #[msg("Lottery has already been finalized")]
     LotteryAlreadyFinalized,
     #[msg("VRF result has not been set")]
     VrfNotSet,
+    #[msg("VRF result has already been set")]
+    VrfAlreadySet,
 
     // Winner claim errors
     #[msg("Claimant does not match proof address")]
@@ -31,4 +47,48 @@ pub enum ErrorCode {
     InvalidMerkleProof,
     #[msg("Selection point does not fall within claimant's range - not the winner")]
     NotTheWinner,
+    #[msg("Winner count must be between 1 and the lottery maximum")]
+    InvalidWinnerCount,
+    #[msg("This leaf has already won in this lottery")]
+    LeafAlreadyWon,
+    #[msg("Too many draws landed on existing winners")]
+    TooManyRedraws,
+    #[msg("The current draw can only be skipped after its claim window")]
+    DrawStillClaimable,
+
+    // Prize errors
+    #[msg("Claim window must be positive")]
+    InvalidClaimWindow,
+    #[msg("Each SOL prize share must cover the vault's rent-exempt minimum")]
+    PrizeBelowRentExemption,
+    #[msg("Token prize accounts are missing")]
+    MissingPrizeAccounts,
+    #[msg("Prize mint does not match the lottery")]
+    PrizeMintMismatch,
+    #[msg("The winner's claim window has expired")]
+    ClaimWindowExpired,
+    #[msg("The prize can only be reclaimed after the claim window")]
+    ClaimWindowOpen,
+    #[msg("The prize can only be reclaimed once the randomness reveal has timed out")]
+    RevealWindowOpen,
+    #[msg("The committed randomness was revealed; submit it with set_vrf_result")]
+    RandomnessAlreadyRevealed,
+    #[msg("Prize is still escrowed in the lottery vault")]
+    PrizeStillEscrowed,
+
+    // Distributor errors
+    #[msg("Leaf count must be between 1 and the claim bitmap capacity")]
+    InvalidLeafCount,
+    #[msg("Claim index is outside the distributor's leaf range")]
+    ClaimIndexOutOfRange,
+    #[msg("This leaf has already been claimed in the current round")]
+    AlreadyClaimed,
+    #[msg("Clawback time must be in the future")]
+    InvalidClawbackTime,
+    #[msg("The claim window for this round has closed")]
+    ClaimWindowClosed,
+    #[msg("Clawback is only allowed after the claim window closes")]
+    ClawbackNotReady,
+    #[msg("Amount overflow")]
+    AmountOverflow,
 }
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::errors::ErrorCode;
+use crate::merkle::{compute_claim_leaf_hash, verify_proof};
+use crate::state::{ClaimBitmap, Distributor};
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(
+        mut,
+        seeds = [
+            Distributor::SEED_PREFIX,
+            distributor.authority.as_ref(),
+            distributor.mint.as_ref()
+        ],
+        bump = distributor.bump,
+        has_one = mint,
+        has_one = vault
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    /// Bitmap of the distributor's current round
+    #[account(
+        mut,
+        seeds = [
+            ClaimBitmap::SEED_PREFIX,
+            distributor.key().as_ref(),
+            &distributor.round.to_le_bytes()
+        ],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        associated_token::mint = mint,
+        associated_token::authority = claimant,
+        associated_token::token_program = token_program,
+    )]
+    pub claimant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The claimant - must be the address in the leaf
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimAirdrop<'info> {
+    pub fn claim_airdrop(&mut self, index: u32, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        let distributor = &mut self.distributor;
+
+        // 1. Claims close at the clawback time
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clock.unix_timestamp < distributor.clawback_at,
+            ErrorCode::ClaimWindowClosed
+        );
+
+        // 2. The index must map to a bit in this round's bitmap
+        require!(
+            index < distributor.num_leaves,
+            ErrorCode::ClaimIndexOutOfRange
+        );
+        require!(
+            !self.claim_bitmap.is_claimed(index),
+            ErrorCode::AlreadyClaimed
+        );
+
+        // 3. The leaf binds the index, the claimant and the amount
+        let leaf_hash = compute_claim_leaf_hash(index, &self.claimant.key(), amount);
+        require!(
+            verify_proof(leaf_hash, &proof, &distributor.merkle_root),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        // 4. Mark claimed before moving tokens
+        self.claim_bitmap.set_claimed(index);
+        distributor.num_claimed += 1;
+        distributor.total_claimed = distributor
+            .total_claimed
+            .checked_add(amount)
+            .ok_or(ErrorCode::AmountOverflow)?;
+
+        // 5. Pay out from the vault, signed by the distributor PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            Distributor::SEED_PREFIX,
+            distributor.authority.as_ref(),
+            distributor.mint.as_ref(),
+            &[distributor.bump],
+        ]];
+        let cpi_accounts = TransferChecked {
+            mint: self.mint.to_account_info(),
+            from: self.vault.to_account_info(),
+            to: self.claimant_token_account.to_account_info(),
+            authority: distributor.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
+            .with_signer(signer_seeds);
+        token_interface::transfer_checked(cpi_context, amount, self.mint.decimals)?;
+
+        msg!("Airdrop claimed: {} tokens", amount);
+        msg!("Claimant: {}, index: {}", self.claimant.key(), index);
+
+        Ok(())
+    }
+}
@@ -1,22 +1,57 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::errors::ErrorCode;
-use crate::merkle::{compute_leaf_hash, compute_selection_point, is_winner, verify_proof};
-use crate::state::{Lottery, WinnerProof};
+use crate::merkle::{compute_draw_point, compute_leaf_hash, is_winner, verify_proof};
+use crate::prize::{pay_out_prize, TokenPrize};
+use crate::state::{Lottery, Winner, WinnerProof};
 
 #[derive(Accounts)]
 pub struct ClaimWinner<'info> {
     #[account(
         mut,
-        seeds = [Lottery::SEED_PREFIX, lottery.authority.as_ref()],
+        seeds = [
+            Lottery::SEED_PREFIX,
+            lottery.authority.as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
         bump = lottery.bump,
         constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized,
         constraint = lottery.vrf_result != [0u8; 32] @ ErrorCode::VrfNotSet
     )]
     pub lottery: Account<'info, Lottery>,
 
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_VAULT_SEED, lottery.key().as_ref()],
+        bump = lottery.prize_vault_bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    /// Prize mint - omit for a SOL prize
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_TOKEN_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub prize_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::authority = claimant,
+        token::token_program = token_program,
+    )]
+    pub claimant_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     /// The claimant - must match the address in the proof
+    #[account(mut)]
     pub claimant: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> ClaimWinner<'info> {
@@ -65,31 +100,87 @@ impl<'info> ClaimWinner<'info> {
             ErrorCode::InvalidMerkleProof
         );
 
-        // 7. Compute the selection point from VRF result
-        let selection_point = compute_selection_point(&lottery.vrf_result, lottery.total_weight);
+        // 7. A leaf wins at most once (draws are without replacement)
+        require!(
+            !lottery.has_won(proof.prev_cumulative),
+            ErrorCode::LeafAlreadyWon
+        );
+
+        // 8. Compute the next draw's selection point, skipping draws that
+        //    land on leaves that have already won
+        let mut selection_point =
+            compute_draw_point(&lottery.vrf_result, lottery.draws, lottery.total_weight);
+        let mut redraws = 0;
+        while lottery
+            .winners
+            .iter()
+            .any(|winner| is_winner(selection_point, winner.prev_cumulative, winner.cumulative))
+        {
+            redraws += 1;
+            require!(redraws <= Lottery::MAX_REDRAWS, ErrorCode::TooManyRedraws);
+            lottery.draws += 1;
+            selection_point =
+                compute_draw_point(&lottery.vrf_result, lottery.draws, lottery.total_weight);
+        }
 
-        msg!("Selection point: {}", selection_point);
+        msg!(
+            "Draw {}: selection point {}",
+            lottery.draws,
+            selection_point
+        );
         msg!(
             "Claimant range: [{}, {})",
             proof.prev_cumulative,
             proof.cumulative
         );
 
-        // 8. Verify the selection point falls within the claimant's range
+        // 9. Verify the selection point falls within the claimant's range
         require!(
             is_winner(selection_point, proof.prev_cumulative, proof.cumulative),
             ErrorCode::NotTheWinner
         );
 
-        // 9. Set the winner and finalize
-        lottery.winner = Some(proof.address);
-        lottery.winner_weight = proof.balance;
-        lottery.finalized = true;
+        // 10. The winner must claim within the current draw's window
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clock.unix_timestamp < lottery.claim_deadline(),
+            ErrorCode::ClaimWindowExpired
+        );
+
+        // 11. Record the winner; finalize once all winners are drawn
+        let prize_share = lottery.next_prize_share();
+        lottery.winners.push(Winner {
+            address: proof.address,
+            weight: proof.balance,
+            prev_cumulative: proof.prev_cumulative,
+            cumulative: proof.cumulative,
+        });
+        lottery.draws += 1;
+        lottery.draw_opened_at = clock.unix_timestamp;
+        lottery.prize_paid += prize_share;
+        lottery.finalized = lottery.winners.len() == lottery.num_winners as usize;
 
         msg!("Winner claimed successfully!");
         msg!("Winner: {}", proof.address);
         msg!("Winner weight: {}", proof.balance);
 
-        Ok(())
+        // 12. Pay the winner's share in the same transaction
+        let token_prize = match self.lottery.prize_mint {
+            Some(_) => Some(TokenPrize::from_accounts(
+                self.prize_mint.as_ref(),
+                self.prize_token_vault.as_ref(),
+                self.claimant_token_account.as_ref(),
+                self.token_program.as_ref(),
+            )?),
+            None => None,
+        };
+        pay_out_prize(
+            &self.lottery,
+            &self.prize_vault,
+            &self.claimant.to_account_info(),
+            &self.system_program,
+            token_prize,
+            prize_share,
+        )
     }
 }
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::ErrorCode;
+use crate::state::Distributor;
+
+#[derive(Accounts)]
+pub struct Clawback<'info> {
+    #[account(
+        seeds = [
+            Distributor::SEED_PREFIX,
+            authority.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump = distributor.bump,
+        has_one = authority,
+        has_one = mint,
+        has_one = vault
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Clawback<'info> {
+    pub fn clawback(&mut self) -> Result<()> {
+        let distributor = &self.distributor;
+
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clock.unix_timestamp >= distributor.clawback_at,
+            ErrorCode::ClawbackNotReady
+        );
+
+        let remaining = self.vault.amount;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            Distributor::SEED_PREFIX,
+            distributor.authority.as_ref(),
+            distributor.mint.as_ref(),
+            &[distributor.bump],
+        ]];
+        let cpi_accounts = TransferChecked {
+            mint: self.mint.to_account_info(),
+            from: self.vault.to_account_info(),
+            to: self.authority_token_account.to_account_info(),
+            authority: distributor.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
+            .with_signer(signer_seeds);
+        token_interface::transfer_checked(cpi_context, remaining, self.mint.decimals)?;
+
+        msg!("Clawed back {} unclaimed tokens", remaining);
+
+        Ok(())
+    }
+}
@@ -1,24 +1,62 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, TokenAccount, TokenInterface};
 
+use crate::errors::ErrorCode;
 use crate::state::Lottery;
 
 #[derive(Accounts)]
 pub struct CloseLottery<'info> {
     #[account(
         mut,
-        seeds = [Lottery::SEED_PREFIX, authority.key().as_ref()],
+        seeds = [
+            Lottery::SEED_PREFIX,
+            authority.key().as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
         bump = lottery.bump,
         has_one = authority,
+        constraint = lottery.finalized || lottery.prize_amount == 0 @ ErrorCode::PrizeStillEscrowed,
         close = authority
     )]
     pub lottery: Account<'info, Lottery>,
 
+    /// Token prize vault, closed alongside the lottery when present
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_TOKEN_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub prize_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 impl<'info> CloseLottery<'info> {
     pub fn close_lottery(&mut self) -> Result<()> {
+        if let (Some(vault), Some(token_program)) = (&self.prize_token_vault, &self.token_program) {
+            let lottery_id = self.lottery.lottery_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                Lottery::SEED_PREFIX,
+                self.lottery.authority.as_ref(),
+                &lottery_id,
+                &[self.lottery.bump],
+            ]];
+            let cpi_accounts = CloseAccount {
+                account: vault.to_account_info(),
+                destination: self.authority.to_account_info(),
+                authority: self.lottery.to_account_info(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_interface::close_account(cpi_context)?;
+        }
+
         msg!("Lottery closed, rent returned to authority");
         Ok(())
     }
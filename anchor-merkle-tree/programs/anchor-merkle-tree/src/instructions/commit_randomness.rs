@@ -1,13 +1,34 @@
 use anchor_lang::prelude::*;
-use switchboard_on_demand::accounts::RandomnessAccountData;
 
 use crate::errors::ErrorCode;
+use crate::randomness::read_randomness;
+use crate::state::Lottery;
 
+/// Bind a lottery to one Switchboard randomness account.
+///
+/// The Merkle root is fixed at `initialize_lottery`, so by the time this runs
+/// the holder set is frozen. Must be sent in the same transaction as the
+/// Switchboard commit so the randomness is still unknown to everyone.
 #[derive(Accounts)]
 pub struct CommitRandomness<'info> {
+    #[account(
+        mut,
+        seeds = [
+            Lottery::SEED_PREFIX,
+            authority.key().as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
+        bump = lottery.bump,
+        has_one = authority,
+        constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized,
+        constraint = lottery.seed_slot == 0 @ ErrorCode::RandomnessAlreadyCommitted
+    )]
+    pub lottery: Account<'info, Lottery>,
+
     /// CHECK: The randomness account data is validated manually within the handler
     pub randomness_account: AccountInfo<'info>,
-    pub user: Signer<'info>,
+
+    pub authority: Signer<'info>,
 }
 
 impl<'info> CommitRandomness<'info> {
@@ -16,8 +37,17 @@ impl<'info> CommitRandomness<'info> {
         let clock = anchor_lang::prelude::Clock::get().map_err(|_| ErrorCode::ClockError)?;
 
         // Parse the randomness account data to verify it's valid
-        let randomness_data = RandomnessAccountData::parse(self.randomness_account.data.borrow())
-            .map_err(|_| ErrorCode::InvalidRandomnessData)?;
+        let randomness_data = read_randomness(&self.randomness_account, &clock)?;
+
+        // The Switchboard commit must have landed in the previous slot
+        require!(
+            randomness_data.seed_slot == clock.slot.saturating_sub(1),
+            ErrorCode::RandomnessNotFresh
+        );
+
+        // Record the commitment; set_vrf_result only accepts this account
+        self.lottery.randomness_account = self.randomness_account.key();
+        self.lottery.seed_slot = randomness_data.seed_slot;
 
         msg!("Randomness committed successfully!");
         msg!("Seed slot: {}", randomness_data.seed_slot);
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::state::Distributor;
+
+#[derive(Accounts)]
+pub struct FundDistributor<'info> {
+    #[account(
+        seeds = [
+            Distributor::SEED_PREFIX,
+            authority.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump = distributor.bump,
+        has_one = authority,
+        has_one = mint,
+        has_one = vault
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    #[account(mut)]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundDistributor<'info> {
+    pub fn fund_distributor(&mut self, amount: u64) -> Result<()> {
+        let cpi_accounts = TransferChecked {
+            mint: self.mint.to_account_info(),
+            from: self.authority_token_account.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(self.token_program.to_account_info(), cpi_accounts);
+        token_interface::transfer_checked(cpi_context, amount, self.mint.decimals)?;
+
+        msg!("Distributor funded with {} tokens", amount);
+
+        Ok(())
+    }
+}
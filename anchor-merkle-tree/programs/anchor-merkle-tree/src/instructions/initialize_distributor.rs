@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::errors::ErrorCode;
+use crate::state::{ClaimBitmap, Distributor};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], num_leaves: u32)]
+pub struct InitializeDistributor<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Distributor::INIT_SPACE,
+        seeds = [
+            Distributor::SEED_PREFIX,
+            authority.key().as_ref(),
+            mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    /// Claim bitmap for round 0
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimBitmap::space(num_leaves),
+        seeds = [
+            ClaimBitmap::SEED_PREFIX,
+            distributor.key().as_ref(),
+            &0u32.to_le_bytes()
+        ],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = distributor,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeDistributor<'info> {
+    pub fn initialize_distributor(
+        &mut self,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        clawback_at: i64,
+        bumps: &InitializeDistributorBumps,
+    ) -> Result<()> {
+        require!(
+            num_leaves > 0 && num_leaves <= ClaimBitmap::MAX_LEAVES,
+            ErrorCode::InvalidLeafCount
+        );
+
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clawback_at > clock.unix_timestamp,
+            ErrorCode::InvalidClawbackTime
+        );
+
+        self.distributor.set_inner(Distributor {
+            authority: self.authority.key(),
+            mint: self.mint.key(),
+            vault: self.vault.key(),
+            merkle_root,
+            round: 0,
+            num_leaves,
+            num_claimed: 0,
+            total_claimed: 0,
+            clawback_at,
+            bump: bumps.distributor,
+        });
+
+        self.claim_bitmap.set_inner(ClaimBitmap {
+            distributor: self.distributor.key(),
+            round: 0,
+            bits: vec![0u8; (num_leaves as usize).div_ceil(8)],
+        });
+
+        msg!("Distributor initialized for mint {}", self.mint.key());
+        msg!("Leaves: {}, clawback at: {}", num_leaves, clawback_at);
+
+        Ok(())
+    }
+}
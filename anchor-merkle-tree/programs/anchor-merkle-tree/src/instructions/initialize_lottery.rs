@@ -1,45 +1,156 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::state::Lottery;
+use crate::errors::ErrorCode;
+use crate::state::{Lottery, PrizeConfig};
 
 #[derive(Accounts)]
+#[instruction(lottery_id: u64)]
 pub struct InitializeLottery<'info> {
     #[account(
         init,
         payer = authority,
         space = 8 + Lottery::INIT_SPACE,
-        seeds = [Lottery::SEED_PREFIX, authority.key().as_ref()],
+        seeds = [
+            Lottery::SEED_PREFIX,
+            authority.key().as_ref(),
+            &lottery_id.to_le_bytes()
+        ],
         bump
     )]
     pub lottery: Account<'info, Lottery>,
 
+    /// Holds a SOL prize; unused for token prizes
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    /// Prize mint - omit for a SOL prize
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [Lottery::PRIZE_TOKEN_VAULT_SEED, lottery.key().as_ref()],
+        bump,
+        token::mint = prize_mint,
+        token::authority = lottery,
+        token::token_program = token_program,
+    )]
+    pub prize_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> InitializeLottery<'info> {
     pub fn initialize_lottery(
         &mut self,
+        lottery_id: u64,
         merkle_root: [u8; 32],
         total_weight: u128,
+        prize: PrizeConfig,
         bumps: &InitializeLotteryBumps,
     ) -> Result<()> {
+        let PrizeConfig {
+            num_winners,
+            prize_amount,
+            claim_window,
+        } = prize;
+
+        require!(claim_window > 0, ErrorCode::InvalidClaimWindow);
+        require!(
+            num_winners > 0 && num_winners as usize <= Lottery::MAX_WINNERS,
+            ErrorCode::InvalidWinnerCount
+        );
+
         self.lottery.set_inner(Lottery {
             authority: self.authority.key(),
+            lottery_id,
             merkle_root,
             vrf_result: [0u8; 32],
+            randomness_account: Pubkey::default(),
+            seed_slot: 0,
             total_weight,
-            winner: None,
-            winner_weight: 0,
+            num_winners,
+            winners: Vec::new(),
+            draws: 0,
             finalized: false,
+            prize_mint: self.prize_mint.as_ref().map(|mint| mint.key()),
+            prize_amount,
+            prize_paid: 0,
+            claim_window,
+            vrf_set_at: 0,
+            draw_opened_at: 0,
             bump: bumps.lottery,
+            prize_vault_bump: bumps.prize_vault,
         });
 
-        msg!("Lottery initialized with merkle root");
+        if prize_amount > 0 {
+            self.deposit_prize(prize_amount, num_winners)?;
+        }
+
+        msg!("Lottery {} initialized with merkle root", lottery_id);
         msg!("Merkle Root: {}", total_weight);
+        msg!("Winners: {}, prize: {}", num_winners, prize_amount);
+        msg!("Claim window: {}s", claim_window);
 
         Ok(())
     }
+
+    /// Escrow the prize in the lottery's vault
+    fn deposit_prize(&self, amount: u64, num_winners: u8) -> Result<()> {
+        match (
+            &self.prize_mint,
+            &self.prize_token_vault,
+            &self.authority_token_account,
+            &self.token_program,
+        ) {
+            (None, ..) => {
+                // A zero-data system account must stay rent exempt until it
+                // is drained; the smallest balance left before the last
+                // payout is one share plus the rounding remainder
+                let share = amount / num_winners as u64;
+                let last_payout = amount - share * (num_winners as u64 - 1);
+                require!(
+                    last_payout >= Rent::get()?.minimum_balance(0),
+                    ErrorCode::PrizeBelowRentExemption
+                );
+
+                let cpi_accounts = Transfer {
+                    from: self.authority.to_account_info(),
+                    to: self.prize_vault.to_account_info(),
+                };
+                let cpi_context =
+                    CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_context, amount)
+            }
+            (Some(mint), Some(vault), Some(from), Some(token_program)) => {
+                let cpi_accounts = TransferChecked {
+                    mint: mint.to_account_info(),
+                    from: from.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                };
+                let cpi_context = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+                token_interface::transfer_checked(cpi_context, amount, mint.decimals)
+            }
+            _ => err!(ErrorCode::MissingPrizeAccounts),
+        }
+    }
 }
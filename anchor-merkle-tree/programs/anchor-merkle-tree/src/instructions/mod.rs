@@ -1,13 +1,27 @@
+pub mod claim_airdrop;
 pub mod claim_winner;
+pub mod clawback;
 pub mod close_lottery;
 pub mod commit_randomness;
+pub mod fund_distributor;
+pub mod initialize_distributor;
 pub mod initialize_lottery;
+pub mod reclaim_prize;
 pub mod reveal_randomness;
+pub mod rotate_root;
 pub mod set_vrf_result;
+pub mod skip_draw;
 
+pub use claim_airdrop::*;
 pub use claim_winner::*;
+pub use clawback::*;
 pub use close_lottery::*;
 pub use commit_randomness::*;
+pub use fund_distributor::*;
+pub use initialize_distributor::*;
 pub use initialize_lottery::*;
+pub use reclaim_prize::*;
 pub use reveal_randomness::*;
+pub use rotate_root::*;
 pub use set_vrf_result::*;
+pub use skip_draw::*;
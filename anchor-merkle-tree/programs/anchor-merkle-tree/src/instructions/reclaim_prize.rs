@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::errors::ErrorCode;
+use crate::prize::{pay_out_prize, TokenPrize};
+use crate::randomness::read_randomness;
+use crate::state::Lottery;
+
+#[derive(Accounts)]
+pub struct ReclaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [
+            Lottery::SEED_PREFIX,
+            authority.key().as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
+        bump = lottery.bump,
+        has_one = authority,
+        constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_VAULT_SEED, lottery.key().as_ref()],
+        bump = lottery.prize_vault_bump
+    )]
+    pub prize_vault: SystemAccount<'info>,
+
+    /// CHECK: Must be the committed account; read by `read_randomness`.
+    /// Only needed when reclaiming after a reveal timeout.
+    pub randomness_account: Option<AccountInfo<'info>>,
+
+    /// Prize mint - omit for a SOL prize
+    pub prize_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Lottery::PRIZE_TOKEN_VAULT_SEED, lottery.key().as_ref()],
+        bump
+    )]
+    pub prize_token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = prize_mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReclaimPrize<'info> {
+    pub fn reclaim_prize(&mut self) -> Result<()> {
+        // 1. Only once nobody claimed within the window, or once the
+        //    committed randomness was never revealed in time
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        if self.lottery.vrf_result == [0u8; 32] {
+            require!(
+                self.lottery.seed_slot != 0,
+                ErrorCode::RandomnessNotCommitted
+            );
+            require!(
+                clock.slot >= self.lottery.reveal_deadline(),
+                ErrorCode::RevealWindowOpen
+            );
+
+            // A reveal the authority read but never submitted, or replaced
+            // by re-committing the account, doesn't count as a timeout
+            let randomness = self
+                .randomness_account
+                .as_ref()
+                .ok_or(ErrorCode::RandomnessAccountMismatch)?;
+            require_keys_eq!(
+                randomness.key(),
+                self.lottery.randomness_account,
+                ErrorCode::RandomnessAccountMismatch
+            );
+            let reading = read_randomness(randomness, &clock)?;
+            require!(
+                reading.seed_slot == self.lottery.seed_slot && reading.reveal_slot == 0,
+                ErrorCode::RandomnessAlreadyRevealed
+            );
+        } else {
+            require!(
+                clock.unix_timestamp >= self.lottery.claim_deadline(),
+                ErrorCode::ClaimWindowOpen
+            );
+        }
+
+        // 2. Finalize; winners drawn so far keep their shares
+        let remaining = self.lottery.prize_amount - self.lottery.prize_paid;
+        self.lottery.prize_paid = self.lottery.prize_amount;
+        self.lottery.finalized = true;
+
+        // 3. Return the unpaid prize to the authority
+        let token_prize = match self.lottery.prize_mint {
+            Some(_) => Some(TokenPrize::from_accounts(
+                self.prize_mint.as_ref(),
+                self.prize_token_vault.as_ref(),
+                self.authority_token_account.as_ref(),
+                self.token_program.as_ref(),
+            )?),
+            None => None,
+        };
+        pay_out_prize(
+            &self.lottery,
+            &self.prize_vault,
+            &self.authority.to_account_info(),
+            &self.system_program,
+            token_prize,
+            remaining,
+        )?;
+
+        msg!("Prize reclaimed by authority");
+
+        Ok(())
+    }
+}
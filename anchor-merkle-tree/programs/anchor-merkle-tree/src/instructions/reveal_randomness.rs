@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use switchboard_on_demand::accounts::RandomnessAccountData;
 
 use crate::errors::ErrorCode;
+use crate::randomness::Switchboard;
 
 #[derive(Accounts)]
 pub struct RevealAndLog<'info> {
-    /// CHECK: The randomness account data is validated manually within the handler
+    /// CHECK: Owner and data are validated by `Switchboard::parse`
     pub randomness_account: AccountInfo<'info>,
     pub user: Signer<'info>,
 }
@@ -16,8 +16,7 @@ impl<'info> RevealAndLog<'info> {
         let clock: Clock = Clock::get()?;
 
         // Parse the randomness account data
-        let randomness_data = RandomnessAccountData::parse(self.randomness_account.data.borrow())
-            .map_err(|_| ErrorCode::InvalidRandomnessData)?;
+        let randomness_data = Switchboard::parse(&self.randomness_account)?;
 
         // Check if randomness has been revealed
         if randomness_data.reveal_slot == 0 {
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{ClaimBitmap, Distributor};
+
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32], num_leaves: u32)]
+pub struct RotateRoot<'info> {
+    #[account(
+        mut,
+        seeds = [
+            Distributor::SEED_PREFIX,
+            authority.key().as_ref(),
+            distributor.mint.as_ref()
+        ],
+        bump = distributor.bump,
+        has_one = authority
+    )]
+    pub distributor: Account<'info, Distributor>,
+
+    /// Bitmap of the round being replaced; its rent goes back to the authority
+    #[account(
+        mut,
+        seeds = [
+            ClaimBitmap::SEED_PREFIX,
+            distributor.key().as_ref(),
+            &distributor.round.to_le_bytes()
+        ],
+        bump,
+        close = authority
+    )]
+    pub previous_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClaimBitmap::space(num_leaves),
+        seeds = [
+            ClaimBitmap::SEED_PREFIX,
+            distributor.key().as_ref(),
+            &(distributor.round + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RotateRoot<'info> {
+    /// Start the next round with a new root. Tokens left in the vault carry
+    /// over, so a round can be topped up with `fund_distributor` instead of
+    /// clawed back.
+    pub fn rotate_root(
+        &mut self,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        clawback_at: i64,
+    ) -> Result<()> {
+        require!(
+            num_leaves > 0 && num_leaves <= ClaimBitmap::MAX_LEAVES,
+            ErrorCode::InvalidLeafCount
+        );
+
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clawback_at > clock.unix_timestamp,
+            ErrorCode::InvalidClawbackTime
+        );
+
+        let distributor = &mut self.distributor;
+        distributor.round += 1;
+        distributor.merkle_root = merkle_root;
+        distributor.num_leaves = num_leaves;
+        distributor.num_claimed = 0;
+        distributor.total_claimed = 0;
+        distributor.clawback_at = clawback_at;
+
+        self.claim_bitmap.set_inner(ClaimBitmap {
+            distributor: distributor.key(),
+            round: distributor.round,
+            bits: vec![0u8; (num_leaves as usize).div_ceil(8)],
+        });
+
+        msg!("Distributor rotated to round {}", distributor.round);
+        msg!("Leaves: {}, clawback at: {}", num_leaves, clawback_at);
+
+        Ok(())
+    }
+}
@@ -1,51 +1,66 @@
 use anchor_lang::prelude::*;
-use switchboard_on_demand::RandomnessAccountData;
 
 use crate::errors::ErrorCode;
+use crate::randomness::read_randomness;
 use crate::state::Lottery;
 
+/// Permissionless: the randomness account checks below pin the result, so
+/// anyone can submit it and the authority can't withhold a draw it dislikes.
 #[derive(Accounts)]
 pub struct SetVrfResult<'info> {
     #[account(
         mut,
-        seeds = [Lottery::SEED_PREFIX, authority.key().as_ref()],
+        seeds = [
+            Lottery::SEED_PREFIX,
+            lottery.authority.as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
         bump = lottery.bump,
-        has_one = authority,
-        constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized
+        constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized,
+        constraint = lottery.seed_slot != 0 @ ErrorCode::RandomnessNotCommitted,
+        constraint = lottery.randomness_account == randomness_account.key()
+            @ ErrorCode::RandomnessAccountMismatch,
+        constraint = lottery.vrf_result == [0u8; 32] @ ErrorCode::VrfAlreadySet
     )]
     pub lottery: Account<'info, Lottery>,
 
-    /// CHECK: Validated by Switchboard SDK
+    /// CHECK: Validated by the randomness provider; must be the committed account
     pub randomness_account: AccountInfo<'info>,
-
-    pub authority: Signer<'info>,
 }
 
 impl<'info> SetVrfResult<'info> {
     pub fn set_vrf_result(&mut self) -> Result<()> {
-        // Parse the Switchboard randomness account
-        let randomness_data =
-            RandomnessAccountData::parse(self.randomness_account.data.borrow()).map_err(|_| {
-                msg!("Failed to parse randomness account data");
-                ErrorCode::InvalidRandomnessData
-            })?;
+        // Get the current clock
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+
+        // Read the randomness account (Switchboard, or a mock in tests)
+        let randomness_data = read_randomness(&self.randomness_account, &clock)?;
 
         // Ensure randomness has been revealed
         if randomness_data.reveal_slot == 0 {
             return Err(ErrorCode::RandomnessNotRevealed.into());
         }
 
-        // Get the current clock
-        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        // Ensure this is the commitment we recorded, revealed after it
+        require!(
+            randomness_data.seed_slot == self.lottery.seed_slot,
+            ErrorCode::RandomnessSeedMismatch
+        );
+        require!(
+            randomness_data.reveal_slot > self.lottery.seed_slot,
+            ErrorCode::RandomnessRevealedBeforeCommit
+        );
 
         // Get the random value (32 bytes)
-        let vrf_result = randomness_data.get_value(clock.slot).map_err(|e| {
-            msg!("Failed to get randomness value: {:?}", e);
+        let vrf_result = randomness_data.value.ok_or_else(|| {
+            msg!("Failed to get randomness value at slot {}", clock.slot);
             ErrorCode::RandomnessNotResolved
         })?;
 
-        // Store the VRF result
+        // Store the VRF result; the first draw's claim window starts now
         self.lottery.vrf_result = vrf_result;
+        self.lottery.vrf_set_at = clock.unix_timestamp;
+        self.lottery.draw_opened_at = clock.unix_timestamp;
 
         msg!("VRF result set successfully");
         msg!(
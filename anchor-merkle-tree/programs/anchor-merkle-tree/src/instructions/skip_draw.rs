@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::Lottery;
+
+/// Skip an unclaimed draw so later draws are not blocked by it.
+///
+/// Permissionless: once the current draw's window has passed, anyone (usually
+/// the next winner) may move the lottery on to the next draw.
+#[derive(Accounts)]
+pub struct SkipDraw<'info> {
+    #[account(
+        mut,
+        seeds = [
+            Lottery::SEED_PREFIX,
+            lottery.authority.as_ref(),
+            &lottery.lottery_id.to_le_bytes()
+        ],
+        bump = lottery.bump,
+        constraint = !lottery.finalized @ ErrorCode::LotteryAlreadyFinalized,
+        constraint = lottery.vrf_result != [0u8; 32] @ ErrorCode::VrfNotSet
+    )]
+    pub lottery: Account<'info, Lottery>,
+}
+
+impl<'info> SkipDraw<'info> {
+    pub fn skip_draw(&mut self) -> Result<()> {
+        let clock = Clock::get().map_err(|_| ErrorCode::ClockError)?;
+        require!(
+            clock.unix_timestamp >= self.lottery.claim_deadline(),
+            ErrorCode::DrawStillClaimable
+        );
+
+        // The winner slot stays open; the next draw index picks a new point
+        self.lottery.draws += 1;
+        self.lottery.draw_opened_at = clock.unix_timestamp;
+
+        msg!("Draw skipped, draw {} is now open", self.lottery.draws);
+
+        Ok(())
+    }
+}
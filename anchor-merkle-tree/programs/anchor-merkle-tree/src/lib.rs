@@ -3,10 +3,12 @@ use anchor_lang::prelude::*;
 pub mod errors;
 pub mod instructions;
 pub mod merkle;
+pub mod prize;
+pub mod randomness;
 pub mod state;
 
 use instructions::*;
-use state::WinnerProof;
+use state::{PrizeConfig, WinnerProof};
 
 declare_id!("HSBQg9YYMu8DtD1pgTfwxTqTdGWTKHtxSjg5wT3bz1mi");
 
@@ -19,6 +21,7 @@ pub mod anchor_merkle_tree {
         Ok(())
     }
 
+    /// Bind the lottery to a freshly committed Switchboard randomness account
     pub fn commit_randomness(ctx: Context<CommitRandomness>) -> Result<()> {
         ctx.accounts.commit_randomness()
     }
@@ -27,30 +30,89 @@ pub mod anchor_merkle_tree {
         ctx.accounts.reveal_and_log_random()
     }
 
-    /// Initialize a new lottery with a Merkle root and total weight
+    /// Initialize a new lottery with a Merkle root and total weight, escrowing
+    /// a SOL prize or, when a prize mint is passed, an SPL prize split across
+    /// `num_winners` winners
     pub fn initialize_lottery(
         ctx: Context<InitializeLottery>,
+        lottery_id: u64,
         merkle_root: [u8; 32],
         total_weight: u128,
+        prize: PrizeConfig,
     ) -> Result<()> {
         ctx.accounts
-            .initialize_lottery(merkle_root, total_weight, &ctx.bumps)
+            .initialize_lottery(lottery_id, merkle_root, total_weight, prize, &ctx.bumps)
     }
 
-    /// Set the VRF result from a Switchboard randomness account
+    /// Set the VRF result from the committed Switchboard randomness account;
+    /// anyone can submit it
     pub fn set_vrf_result(ctx: Context<SetVrfResult>) -> Result<()> {
         ctx.accounts.set_vrf_result()
     }
 
-    /// Claim winner status by providing a valid Merkle proof
+    /// Claim the next winner slot by providing a valid Merkle proof
     pub fn claim_winner(ctx: Context<ClaimWinner>, proof: WinnerProof) -> Result<()> {
         ctx.accounts.claim_winner(proof)
     }
 
+    /// Skip a draw whose winner did not claim within its window, opening the
+    /// next draw for the same winner slot
+    pub fn skip_draw(ctx: Context<SkipDraw>) -> Result<()> {
+        ctx.accounts.skip_draw()
+    }
+
+    /// Return the prize to the authority if the winner did not claim in time,
+    /// or if the committed randomness was never revealed
+    pub fn reclaim_prize(ctx: Context<ReclaimPrize>) -> Result<()> {
+        ctx.accounts.reclaim_prize()
+    }
+
     /// Close lottery and return rent to authority
     pub fn close_lottery(ctx: Context<CloseLottery>) -> Result<()> {
         ctx.accounts.close_lottery()
     }
+
+    /// Create an airdrop distributor for a mint with the first round's root
+    pub fn initialize_distributor(
+        ctx: Context<InitializeDistributor>,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        clawback_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .initialize_distributor(merkle_root, num_leaves, clawback_at, &ctx.bumps)
+    }
+
+    /// Move tokens from the authority into the distributor vault
+    pub fn fund_distributor(ctx: Context<FundDistributor>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_distributor(amount)
+    }
+
+    /// Claim an airdrop allocation by proving `(index, claimant, amount)`
+    pub fn claim_airdrop(
+        ctx: Context<ClaimAirdrop>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.claim_airdrop(index, amount, proof)
+    }
+
+    /// Return unclaimed tokens to the authority once the claim window closes
+    pub fn clawback(ctx: Context<Clawback>) -> Result<()> {
+        ctx.accounts.clawback()
+    }
+
+    /// Start a new distribution round with a fresh root and claim bitmap
+    pub fn rotate_root(
+        ctx: Context<RotateRoot>,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        clawback_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .rotate_root(merkle_root, num_leaves, clawback_at)
+    }
 }
 
 #[derive(Accounts)]
@@ -23,6 +23,17 @@ pub fn compute_leaf_hash(
     keccak256(&data)
 }
 
+/// Compute the leaf hash for a distributor claim
+/// leaf = keccak256(index || address || amount)
+/// The index is the claimant's bit in the round's claim bitmap
+pub fn compute_claim_leaf_hash(index: u32, address: &Pubkey, amount: u64) -> [u8; 32] {
+    let mut data = Vec::with_capacity(4 + 32 + 8); // 44 bytes
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(address.as_ref());
+    data.extend_from_slice(&amount.to_le_bytes());
+    keccak256(&data)
+}
+
 /// Compute hash of two sibling nodes
 /// For deterministic ordering, we sort the two hashes before hashing
 /// This ensures the same root regardless of proof direction
@@ -66,6 +77,15 @@ pub fn compute_selection_point(vrf_result: &[u8; 32], total_weight: u128) -> u12
     random_value % total_weight
 }
 
+/// Selection point for the `draw`-th draw of a multi-winner lottery
+/// point = keccak256(vrf_result || draw) interpreted as in `compute_selection_point`
+pub fn compute_draw_point(vrf_result: &[u8; 32], draw: u32, total_weight: u128) -> u128 {
+    let mut data = Vec::with_capacity(32 + 4); // 36 bytes
+    data.extend_from_slice(vrf_result);
+    data.extend_from_slice(&draw.to_le_bytes());
+    compute_selection_point(&keccak256(&data), total_weight)
+}
+
 /// Check if a selection point falls within the given range [prev_cumulative, cumulative)
 pub fn is_winner(selection_point: u128, prev_cumulative: u128, cumulative: u128) -> bool {
     selection_point >= prev_cumulative && selection_point < cumulative
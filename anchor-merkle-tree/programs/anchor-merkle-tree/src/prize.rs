@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::errors::ErrorCode;
+use crate::state::Lottery;
+
+/// Token accounts needed to move an SPL prize out of the lottery
+pub struct TokenPrize<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub recipient: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenPrize<'a, 'info> {
+    /// Collect the optional token accounts of an instruction; all of them
+    /// must be present when the lottery pays out in tokens
+    pub fn from_accounts(
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        recipient: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Self> {
+        match (mint, vault, recipient, token_program) {
+            (Some(mint), Some(vault), Some(recipient), Some(token_program)) => Ok(Self {
+                mint,
+                vault,
+                recipient,
+                token_program,
+            }),
+            _ => err!(ErrorCode::MissingPrizeAccounts),
+        }
+    }
+}
+
+/// Pay `amount` of the escrowed prize to `recipient`
+///
+/// SOL prizes leave the system-owned prize vault, signed by the vault's own
+/// seeds. Token prizes leave the prize token vault, signed by the lottery PDA.
+pub fn pay_out_prize<'info>(
+    lottery: &Account<'info, Lottery>,
+    prize_vault: &SystemAccount<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token_prize: Option<TokenPrize<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match lottery.prize_mint {
+        None => {
+            let lottery_key = lottery.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                Lottery::PRIZE_VAULT_SEED,
+                lottery_key.as_ref(),
+                &[lottery.prize_vault_bump],
+            ]];
+            let cpi_accounts = Transfer {
+                from: prize_vault.to_account_info(),
+                to: recipient.clone(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            system_program::transfer(cpi_context, amount)?;
+        }
+        Some(prize_mint) => {
+            let token_prize = token_prize.ok_or(ErrorCode::MissingPrizeAccounts)?;
+            require_keys_eq!(
+                token_prize.mint.key(),
+                prize_mint,
+                ErrorCode::PrizeMintMismatch
+            );
+
+            let lottery_id = lottery.lottery_id.to_le_bytes();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                Lottery::SEED_PREFIX,
+                lottery.authority.as_ref(),
+                &lottery_id,
+                &[lottery.bump],
+            ]];
+            let cpi_accounts = TransferChecked {
+                mint: token_prize.mint.to_account_info(),
+                from: token_prize.vault.to_account_info(),
+                to: token_prize.recipient.to_account_info(),
+                authority: lottery.to_account_info(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                token_prize.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            token_interface::transfer_checked(cpi_context, amount, token_prize.mint.decimals)?;
+        }
+    }
+
+    msg!("Prize of {} paid to {}", amount, recipient.key());
+
+    Ok(())
+}
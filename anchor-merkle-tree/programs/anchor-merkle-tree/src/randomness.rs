@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use switchboard_on_demand::accounts::RandomnessAccountData;
+
+use crate::errors::ErrorCode;
+
+/// Commit-reveal state read from a randomness account
+pub struct RandomnessReading {
+    /// Slot whose slothash seeds the randomness (set at commit)
+    pub seed_slot: u64,
+    /// Slot the randomness was revealed at (0 until revealed)
+    pub reveal_slot: u64,
+    /// Revealed value, if it can be read at the current slot
+    pub value: Option<[u8; 32]>,
+}
+
+/// A provider of commit-reveal randomness for the lottery
+pub trait RandomnessSource {
+    fn read(account: &AccountInfo, clock: &Clock) -> Result<RandomnessReading>;
+}
+
+/// Switchboard On-Demand program on the cluster this build targets
+#[cfg(not(feature = "devnet"))]
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = switchboard_on_demand::ON_DEMAND_MAINNET_PID;
+#[cfg(feature = "devnet")]
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = switchboard_on_demand::ON_DEMAND_DEVNET_PID;
+
+/// Switchboard On-Demand randomness accounts
+pub struct Switchboard;
+
+impl Switchboard {
+    /// Parse a randomness account, rejecting accounts Switchboard doesn't own
+    ///
+    /// `RandomnessAccountData::parse` only checks the discriminator, so
+    /// without the owner check anyone could hand in a look-alike account.
+    pub fn parse<'a>(
+        account: &'a AccountInfo,
+    ) -> Result<std::cell::Ref<'a, RandomnessAccountData>> {
+        require_keys_eq!(
+            *account.owner,
+            SWITCHBOARD_PROGRAM_ID,
+            ErrorCode::InvalidRandomnessOwner
+        );
+
+        RandomnessAccountData::parse(account.data.borrow()).map_err(|_| {
+            msg!("Failed to parse randomness account data");
+            error!(ErrorCode::InvalidRandomnessData)
+        })
+    }
+}
+
+impl RandomnessSource for Switchboard {
+    fn read(account: &AccountInfo, clock: &Clock) -> Result<RandomnessReading> {
+        let randomness_data = Self::parse(account)?;
+
+        Ok(RandomnessReading {
+            seed_slot: randomness_data.seed_slot,
+            reveal_slot: randomness_data.reveal_slot,
+            value: randomness_data.get_value(clock.slot).ok(),
+        })
+    }
+}
+
+/// `MockRandomness` accounts owned by this program, for local tests only
+#[cfg(feature = "mock-randomness")]
+pub struct Mock;
+
+#[cfg(feature = "mock-randomness")]
+impl RandomnessSource for Mock {
+    fn read(account: &AccountInfo, clock: &Clock) -> Result<RandomnessReading> {
+        let mock = crate::state::MockRandomness::try_deserialize(&mut &account.data.borrow()[..])
+            .map_err(|_| ErrorCode::InvalidRandomnessData)?;
+
+        let revealed = mock.reveal_slot != 0 && mock.reveal_slot <= clock.slot;
+        Ok(RandomnessReading {
+            seed_slot: mock.seed_slot,
+            reveal_slot: mock.reveal_slot,
+            value: revealed.then_some(mock.value),
+        })
+    }
+}
+
+/// Read a randomness account with the provider that owns it
+pub fn read_randomness(account: &AccountInfo, clock: &Clock) -> Result<RandomnessReading> {
+    #[cfg(feature = "mock-randomness")]
+    if account.owner == &crate::ID {
+        return Mock::read(account, clock);
+    }
+
+    Switchboard::read(account, clock)
+}
@@ -6,24 +6,126 @@ use anchor_lang::prelude::*;
 pub struct Lottery {
     /// Authority that can update the lottery
     pub authority: Pubkey,
+    /// Identifier chosen by the authority, part of the PDA seeds
+    pub lottery_id: u64,
     /// Merkle root of the weighted holder tree
     pub merkle_root: [u8; 32],
     /// VRF result from Switchboard (32 bytes of randomness)
     pub vrf_result: [u8; 32],
+    /// Switchboard randomness account committed to by `commit_randomness`
+    pub randomness_account: Pubkey,
+    /// Seed slot of the committed randomness (0 until committed)
+    pub seed_slot: u64,
     /// Total weight (sum of all balances) for modulo operation
     pub total_weight: u128,
-    /// Winner address (set after claim_winner succeeds)
-    pub winner: Option<Pubkey>,
-    /// Winner's balance/weight
-    pub winner_weight: u64,
-    /// Whether the lottery has been finalized (winner claimed)
+    /// Number of winners to draw
+    pub num_winners: u8,
+    /// Winners in draw order (appended as each claim_winner succeeds)
+    #[max_len(10)]
+    pub winners: Vec<Winner>,
+    /// Next draw index; draws landing on an existing winner are skipped
+    pub draws: u32,
+    /// Whether the lottery has been finalized (all winners claimed or prize reclaimed)
     pub finalized: bool,
+    /// Prize mint, or `None` for a SOL prize
+    pub prize_mint: Option<Pubkey>,
+    /// Prize escrowed in the prize vault at initialization, split across winners
+    pub prize_amount: u64,
+    /// Portion of the prize already paid out to winners
+    pub prize_paid: u64,
+    /// Seconds each draw stays claimable before it can be skipped
+    pub claim_window: i64,
+    /// Unix timestamp at which the VRF result was set (0 until then)
+    pub vrf_set_at: i64,
+    /// Unix timestamp at which the current draw became claimable
+    pub draw_opened_at: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Bump seed for the SOL prize vault PDA
+    pub prize_vault_bump: u8,
 }
 
 impl Lottery {
     pub const SEED_PREFIX: &'static [u8] = b"lottery";
+    /// Must match the `max_len` of `winners`
+    pub const MAX_WINNERS: usize = 10;
+    /// Upper bound on skipped draws per claim, to bound compute
+    pub const MAX_REDRAWS: u32 = 32;
+    /// System-owned PDA holding a SOL prize
+    pub const PRIZE_VAULT_SEED: &'static [u8] = b"prize_vault";
+    /// Token account PDA holding an SPL prize, owned by the lottery PDA
+    pub const PRIZE_TOKEN_VAULT_SEED: &'static [u8] = b"prize_token_vault";
+    /// Slots after the commitment within which the VRF result must be set
+    /// (about an hour); after that the authority may reclaim the prize, as
+    /// long as the randomness was never revealed
+    pub const REVEAL_TIMEOUT_SLOTS: u64 = 9_000;
+
+    /// Whether a leaf with this range has already won
+    pub fn has_won(&self, prev_cumulative: u128) -> bool {
+        self.winners
+            .iter()
+            .any(|winner| winner.prev_cumulative == prev_cumulative)
+    }
+
+    /// Prize share of the next winner; the last winner also takes the remainder
+    pub fn next_prize_share(&self) -> u64 {
+        if self.winners.len() + 1 >= self.num_winners as usize {
+            self.prize_amount - self.prize_paid
+        } else {
+            self.prize_amount / self.num_winners as u64
+        }
+    }
+
+    /// End of the current draw's claim window; from here on anyone may skip
+    /// the draw, or the authority may reclaim the unpaid prize
+    pub fn claim_deadline(&self) -> i64 {
+        self.draw_opened_at.saturating_add(self.claim_window)
+    }
+
+    /// Slot from which an unrevealed commitment counts as abandoned
+    pub fn reveal_deadline(&self) -> u64 {
+        self.seed_slot.saturating_add(Self::REVEAL_TIMEOUT_SLOTS)
+    }
+}
+
+/// Randomness account with directly writable commit-reveal state
+///
+/// Stands in for a Switchboard randomness account in local tests; only
+/// accepted when the program is built with the `mock-randomness` feature.
+#[cfg(feature = "mock-randomness")]
+#[account]
+#[derive(InitSpace)]
+pub struct MockRandomness {
+    /// Commit slot, as Switchboard's `seed_slot`
+    pub seed_slot: u64,
+    /// Reveal slot, 0 until revealed
+    pub reveal_slot: u64,
+    /// Value returned once revealed
+    pub value: [u8; 32],
+}
+
+/// Prize and draw settings for a new lottery
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PrizeConfig {
+    /// Number of winners to draw
+    pub num_winners: u8,
+    /// Prize escrowed at initialization (lamports or token base units)
+    pub prize_amount: u64,
+    /// Seconds each draw stays claimable before it can be skipped
+    pub claim_window: i64,
+}
+
+/// A drawn winner and the weight range of its leaf
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct Winner {
+    /// Winner address
+    pub address: Pubkey,
+    /// Winner's balance/weight
+    pub weight: u64,
+    /// Start of the winning leaf's range (unique per leaf)
+    pub prev_cumulative: u128,
+    /// End of the winning leaf's range
+    pub cumulative: u128,
 }
 
 /// Proof data for claiming winner status
@@ -40,3 +142,67 @@ pub struct WinnerProof {
     /// Merkle proof hashes (path from leaf to root)
     pub proof: Vec<[u8; 32]>,
 }
+
+/// Merkle airdrop distributor for one mint
+///
+/// Each round has its own root over `(index, address, amount)` leaves and
+/// its own claim bitmap; rotating the root starts a new round.
+#[account]
+#[derive(InitSpace)]
+pub struct Distributor {
+    /// Authority that funds the vault, rotates roots and claws back
+    pub authority: Pubkey,
+    /// Mint being distributed
+    pub mint: Pubkey,
+    /// Associated token account of this PDA holding the airdrop funds
+    pub vault: Pubkey,
+    /// Merkle root of the current round's claim tree
+    pub merkle_root: [u8; 32],
+    /// Current round, part of the claim bitmap seeds
+    pub round: u32,
+    /// Number of leaves in the current round's tree
+    pub num_leaves: u32,
+    /// Claims made in the current round
+    pub num_claimed: u32,
+    /// Tokens claimed in the current round
+    pub total_claimed: u64,
+    /// Unix timestamp after which claims stop and the authority may claw back
+    pub clawback_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Distributor {
+    pub const SEED_PREFIX: &'static [u8] = b"distributor";
+}
+
+/// One bit per leaf of a distributor round; a set bit means claimed
+#[account]
+pub struct ClaimBitmap {
+    /// Distributor this bitmap belongs to
+    pub distributor: Pubkey,
+    /// Round this bitmap tracks
+    pub round: u32,
+    /// Claim bits, little-endian within each byte
+    pub bits: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub const SEED_PREFIX: &'static [u8] = b"claim_bitmap";
+
+    /// Largest tree a bitmap can cover while staying under the 10 KiB limit
+    /// for accounts created through CPI.
+    pub const MAX_LEAVES: u32 = 80_000;
+
+    pub fn space(num_leaves: u32) -> usize {
+        8 + 32 + 4 + 4 + (num_leaves as usize).div_ceil(8)
+    }
+
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.bits[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.bits[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
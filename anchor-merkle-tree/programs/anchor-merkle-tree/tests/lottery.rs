@@ -0,0 +1,668 @@
+//! In-process lottery tests against the `mock-randomness` build:
+//!
+//!     anchor build -- --features mock-randomness
+//!     cargo test --features mock-randomness
+#![cfg(feature = "mock-randomness")]
+
+use std::collections::HashMap;
+
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_merkle_tree::errors::ErrorCode;
+use anchor_merkle_tree::merkle::{compute_draw_point, compute_leaf_hash, hash_pair};
+use anchor_merkle_tree::state::{Lottery, MockRandomness, PrizeConfig, WinnerProof};
+use mollusk_svm::{program, result::Check, result::InstructionResult, Mollusk};
+use solana_account::Account;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_program_error::ProgramError;
+use solana_pubkey::Pubkey;
+
+type AnchorPubkey = anchor_lang::prelude::Pubkey;
+
+const PRIZE: u64 = 1_000_000_000;
+const START_SLOT: u64 = 1_000;
+const VRF: [u8; 32] = [0x5A; 32];
+
+fn err(code: ErrorCode) -> ProgramError {
+    ProgramError::Custom(code.into())
+}
+
+fn to_anchor(key: &Pubkey) -> AnchorPubkey {
+    AnchorPubkey::new_from_array(key.to_bytes())
+}
+
+fn from_anchor(key: &AnchorPubkey) -> Pubkey {
+    Pubkey::new_from_array(key.to_bytes())
+}
+
+fn instruction(data: impl InstructionData, accounts: impl ToAccountMetas) -> Instruction {
+    Instruction {
+        program_id: from_anchor(&anchor_merkle_tree::ID),
+        accounts: accounts
+            .to_account_metas(None)
+            .into_iter()
+            .map(|meta| AccountMeta {
+                pubkey: from_anchor(&meta.pubkey),
+                is_signer: meta.is_signer,
+                is_writable: meta.is_writable,
+            })
+            .collect(),
+        data: data.data(),
+    }
+}
+
+/// Weighted holder tree built the same way as `scripts/merkle.ts`
+struct HolderTree {
+    holders: Vec<(Pubkey, u64, u128, u128)>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl HolderTree {
+    fn new(balances: &[u64]) -> Self {
+        let mut keys: Vec<Pubkey> = balances.iter().map(|_| Pubkey::new_unique()).collect();
+        keys.sort_by_key(|key| key.to_bytes());
+
+        let mut cumulative = 0u128;
+        let holders: Vec<_> = keys
+            .into_iter()
+            .zip(balances)
+            .map(|(key, &balance)| {
+                let prev = cumulative;
+                cumulative += balance as u128;
+                (key, balance, prev, cumulative)
+            })
+            .collect();
+
+        let mut levels = vec![holders
+            .iter()
+            .map(|(key, balance, prev, cum)| {
+                compute_leaf_hash(&to_anchor(key), *balance, *prev, *cum)
+            })
+            .collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [odd] => *odd,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self { holders, levels }
+    }
+
+    fn root(&self) -> [u8; 32] {
+        self.levels.last().unwrap()[0]
+    }
+
+    fn total_weight(&self) -> u128 {
+        self.holders.last().unwrap().3
+    }
+
+    fn owner_of(&self, point: u128) -> usize {
+        self.holders
+            .iter()
+            .position(|(_, _, prev, cum)| point >= *prev && point < *cum)
+            .unwrap()
+    }
+
+    fn proof(&self, index: usize) -> WinnerProof {
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(*sibling);
+            }
+            position /= 2;
+        }
+
+        let (key, balance, prev, cum) = self.holders[index];
+        WinnerProof {
+            address: to_anchor(&key),
+            balance,
+            prev_cumulative: prev,
+            cumulative: cum,
+            proof: siblings,
+        }
+    }
+}
+
+/// Accounts carried between instructions; state only advances on success
+struct Bank {
+    mollusk: Mollusk,
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl Bank {
+    fn new() -> Self {
+        let program_id = from_anchor(&anchor_merkle_tree::ID);
+        let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/anchor_merkle_tree");
+        mollusk.warp_to_slot(START_SLOT);
+        mollusk.sysvars.clock.unix_timestamp = 1_700_000_000;
+
+        let (sp, sa) = program::keyed_account_for_system_program();
+        let mut accounts = HashMap::new();
+        accounts.insert(sp, sa);
+        accounts.insert(
+            program_id,
+            program::create_program_account_loader_v3(&program_id),
+        );
+
+        Self { mollusk, accounts }
+    }
+
+    fn fund(&mut self, key: &Pubkey) {
+        let sp = program::keyed_account_for_system_program().0;
+        self.accounts
+            .insert(*key, Account::new(10_000_000_000, 0, &sp));
+    }
+
+    fn lamports(&self, key: &Pubkey) -> u64 {
+        self.accounts.get(key).map_or(0, |account| account.lamports)
+    }
+
+    fn lottery(&self, key: &Pubkey) -> Lottery {
+        Lottery::try_deserialize(&mut self.accounts[key].data.as_slice()).unwrap()
+    }
+
+    fn set_mock(&mut self, key: &Pubkey, seed_slot: u64, reveal_slot: u64, value: [u8; 32]) {
+        let mut data = Vec::new();
+        MockRandomness {
+            seed_slot,
+            reveal_slot,
+            value,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        let mut account = Account::new(
+            1_000_000_000,
+            data.len(),
+            &from_anchor(&anchor_merkle_tree::ID),
+        );
+        account.data = data;
+        self.accounts.insert(*key, account);
+    }
+
+    fn run(&mut self, ix: &Instruction, checks: &[Check]) -> InstructionResult {
+        let mut keys: Vec<Pubkey> = Vec::new();
+        for meta in &ix.accounts {
+            if !keys.contains(&meta.pubkey) {
+                keys.push(meta.pubkey);
+            }
+        }
+        let accounts: Vec<(Pubkey, Account)> = keys
+            .iter()
+            .map(|key| (*key, self.accounts.get(key).cloned().unwrap_or_default()))
+            .collect();
+
+        let result = self
+            .mollusk
+            .process_and_validate_instruction(ix, &accounts, checks);
+        if result.raw_result.is_ok() {
+            self.accounts
+                .extend(result.resulting_accounts.iter().cloned());
+        }
+        result
+    }
+}
+
+struct LotteryKeys {
+    authority: Pubkey,
+    lottery: Pubkey,
+    prize_vault: Pubkey,
+    randomness: Pubkey,
+}
+
+fn lottery_keys(authority: Pubkey, lottery_id: u64) -> LotteryKeys {
+    let program_id = from_anchor(&anchor_merkle_tree::ID);
+    let (lottery, _) = Pubkey::find_program_address(
+        &[b"lottery", authority.as_ref(), &lottery_id.to_le_bytes()],
+        &program_id,
+    );
+    let (prize_vault, _) =
+        Pubkey::find_program_address(&[b"prize_vault", lottery.as_ref()], &program_id);
+    LotteryKeys {
+        authority,
+        lottery,
+        prize_vault,
+        randomness: Pubkey::new_unique(),
+    }
+}
+
+fn ix_initialize_lottery(
+    keys: &LotteryKeys,
+    lottery_id: u64,
+    tree: &HolderTree,
+    num_winners: u8,
+) -> Instruction {
+    ix_initialize_lottery_with_prize(keys, lottery_id, tree, num_winners, PRIZE)
+}
+
+fn ix_initialize_lottery_with_prize(
+    keys: &LotteryKeys,
+    lottery_id: u64,
+    tree: &HolderTree,
+    num_winners: u8,
+    prize_amount: u64,
+) -> Instruction {
+    instruction(
+        anchor_merkle_tree::instruction::InitializeLottery {
+            lottery_id,
+            merkle_root: tree.root(),
+            total_weight: tree.total_weight(),
+            prize: PrizeConfig {
+                num_winners,
+                prize_amount,
+                claim_window: 3_600,
+            },
+        },
+        anchor_merkle_tree::accounts::InitializeLottery {
+            lottery: to_anchor(&keys.lottery),
+            prize_vault: to_anchor(&keys.prize_vault),
+            randomness_account: Some(to_anchor(&keys.randomness)),
+            prize_mint: None,
+            prize_token_vault: None,
+            authority_token_account: None,
+            authority: to_anchor(&keys.authority),
+            token_program: None,
+            system_program: anchor_lang::system_program::ID,
+        },
+    )
+}
+
+fn ix_commit_randomness(keys: &LotteryKeys, randomness: &Pubkey) -> Instruction {
+    instruction(
+        anchor_merkle_tree::instruction::CommitRandomness {},
+        anchor_merkle_tree::accounts::CommitRandomness {
+            lottery: to_anchor(&keys.lottery),
+            randomness_account: to_anchor(randomness),
+            authority: to_anchor(&keys.authority),
+        },
+    )
+}
+
+fn ix_set_vrf_result(keys: &LotteryKeys, randomness: &Pubkey) -> Instruction {
+    instruction(
+        anchor_merkle_tree::instruction::SetVrfResult {},
+        anchor_merkle_tree::accounts::SetVrfResult {
+            lottery: to_anchor(&keys.lottery),
+            randomness_account: to_anchor(randomness),
+        },
+    )
+}
+
+fn ix_claim_winner(keys: &LotteryKeys, proof: WinnerProof) -> Instruction {
+    let claimant = proof.address;
+    instruction(
+        anchor_merkle_tree::instruction::ClaimWinner { proof },
+        anchor_merkle_tree::accounts::ClaimWinner {
+            lottery: to_anchor(&keys.lottery),
+            prize_vault: to_anchor(&keys.prize_vault),
+            prize_mint: None,
+            prize_token_vault: None,
+            claimant_token_account: None,
+            claimant,
+            token_program: None,
+            system_program: anchor_lang::system_program::ID,
+        },
+    )
+}
+
+fn ix_skip_draw(keys: &LotteryKeys) -> Instruction {
+    instruction(
+        anchor_merkle_tree::instruction::SkipDraw {},
+        anchor_merkle_tree::accounts::SkipDraw {
+            lottery: to_anchor(&keys.lottery),
+        },
+    )
+}
+
+fn ix_reclaim_prize(keys: &LotteryKeys) -> Instruction {
+    instruction(
+        anchor_merkle_tree::instruction::ReclaimPrize {},
+        anchor_merkle_tree::accounts::ReclaimPrize {
+            lottery: to_anchor(&keys.lottery),
+            prize_vault: to_anchor(&keys.prize_vault),
+            randomness_account: Some(to_anchor(&keys.randomness)),
+            prize_mint: None,
+            prize_token_vault: None,
+            authority_token_account: None,
+            authority: to_anchor(&keys.authority),
+            token_program: None,
+            system_program: anchor_lang::system_program::ID,
+        },
+    )
+}
+
+/// Initialize a lottery over `tree`, commit the mock randomness in the slot
+/// after its seed slot, then reveal `VRF` one slot later and set it.
+fn setup_lottery(bank: &mut Bank, tree: &HolderTree, num_winners: u8) -> LotteryKeys {
+    let keys = lottery_keys(Pubkey::new_unique(), 7);
+    bank.fund(&keys.authority);
+    bank.run(
+        &ix_initialize_lottery(&keys, 7, tree, num_winners),
+        &[Check::success()],
+    );
+    assert_eq!(
+        bank.lamports(&keys.prize_vault),
+        PRIZE,
+        "prize not escrowed"
+    );
+
+    bank.set_mock(&keys.randomness, START_SLOT - 1, 0, [0; 32]);
+    bank.run(
+        &ix_commit_randomness(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+
+    bank.mollusk.warp_to_slot(START_SLOT + 1);
+    bank.set_mock(&keys.randomness, START_SLOT - 1, START_SLOT + 1, VRF);
+    bank.run(
+        &ix_set_vrf_result(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+    assert_eq!(
+        bank.lottery(&keys.lottery).vrf_result,
+        VRF,
+        "vrf not stored"
+    );
+
+    keys
+}
+
+#[test]
+fn test_only_owning_leaf_can_claim() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[5_000, 2_000, 1_500, 1_000, 500]);
+    let keys = setup_lottery(&mut bank, &tree, 1);
+
+    let point = compute_draw_point(&VRF, 0, tree.total_weight());
+    let winner = tree.owner_of(point);
+
+    for index in (0..tree.holders.len()).filter(|&i| i != winner) {
+        let claimant = tree.holders[index].0;
+        bank.fund(&claimant);
+        bank.run(
+            &ix_claim_winner(&keys, tree.proof(index)),
+            &[Check::err(err(ErrorCode::NotTheWinner))],
+        );
+    }
+
+    // A valid proof for someone else's leaf is rejected too
+    let mut stolen = tree.proof(winner);
+    let thief = tree.holders[(winner + 1) % tree.holders.len()].0;
+    stolen.address = to_anchor(&thief);
+    bank.run(
+        &ix_claim_winner(&keys, stolen),
+        &[Check::err(err(ErrorCode::InvalidMerkleProof))],
+    );
+
+    let claimant = tree.holders[winner].0;
+    bank.fund(&claimant);
+    let before = bank.lamports(&claimant);
+    let result = bank.run(
+        &ix_claim_winner(&keys, tree.proof(winner)),
+        &[Check::success()],
+    );
+    println!("claim_winner CU: {}", result.compute_units_consumed);
+
+    assert_eq!(bank.lamports(&claimant), before + PRIZE, "prize not paid");
+    assert_eq!(bank.lamports(&keys.prize_vault), 0, "vault not drained");
+
+    let lottery = bank.lottery(&keys.lottery);
+    assert!(lottery.finalized, "lottery not finalized");
+    assert_eq!(lottery.winners.len(), 1);
+    assert_eq!(lottery.winners[0].address, to_anchor(&claimant));
+
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(winner)),
+        &[Check::err(err(ErrorCode::LotteryAlreadyFinalized))],
+    );
+}
+
+#[test]
+fn test_multi_winner_draws_without_replacement() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[9_000, 400, 300, 200, 100]);
+    let keys = setup_lottery(&mut bank, &tree, 2);
+
+    // Replay the on-chain draw: skip points that land on an earlier winner
+    let mut winners = Vec::new();
+    let mut draw = 0;
+    while winners.len() < 2 {
+        let owner = tree.owner_of(compute_draw_point(&VRF, draw, tree.total_weight()));
+        if !winners.contains(&owner) {
+            winners.push(owner);
+        }
+        draw += 1;
+    }
+
+    for &index in &winners {
+        bank.fund(&tree.holders[index].0);
+    }
+
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(winners[0])),
+        &[Check::success()],
+    );
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(winners[0])),
+        &[Check::err(err(ErrorCode::LeafAlreadyWon))],
+    );
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(winners[1])),
+        &[Check::success()],
+    );
+
+    let lottery = bank.lottery(&keys.lottery);
+    assert!(lottery.finalized, "lottery not finalized");
+    assert_eq!(lottery.prize_paid, PRIZE, "prize not fully split");
+    assert_eq!(bank.lamports(&keys.prize_vault), 0, "vault not drained");
+}
+
+#[test]
+fn test_unclaimed_draw_can_be_skipped() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[5_000, 2_000, 1_500, 1_000, 500]);
+    let keys = setup_lottery(&mut bank, &tree, 2);
+
+    let skipped = tree.owner_of(compute_draw_point(&VRF, 0, tree.total_weight()));
+    let next = tree.owner_of(compute_draw_point(&VRF, 1, tree.total_weight()));
+    bank.fund(&tree.holders[skipped].0);
+    bank.fund(&tree.holders[next].0);
+
+    // The first winner still has their whole window
+    bank.run(
+        &ix_skip_draw(&keys),
+        &[Check::err(err(ErrorCode::DrawStillClaimable))],
+    );
+
+    bank.mollusk.sysvars.clock.unix_timestamp += 3_600;
+    bank.run(&ix_skip_draw(&keys), &[Check::success()]);
+
+    // The skipped draw is gone; the next draw opens with a fresh window
+    if skipped != next {
+        bank.run(
+            &ix_claim_winner(&keys, tree.proof(skipped)),
+            &[Check::err(err(ErrorCode::NotTheWinner))],
+        );
+    }
+    bank.mollusk.sysvars.clock.unix_timestamp += 3_599;
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(next)),
+        &[Check::success()],
+    );
+
+    let lottery = bank.lottery(&keys.lottery);
+    assert_eq!(lottery.winners.len(), 1);
+    assert_eq!(lottery.winners[0].address, to_anchor(&tree.holders[next].0));
+    assert_eq!(lottery.draws, 2, "skipped draw not counted");
+    assert!(!lottery.finalized, "second winner slot still open");
+}
+
+#[test]
+fn test_randomness_must_be_fresh_and_committed() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[100, 200, 300]);
+    let keys = lottery_keys(Pubkey::new_unique(), 1);
+    bank.fund(&keys.authority);
+    bank.run(
+        &ix_initialize_lottery(&keys, 1, &tree, 1),
+        &[Check::success()],
+    );
+
+    // Setting a result before any commitment
+    bank.set_mock(&keys.randomness, START_SLOT - 1, START_SLOT, VRF);
+    bank.run(
+        &ix_set_vrf_result(&keys, &keys.randomness),
+        &[Check::err(err(ErrorCode::RandomnessNotCommitted))],
+    );
+
+    // Committing randomness seeded in an older slot
+    bank.set_mock(&keys.randomness, START_SLOT - 5, 0, [0; 32]);
+    bank.run(
+        &ix_commit_randomness(&keys, &keys.randomness),
+        &[Check::err(err(ErrorCode::RandomnessNotFresh))],
+    );
+
+    bank.set_mock(&keys.randomness, START_SLOT - 1, 0, [0; 32]);
+    bank.run(
+        &ix_commit_randomness(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+
+    // Another, already revealed account cannot be swapped in
+    let other = Pubkey::new_unique();
+    bank.set_mock(&other, START_SLOT - 1, START_SLOT, [0xEE; 32]);
+    bank.run(
+        &ix_set_vrf_result(&keys, &other),
+        &[Check::err(err(ErrorCode::RandomnessAccountMismatch))],
+    );
+
+    // The committed account re-seeded after the commitment is rejected
+    bank.mollusk.warp_to_slot(START_SLOT + 2);
+    bank.set_mock(&keys.randomness, START_SLOT + 1, START_SLOT + 2, [0xEE; 32]);
+    bank.run(
+        &ix_set_vrf_result(&keys, &keys.randomness),
+        &[Check::err(err(ErrorCode::RandomnessSeedMismatch))],
+    );
+
+    // Claims need the VRF result
+    let claimant = tree.holders[0].0;
+    bank.fund(&claimant);
+    bank.run(
+        &ix_claim_winner(&keys, tree.proof(0)),
+        &[Check::err(err(ErrorCode::VrfNotSet))],
+    );
+}
+
+#[test]
+fn test_unrevealed_commitment_can_be_reclaimed_after_timeout() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[100, 200, 300]);
+    let keys = lottery_keys(Pubkey::new_unique(), 3);
+    bank.fund(&keys.authority);
+    bank.run(
+        &ix_initialize_lottery(&keys, 3, &tree, 1),
+        &[Check::success()],
+    );
+
+    // Nothing has been committed yet, so there is nothing to time out
+    bank.run(
+        &ix_reclaim_prize(&keys),
+        &[Check::err(err(ErrorCode::RandomnessNotCommitted))],
+    );
+
+    bank.set_mock(&keys.randomness, START_SLOT - 1, 0, [0; 32]);
+    bank.run(
+        &ix_commit_randomness(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+
+    // The randomness is never revealed
+    let deadline = START_SLOT - 1 + Lottery::REVEAL_TIMEOUT_SLOTS;
+    bank.mollusk.warp_to_slot(deadline - 1);
+    bank.run(
+        &ix_reclaim_prize(&keys),
+        &[Check::err(err(ErrorCode::RevealWindowOpen))],
+    );
+
+    bank.mollusk.warp_to_slot(deadline);
+    let before = bank.lamports(&keys.authority);
+    bank.run(&ix_reclaim_prize(&keys), &[Check::success()]);
+
+    assert_eq!(
+        bank.lamports(&keys.authority),
+        before + PRIZE,
+        "prize not returned"
+    );
+    assert_eq!(bank.lamports(&keys.prize_vault), 0, "vault not drained");
+    assert!(
+        bank.lottery(&keys.lottery).finalized,
+        "lottery not finalized"
+    );
+}
+
+#[test]
+fn test_withheld_reveal_cannot_be_reclaimed() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[100, 200, 300]);
+    let keys = lottery_keys(Pubkey::new_unique(), 4);
+    bank.fund(&keys.authority);
+    bank.run(
+        &ix_initialize_lottery(&keys, 4, &tree, 1),
+        &[Check::success()],
+    );
+    bank.set_mock(&keys.randomness, START_SLOT - 1, 0, [0; 32]);
+    bank.run(
+        &ix_commit_randomness(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+
+    // The authority sees the reveal but never submits it
+    bank.set_mock(&keys.randomness, START_SLOT - 1, START_SLOT + 1, VRF);
+    let deadline = START_SLOT - 1 + Lottery::REVEAL_TIMEOUT_SLOTS;
+    bank.mollusk.warp_to_slot(deadline);
+    bank.run(
+        &ix_reclaim_prize(&keys),
+        &[Check::err(err(ErrorCode::RandomnessAlreadyRevealed))],
+    );
+
+    // Re-committing the account to hide the reveal doesn't help either
+    bank.set_mock(&keys.randomness, deadline - 1, 0, [0; 32]);
+    bank.run(
+        &ix_reclaim_prize(&keys),
+        &[Check::err(err(ErrorCode::RandomnessAlreadyRevealed))],
+    );
+
+    // Anyone can still submit the revealed result
+    bank.set_mock(&keys.randomness, START_SLOT - 1, START_SLOT + 1, VRF);
+    bank.run(
+        &ix_set_vrf_result(&keys, &keys.randomness),
+        &[Check::success()],
+    );
+    assert_eq!(bank.lottery(&keys.lottery).vrf_result, VRF);
+}
+
+#[test]
+fn test_sol_prize_shares_keep_vault_rent_exempt() {
+    let mut bank = Bank::new();
+    let tree = HolderTree::new(&[100, 200, 300]);
+    let rent = bank.mollusk.sysvars.rent.minimum_balance(0);
+    let keys = lottery_keys(Pubkey::new_unique(), 4);
+    bank.fund(&keys.authority);
+
+    // Paying the first of two winners would leave the vault below rent exemption
+    bank.run(
+        &ix_initialize_lottery_with_prize(&keys, 4, &tree, 2, 2 * rent - 2),
+        &[Check::err(err(ErrorCode::PrizeBelowRentExemption))],
+    );
+    bank.run(
+        &ix_initialize_lottery_with_prize(&keys, 4, &tree, 2, 2 * rent - 1),
+        &[Check::success()],
+    );
+}
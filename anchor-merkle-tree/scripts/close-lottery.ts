@@ -1,6 +1,6 @@
 #!/usr/bin/env ts-node
 
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { AnchorUtils } from "@switchboard-xyz/on-demand";
 import * as fs from "fs";
@@ -9,6 +9,9 @@ import type { AnchorMerkleTree } from "../target/types/anchor_merkle_tree";
 
 const PROGRAM_ID = new PublicKey("HSBQg9YYMu8DtD1pgTfwxTqTdGWTKHtxSjg5wT3bz1mi");
 
+// Lotteries are keyed by (authority, id); override with LOTTERY_ID
+const LOTTERY_ID = new BN(process.env.LOTTERY_ID ?? "0");
+
 async function main() {
   const { provider, wallet } = await AnchorUtils.loadEnv();
 
@@ -18,7 +21,11 @@ async function main() {
   const program = new Program(IDL, provider) as Program<AnchorMerkleTree>;
 
   const [lotteryPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("lottery"), wallet.payer.publicKey.toBuffer()],
+    [
+      Buffer.from("lottery"),
+      wallet.payer.publicKey.toBuffer(),
+      LOTTERY_ID.toArrayLike(Buffer, "le", 8),
+    ],
     PROGRAM_ID
   );
 
@@ -30,14 +37,19 @@ async function main() {
     const lottery = await program.account.lottery.fetch(lotteryPda);
     console.log("\nLottery found:");
     console.log("  Finalized:", lottery.finalized);
-    console.log("  Winner:", lottery.winner?.toBase58() || "None");
+    console.log(
+      "  Winners:",
+      lottery.winners.map((w) => w.address.toBase58()).join(", ") || "None"
+    );
 
     // Close it
     const tx = await program.methods
       .closeLottery()
       .accountsStrict({
         lottery: lotteryPda,
+        prizeTokenVault: null,
         authority: wallet.payer.publicKey,
+        tokenProgram: null,
       })
       .signers([wallet.payer])
       .rpc();
@@ -2,6 +2,12 @@ import { PublicKey } from "@solana/web3.js";
 import { keccak_256 } from "@noble/hashes/sha3";
 import BN from "bn.js";
 
+/**
+ * Most draws a claim may skip for landing on past winners; must match
+ * `Lottery::MAX_REDRAWS`
+ */
+export const MAX_REDRAWS = 32;
+
 /**
  * Holder entry with cumulative weight information
  */
@@ -266,15 +272,10 @@ export class MerkleTree {
   }
 
   /**
-   * Find the winner entry given a VRF result
-   * Uses binary search for efficiency
+   * Find the entry whose range [prevCumulative, cumulative) contains the
+   * selection point. Uses binary search for efficiency
    */
-  findWinner(vrfResult: Buffer | Uint8Array): { entry: HolderEntry; index: number } {
-    // Compute selection point: (vrf as u128) % total_weight
-    const vrfValue = leBytesToU128(vrfResult);
-    const selectionPoint = vrfValue % this.totalWeight;
-
-    // Binary search for the entry containing the selection point
+  findEntryAt(selectionPoint: bigint): { entry: HolderEntry; index: number } {
     let left = 0;
     let right = this.entries.length - 1;
 
@@ -302,19 +303,60 @@ export class MerkleTree {
   }
 
   /**
-   * Generate winner proof for on-chain submission
+   * Find the winner entry given a VRF result
+   * Uses (vrf as u128) % total_weight as the selection point
    */
-  generateWinnerProof(vrfResult: Buffer | Uint8Array): WinnerProof {
-    const { entry, index } = this.findWinner(vrfResult);
-    const proof = this.getProof(index);
+  findWinner(vrfResult: Buffer | Uint8Array): { entry: HolderEntry; index: number } {
+    return this.findEntryAt(computeSelectionPoint(vrfResult, this.totalWeight));
+  }
 
-    return {
+  /**
+   * Draw `numWinners` winners without replacement, in on-chain claim order.
+   * Draws landing on a leaf that already won are skipped, as on-chain, and
+   * a winner needing more than `MAX_REDRAWS` skips can't be claimed.
+   * Assumes every winner claims; a draw skipped with `skip_draw` shifts
+   * the later winners to the following draw indices.
+   */
+  drawWinners(
+    vrfResult: Buffer | Uint8Array,
+    numWinners: number
+  ): { entry: HolderEntry; index: number; draw: number }[] {
+    if (numWinners > this.entries.length) {
+      throw new Error(`Cannot draw ${numWinners} winners from ${this.entries.length} entries`);
+    }
+
+    const winners: { entry: HolderEntry; index: number; draw: number }[] = [];
+    let draw = 0;
+    let redraws = 0;
+
+    while (winners.length < numWinners) {
+      const point = computeDrawPoint(vrfResult, draw, this.totalWeight);
+      const { entry, index } = this.findEntryAt(point);
+      if (!winners.some((w) => w.index === index)) {
+        winners.push({ entry, index, draw });
+        redraws = 0;
+      } else if (++redraws > MAX_REDRAWS) {
+        throw new Error(
+          `Winner ${winners.length + 1} needs more than ${MAX_REDRAWS} redraws`
+        );
+      }
+      draw++;
+    }
+
+    return winners;
+  }
+
+  /**
+   * Generate winner proofs for on-chain submission, in claim order
+   */
+  generateWinnerProofs(vrfResult: Buffer | Uint8Array, numWinners: number): WinnerProof[] {
+    return this.drawWinners(vrfResult, numWinners).map(({ entry, index }) => ({
       address: entry.address,
       balance: entry.balance,
       prevCumulative: entry.prevCumulative,
       cumulative: entry.cumulative,
-      proof,
-    };
+      proof: this.getProof(index),
+    }));
   }
 
   /**
@@ -350,6 +392,21 @@ export function proofToAnchorFormat(proof: WinnerProof): {
   };
 }
 
+/**
+ * Compute selection point for the `draw`-th draw of a multi-winner lottery
+ * point = keccak256(vrf || draw as u32 LE), reduced as in computeSelectionPoint
+ */
+export function computeDrawPoint(
+  vrfResult: Buffer | Uint8Array,
+  draw: number,
+  totalWeight: bigint
+): bigint {
+  const drawBytes = Buffer.alloc(4);
+  drawBytes.writeUInt32LE(draw);
+  const hash = keccak256(Buffer.concat([Buffer.from(vrfResult), drawBytes]));
+  return computeSelectionPoint(hash, totalWeight);
+}
+
 /**
  * Compute selection point from VRF result (for debugging/verification)
  */
@@ -360,3 +417,107 @@ export function computeSelectionPoint(
   const vrfValue = leBytesToU128(vrfResult);
   return vrfValue % totalWeight;
 }
+
+/**
+ * Airdrop allocation for the distributor
+ */
+export interface ClaimEntry {
+  index: number;
+  address: PublicKey;
+  amount: bigint;
+}
+
+/**
+ * Compute the leaf hash for an airdrop allocation
+ * leaf = keccak256(index || address || amount)
+ */
+export function computeClaimLeafHash(entry: ClaimEntry): Buffer {
+  const index = Buffer.alloc(4);
+  index.writeUInt32LE(entry.index);
+  return keccak256(
+    Buffer.concat([index, entry.address.toBuffer(), u64ToLeBytes(entry.amount)])
+  );
+}
+
+/**
+ * Build indexed claim entries from raw allocations
+ * Allocations are sorted by address so indices are deterministic
+ */
+export function buildClaimEntries(
+  allocations: Record<string, string | number>
+): ClaimEntry[] {
+  return Object.entries(allocations)
+    .map(([address, amount]) => ({
+      address: new PublicKey(address),
+      amount: BigInt(amount),
+    }))
+    .sort((a, b) => a.address.toBuffer().compare(b.address.toBuffer()))
+    .map((allocation, index) => ({ index, ...allocation }));
+}
+
+/**
+ * Merkle tree over airdrop allocations, using the same pairing rules as the
+ * lottery tree so proofs verify with the same on-chain `verify_proof`
+ */
+export class DistributorTree {
+  private levels: Buffer[][];
+  private entries: ClaimEntry[];
+
+  constructor(entries: ClaimEntry[]) {
+    if (entries.length === 0) {
+      throw new Error("Cannot create Merkle tree with no entries");
+    }
+
+    this.entries = entries;
+    this.levels = [entries.map(computeClaimLeafHash)];
+
+    while (this.levels[this.levels.length - 1].length > 1) {
+      const nodes = this.levels[this.levels.length - 1];
+      const nextLevel: Buffer[] = [];
+      for (let i = 0; i < nodes.length; i += 2) {
+        // Odd node is promoted to the next level unchanged
+        nextLevel.push(
+          i + 1 < nodes.length ? hashPair(nodes[i], nodes[i + 1]) : nodes[i]
+        );
+      }
+      this.levels.push(nextLevel);
+    }
+  }
+
+  getRoot(): Buffer {
+    return this.levels[this.levels.length - 1][0];
+  }
+
+  getRootArray(): number[] {
+    return Array.from(this.getRoot());
+  }
+
+  getEntries(): ClaimEntry[] {
+    return this.entries;
+  }
+
+  getTotalAmount(): bigint {
+    return this.entries.reduce((sum, entry) => sum + entry.amount, 0n);
+  }
+
+  /**
+   * Generate Merkle proof for the allocation at given index
+   */
+  getProof(index: number): Buffer[] {
+    if (index < 0 || index >= this.entries.length) {
+      throw new Error(`Invalid leaf index: ${index}`);
+    }
+
+    const proof: Buffer[] = [];
+    let currentIndex = index;
+    for (const nodes of this.levels.slice(0, -1)) {
+      const siblingIndex = currentIndex ^ 1;
+      if (siblingIndex < nodes.length) {
+        proof.push(nodes[siblingIndex]);
+      }
+      currentIndex = Math.floor(currentIndex / 2);
+    }
+
+    return proof;
+  }
+}
@@ -2,6 +2,10 @@
 
 /**
  * Full devnet test of the Merkle Tree Weighted Lottery
+ *
+ * Deploy a build that accepts devnet Switchboard randomness first:
+ *
+ *     anchor build -- --features devnet
  */
 
 import * as fs from "fs";
@@ -21,7 +25,7 @@ import {
   MerkleTree,
   buildHolderEntries,
   proofToAnchorFormat,
-  computeSelectionPoint,
+  computeDrawPoint,
 } from "./merkle";
 import type { AnchorMerkleTree } from "../target/types/anchor_merkle_tree";
 
@@ -33,9 +37,19 @@ function loadIdl(): any {
   );
 }
 
-function getLotteryPda(authority: PublicKey): [PublicKey, number] {
+// Lotteries are keyed by (authority, id); override with LOTTERY_ID
+const LOTTERY_ID = new BN(process.env.LOTTERY_ID ?? "0");
+
+function getLotteryPda(authority: PublicKey, lotteryId: BN): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("lottery"), authority.toBuffer()],
+    [Buffer.from("lottery"), authority.toBuffer(), lotteryId.toArrayLike(Buffer, "le", 8)],
+    PROGRAM_ID
+  );
+}
+
+function getPrizeVaultPda(lottery: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("prize_vault"), lottery.toBuffer()],
     PROGRAM_ID
   );
 }
@@ -65,7 +79,8 @@ async function main() {
     return;
   }
 
-  const [lotteryPda] = getLotteryPda(payer.publicKey);
+  const [lotteryPda] = getLotteryPda(payer.publicKey, LOTTERY_ID);
+  const [prizeVaultPda] = getPrizeVaultPda(lotteryPda);
   console.log("Lottery PDA:", lotteryPda.toBase58());
 
   // Check if lottery already exists
@@ -77,8 +92,11 @@ async function main() {
     console.log("  VRF set:", !Buffer.from(existingLottery.vrfResult).equals(Buffer.alloc(32, 0)));
 
     if (existingLottery.finalized) {
-      console.log("\n⚠️  Lottery is already finalized. Winner:", existingLottery.winner?.toBase58());
-      console.log("To test again, you need to close this lottery or use a different authority.");
+      console.log(
+        "\n⚠️  Lottery is already finalized. Winners:",
+        existingLottery.winners.map((w: any) => w.address.toBase58())
+      );
+      console.log("To test again, close this lottery or set a different LOTTERY_ID.");
       return;
     }
   } catch (e) {
@@ -116,10 +134,19 @@ async function main() {
   if (!existingLottery) {
     console.log("\n--- Step 3: Initialize Lottery ---");
     const initTx = await program.methods
-      .initializeLottery(merkleRoot as number[], new BN(totalWeight.toString()))
+      .initializeLottery(LOTTERY_ID, merkleRoot as number[], new BN(totalWeight.toString()), {
+        numWinners: 1,
+        prizeAmount: new BN(10_000_000), // 0.01 SOL prize
+        claimWindow: new BN(3600), // 1 hour claim window
+      })
       .accountsStrict({
         lottery: lotteryPda,
+        prizeVault: prizeVaultPda,
+        prizeMint: null,
+        prizeTokenVault: null,
+        authorityTokenAccount: null,
         authority: payer.publicKey,
+        tokenProgram: null,
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([payer])
@@ -156,9 +183,17 @@ async function main() {
   // Step 5: Commit randomness
   console.log("\n--- Step 5: Commit Randomness ---");
   const commitIx = await randomness.commitIx(ON_DEMAND_DEVNET_QUEUE);
+  // Bind the lottery to this randomness in the same transaction
+  const lotteryCommitIx = await program.methods
+    .commitRandomness()
+    .accountsStrict({
+      lottery: lotteryPda,
+      randomnessAccount: randomness.pubkey,
+    })
+    .instruction();
   const commitTx = await asV0Tx({
     connection,
-    ixs: [commitIx],
+    ixs: [commitIx, lotteryCommitIx],
     payer: payer.publicKey,
     signers: [payer],
     computeUnitPrice: 75_000,
@@ -176,7 +211,6 @@ async function main() {
     .accountsStrict({
       lottery: lotteryPda,
       randomnessAccount: randomness.pubkey,
-      authority: payer.publicKey,
     })
     .instruction();
 
@@ -203,14 +237,14 @@ async function main() {
   // Step 7: Find winner off-chain
   console.log("\n--- Step 7: Find Winner (Off-chain) ---");
   const onChainTotalWeight = BigInt(lottery.totalWeight.toString());
-  const selectionPoint = computeSelectionPoint(vrfResult, onChainTotalWeight);
+  const selectionPoint = computeDrawPoint(vrfResult, 0, onChainTotalWeight);
 
   console.log("On-chain total weight:", onChainTotalWeight.toString());
   console.log("Selection point:", selectionPoint.toString());
 
   // Find winner from our tree (only works if we just initialized)
   if (!existingLottery) {
-    const { entry: winner, index } = tree.findWinner(vrfResult);
+    const [{ entry: winner, index }] = tree.drawWinners(vrfResult, 1);
     console.log("Winner index:", index);
     console.log("Winner address:", winner.address.toBase58());
     console.log("Winner balance:", winner.balance.toString());
@@ -228,7 +262,7 @@ async function main() {
 
     // Step 8: Claim winner
     console.log("\n--- Step 8: Claim Winner (On-chain Verification) ---");
-    const winnerProof = tree.generateWinnerProof(vrfResult);
+    const [winnerProof] = tree.generateWinnerProofs(vrfResult, 1);
     const anchorProof = proofToAnchorFormat(winnerProof);
 
     console.log("Submitting claim with proof length:", anchorProof.proof.length);
@@ -243,7 +277,13 @@ async function main() {
       })
       .accountsStrict({
         lottery: lotteryPda,
+        prizeVault: prizeVaultPda,
+        prizeMint: null,
+        prizeTokenVault: null,
+        claimantTokenAccount: null,
         claimant: payer.publicKey,
+        tokenProgram: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([payer])
       .rpc();
@@ -253,8 +293,8 @@ async function main() {
     console.log("\n--- Step 9: Final State ---");
     const finalLottery = await program.account.lottery.fetch(lotteryPda);
     console.log("Finalized:", finalLottery.finalized);
-    console.log("Winner on-chain:", finalLottery.winner?.toBase58());
-    console.log("Winner weight:", finalLottery.winnerWeight.toString());
+    console.log("Winner on-chain:", finalLottery.winners[0]?.address.toBase58());
+    console.log("Winner weight:", finalLottery.winners[0]?.weight.toString());
 
     console.log("\n=== TEST COMPLETE ✅ ===");
   } else {
@@ -0,0 +1,281 @@
+//! Off-chain builder for the weighted holder tree used by `initialize_lottery`
+//! and `claim_winner`.
+//!
+//! Leaves and pairs are hashed with the program's own `compute_leaf_hash` and
+//! `hash_pair`, holders are ordered by address bytes and odd nodes are
+//! promoted unhashed, so roots and proofs match `scripts/merkle.ts`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_merkle_tree::merkle::{compute_leaf_hash, hash_pair, verify_proof};
+use serde_json::Value;
+
+/// Errors raised while reading a holder list or building the tree
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// A line or entry of the input could not be parsed
+    Parse(String),
+    /// No holder has a non-zero balance
+    Empty,
+    /// A locally generated proof did not verify against the root
+    InvalidProof(Pubkey),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(msg) => write!(f, "parse error: {msg}"),
+            Self::Empty => write!(f, "no holders with a non-zero balance"),
+            Self::InvalidProof(address) => write!(f, "proof for {address} does not verify"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Input formats accepted by the builder
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// `address,balance` lines; a non-numeric header line is skipped
+    Csv,
+    /// `{ "address": balance }`, `[{ "address", "balance" }]`, or a
+    /// `getProgramAccounts` jsonParsed token account dump
+    Json,
+}
+
+/// Holder entry with its cumulative weight range `[prev_cumulative, cumulative)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HolderEntry {
+    pub address: Pubkey,
+    pub balance: u64,
+    pub prev_cumulative: u128,
+    pub cumulative: u128,
+}
+
+fn parse_address(s: &str) -> Result<Pubkey, SnapshotError> {
+    Pubkey::from_str(s.trim()).map_err(|_| SnapshotError::Parse(format!("invalid address {s:?}")))
+}
+
+fn parse_balance(value: &Value) -> Result<u64, SnapshotError> {
+    match value {
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+    .ok_or_else(|| SnapshotError::Parse(format!("invalid balance {value}")))
+}
+
+/// Read `(address, balance)` pairs from a holder list
+///
+/// Balances of repeated addresses are summed, so a token account dump with
+/// several accounts per owner yields one holder per owner.
+pub fn parse_holders(input: &str, format: Format) -> Result<Vec<(Pubkey, u64)>, SnapshotError> {
+    let raw = match format {
+        Format::Csv => parse_csv(input)?,
+        Format::Json => {
+            let value: Value = serde_json::from_str(input)
+                .map_err(|e| SnapshotError::Parse(format!("invalid JSON: {e}")))?;
+            parse_json(&value)?
+        }
+    };
+
+    let mut balances: BTreeMap<Pubkey, u64> = BTreeMap::new();
+    for (address, balance) in raw {
+        let total = balances.entry(address).or_default();
+        *total = total
+            .checked_add(balance)
+            .ok_or_else(|| SnapshotError::Parse(format!("balance overflow for {address}")))?;
+    }
+    Ok(balances.into_iter().collect())
+}
+
+fn parse_csv(input: &str) -> Result<Vec<(Pubkey, u64)>, SnapshotError> {
+    let mut holders = Vec::new();
+    for (line_no, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (address, balance) = line.split_once(',').ok_or_else(|| {
+            SnapshotError::Parse(format!("line {}: expected address,balance", line_no + 1))
+        })?;
+        let balance = balance.trim();
+        if line_no == 0 && balance.parse::<u64>().is_err() {
+            continue; // header
+        }
+        holders.push((
+            parse_address(address)?,
+            parse_balance(&Value::String(balance.into()))?,
+        ));
+    }
+    Ok(holders)
+}
+
+fn parse_json(value: &Value) -> Result<Vec<(Pubkey, u64)>, SnapshotError> {
+    match value {
+        // { "address": balance, ... }
+        Value::Object(map) if !map.contains_key("result") => map
+            .iter()
+            .map(|(address, balance)| Ok((parse_address(address)?, parse_balance(balance)?)))
+            .collect(),
+        // Raw RPC response: { "result": [...] }
+        Value::Object(map) => parse_json(&map["result"]),
+        Value::Array(items) => items.iter().map(parse_json_item).collect(),
+        _ => Err(SnapshotError::Parse(
+            "expected a JSON object or array".into(),
+        )),
+    }
+}
+
+fn parse_json_item(item: &Value) -> Result<(Pubkey, u64), SnapshotError> {
+    // Token account from getProgramAccounts with jsonParsed encoding
+    let info = &item["account"]["data"]["parsed"]["info"];
+    if info.is_object() {
+        let owner = info["owner"]
+            .as_str()
+            .ok_or_else(|| SnapshotError::Parse("token account without owner".into()))?;
+        return Ok((
+            parse_address(owner)?,
+            parse_balance(&info["tokenAmount"]["amount"])?,
+        ));
+    }
+
+    // { "address": ..., "balance": ... }
+    let address = item["address"]
+        .as_str()
+        .ok_or_else(|| SnapshotError::Parse(format!("entry without address: {item}")))?;
+    Ok((parse_address(address)?, parse_balance(&item["balance"])?))
+}
+
+/// Weighted holder tree with every level kept for proof generation
+pub struct Snapshot {
+    entries: Vec<HolderEntry>,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl Snapshot {
+    /// Build the tree; holders are sorted by address and zero balances dropped,
+    /// since an empty range can never be selected or claimed
+    pub fn build(holders: &[(Pubkey, u64)]) -> Result<Self, SnapshotError> {
+        let mut holders: Vec<(Pubkey, u64)> = holders
+            .iter()
+            .copied()
+            .filter(|(_, balance)| *balance > 0)
+            .collect();
+        if holders.is_empty() {
+            return Err(SnapshotError::Empty);
+        }
+        holders.sort_by_key(|(address, _)| address.to_bytes());
+
+        let mut cumulative = 0u128;
+        let entries: Vec<HolderEntry> = holders
+            .into_iter()
+            .map(|(address, balance)| {
+                let prev_cumulative = cumulative;
+                cumulative += balance as u128;
+                HolderEntry {
+                    address,
+                    balance,
+                    prev_cumulative,
+                    cumulative,
+                }
+            })
+            .collect();
+
+        let mut levels = vec![entries
+            .iter()
+            .map(|e| compute_leaf_hash(&e.address, e.balance, e.prev_cumulative, e.cumulative))
+            .collect::<Vec<_>>()];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    // Odd node, promote to next level
+                    [odd] => *odd,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(Self { entries, levels })
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.levels.len() - 1][0]
+    }
+
+    pub fn total_weight(&self) -> u128 {
+        self.entries[self.entries.len() - 1].cumulative
+    }
+
+    pub fn entries(&self) -> &[HolderEntry] {
+        &self.entries
+    }
+
+    /// Sibling path for the holder at `index`, leaf to root
+    pub fn proof(&self, index: usize) -> Vec<[u8; 32]> {
+        let mut proof = Vec::with_capacity(self.levels.len() - 1);
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                proof.push(*sibling);
+            }
+            position /= 2;
+        }
+        proof
+    }
+
+    /// Check every holder's proof with the on-chain `verify_proof`
+    pub fn verify_all(&self) -> Result<(), SnapshotError> {
+        let root = self.root();
+        for (index, e) in self.entries.iter().enumerate() {
+            let leaf = compute_leaf_hash(&e.address, e.balance, e.prev_cumulative, e.cumulative);
+            if !verify_proof(leaf, &self.proof(index), &root) {
+                return Err(SnapshotError::InvalidProof(e.address));
+            }
+        }
+        Ok(())
+    }
+
+    /// Root and total weight, in the layout `initialize_lottery` callers read
+    pub fn summary_json(&self) -> Value {
+        let root = self.root();
+        serde_json::json!({
+            "holderCount": self.entries.len(),
+            "totalWeight": self.total_weight().to_string(),
+            "merkleRoot": to_hex(&root),
+            "merkleRootArray": root.to_vec(),
+        })
+    }
+
+    /// Per-holder claim data for `claim_winner`, keyed by address
+    pub fn proofs_json(&self) -> Value {
+        let proofs: serde_json::Map<String, Value> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, e)| {
+                let proof: Vec<String> = self.proof(index).iter().map(|p| to_hex(p)).collect();
+                (
+                    e.address.to_string(),
+                    serde_json::json!({
+                        "balance": e.balance.to_string(),
+                        "prevCumulative": e.prev_cumulative.to_string(),
+                        "cumulative": e.cumulative.to_string(),
+                        "proof": proof,
+                    }),
+                )
+            })
+            .collect();
+        Value::Object(proofs)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+use lottery_snapshot::{parse_holders, Format, Snapshot};
+
+const USAGE: &str = "\
+Usage: lottery-snapshot <holders> [--format csv|json] [--out <dir>]
+
+  <holders>   CSV (address,balance), JSON map/array, or a jsonParsed
+              token account dump from getProgramAccounts
+  --format    Input format; inferred from the file extension by default
+  --out       Output directory for snapshot.json and proofs.json (default: .)";
+
+struct Args {
+    input: PathBuf,
+    format: Format,
+    out: PathBuf,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut input = None;
+    let mut format = None;
+    let mut out = PathBuf::from(".");
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = Some(match args.next().as_deref() {
+                    Some("csv") => Format::Csv,
+                    Some("json") => Format::Json,
+                    other => return Err(format!("unknown format {other:?}")),
+                })
+            }
+            "--out" => out = args.next().ok_or("--out needs a directory")?.into(),
+            "-h" | "--help" => return Err(String::new()),
+            _ if input.is_none() => input = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    let input = input.ok_or("missing holders file")?;
+    let format = match format {
+        Some(format) => format,
+        None => match input.extension().and_then(|e| e.to_str()) {
+            Some("csv") => Format::Csv,
+            Some("json") => Format::Json,
+            _ => return Err("cannot infer format, pass --format".into()),
+        },
+    };
+    Ok(Args { input, format, out })
+}
+
+fn write_json(path: &Path, value: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, serde_json::to_string_pretty(value)?)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let input = fs::read_to_string(&args.input)?;
+    let holders = parse_holders(&input, args.format)?;
+    let snapshot = Snapshot::build(&holders)?;
+
+    snapshot.verify_all()?;
+    println!("Holders: {}", snapshot.entries().len());
+    println!("Total weight: {}", snapshot.total_weight());
+    println!(
+        "Merkle root: {}",
+        snapshot.summary_json()["merkleRoot"]
+            .as_str()
+            .unwrap_or_default()
+    );
+    println!("All proofs verified");
+
+    fs::create_dir_all(&args.out)?;
+    write_json(&args.out.join("snapshot.json"), &snapshot.summary_json())?;
+    write_json(&args.out.join("proofs.json"), &snapshot.proofs_json())?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("error: {msg}\n");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::fs;
+
+use anchor_lang::prelude::Pubkey;
+use lottery_snapshot::{parse_holders, Format, Snapshot};
+
+/// `holders.json` and `output.json` were produced by `scripts/merkle.ts`
+#[test]
+fn test_matches_typescript_snapshot() {
+    let holders = fs::read_to_string("../holders.json").unwrap();
+    let expected: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("../output.json").unwrap()).unwrap();
+
+    let snapshot = Snapshot::build(&parse_holders(&holders, Format::Json).unwrap()).unwrap();
+    snapshot.verify_all().unwrap();
+
+    let summary = snapshot.summary_json();
+    assert_eq!(summary["merkleRoot"], expected["merkleRoot"]);
+    assert_eq!(summary["totalWeight"], expected["totalWeight"]);
+    assert_eq!(summary["holderCount"], expected["holderCount"]);
+
+    let first = &snapshot.entries()[0];
+    assert_eq!(first.address.to_string(), expected["entries"][0]["address"]);
+    assert_eq!(
+        first.cumulative.to_string(),
+        expected["entries"][0]["cumulative"]
+    );
+}
+
+#[test]
+fn test_formats_agree() {
+    let a = Pubkey::new_unique();
+    let b = Pubkey::new_unique();
+
+    let csv = format!("address,balance\n{a},100\n{b},250\n{a},50\n");
+    let map = format!(r#"{{"{a}": "150", "{b}": 250}}"#);
+    let dump = format!(
+        r#"{{"result": [
+            {{"account": {{"data": {{"parsed": {{"info": {{"owner": "{a}", "tokenAmount": {{"amount": "150"}}}}}}}}}}}},
+            {{"account": {{"data": {{"parsed": {{"info": {{"owner": "{b}", "tokenAmount": {{"amount": "200"}}}}}}}}}}}},
+            {{"account": {{"data": {{"parsed": {{"info": {{"owner": "{b}", "tokenAmount": {{"amount": "50"}}}}}}}}}}}}
+        ]}}"#
+    );
+
+    let roots: Vec<[u8; 32]> = [
+        (csv, Format::Csv),
+        (map, Format::Json),
+        (dump, Format::Json),
+    ]
+    .iter()
+    .map(|(input, format)| {
+        let snapshot = Snapshot::build(&parse_holders(input, *format).unwrap()).unwrap();
+        assert_eq!(snapshot.total_weight(), 400);
+        snapshot.root()
+    })
+    .collect();
+
+    assert_eq!(roots[0], roots[1]);
+    assert_eq!(roots[1], roots[2]);
+}
+
+#[test]
+fn test_zero_balances_are_dropped() {
+    let holders = vec![(Pubkey::new_unique(), 0), (Pubkey::new_unique(), 10)];
+    let snapshot = Snapshot::build(&holders).unwrap();
+    assert_eq!(snapshot.entries().len(), 1);
+    assert_eq!(snapshot.proof(0), Vec::<[u8; 32]>::new());
+
+    assert!(Snapshot::build(&[(Pubkey::new_unique(), 0)]).is_err());
+}
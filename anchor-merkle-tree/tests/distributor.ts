@@ -0,0 +1,299 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  LAMPORTS_PER_SOL,
+} from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { AnchorMerkleTree } from "../target/types/anchor_merkle_tree";
+import {
+  ClaimEntry,
+  DistributorTree,
+  buildClaimEntries,
+} from "../scripts/merkle";
+
+describe("Merkle Airdrop Distributor", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace
+    .AnchorMerkleTree as Program<AnchorMerkleTree>;
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  const claimants = [0, 1, 2].map(() => Keypair.generate());
+
+  let mint: PublicKey;
+  let authorityTokenAccount: PublicKey;
+  let distributorPda: PublicKey;
+  let vault: PublicKey;
+  let tree: DistributorTree;
+  let clawbackAt: number;
+
+  const bitmapPda = (round: number) => {
+    const seed = Buffer.alloc(4);
+    seed.writeUInt32LE(round);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_bitmap"), distributorPda.toBuffer(), seed],
+      program.programId
+    )[0];
+  };
+
+  const chainTime = async () =>
+    (await connection.getBlockTime(await connection.getSlot())) as number;
+
+  const buildTree = (amounts: number[]) =>
+    new DistributorTree(
+      buildClaimEntries(
+        Object.fromEntries(
+          claimants.map((kp, i) => [kp.publicKey.toBase58(), amounts[i]])
+        )
+      )
+    );
+
+  const entryOf = (kp: Keypair): ClaimEntry =>
+    tree.getEntries().find((e) => e.address.equals(kp.publicKey))!;
+
+  const claim = (kp: Keypair, round: number, amount?: bigint) => {
+    const entry = entryOf(kp);
+    return program.methods
+      .claimAirdrop(
+        entry.index,
+        new BN((amount ?? entry.amount).toString()),
+        tree.getProof(entry.index).map((node) => Array.from(node))
+      )
+      .accountsPartial({
+        distributor: distributorPda,
+        claimBitmap: bitmapPda(round),
+        vault,
+        mint,
+        claimantTokenAccount: getAssociatedTokenAddressSync(
+          mint,
+          kp.publicKey
+        ),
+        claimant: kp.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([kp])
+      .rpc();
+  };
+
+  const clawback = () =>
+    program.methods
+      .clawback()
+      .accountsPartial({
+        distributor: distributorPda,
+        vault,
+        mint,
+        authorityTokenAccount,
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+  const balance = async (owner: PublicKey) =>
+    (await getAccount(connection, getAssociatedTokenAddressSync(mint, owner)))
+      .amount;
+
+  before(async () => {
+    mint = await createMint(connection, payer, payer.publicKey, null, 6);
+    authorityTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        payer,
+        mint,
+        payer.publicKey
+      )
+    ).address;
+    await mintTo(connection, payer, mint, authorityTokenAccount, payer, 10_000);
+
+    // Claimants pay for their own token accounts
+    const fund = new Transaction().add(
+      ...claimants.map((kp) =>
+        SystemProgram.transfer({
+          fromPubkey: payer.publicKey,
+          toPubkey: kp.publicKey,
+          lamports: 0.01 * LAMPORTS_PER_SOL,
+        })
+      )
+    );
+    await provider.sendAndConfirm(fund);
+
+    [distributorPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("distributor"), payer.publicKey.toBuffer(), mint.toBuffer()],
+      program.programId
+    );
+    vault = getAssociatedTokenAddressSync(mint, distributorPda, true);
+  });
+
+  it("initializes and funds a distributor", async () => {
+    tree = buildTree([100, 200, 300]);
+    clawbackAt = (await chainTime()) + 3600;
+
+    await program.methods
+      .initializeDistributor(
+        tree.getRootArray(),
+        tree.getEntries().length,
+        new BN(clawbackAt)
+      )
+      .accountsPartial({
+        distributor: distributorPda,
+        claimBitmap: bitmapPda(0),
+        vault,
+        mint,
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+    await program.methods
+      .fundDistributor(new BN(1_000))
+      .accountsPartial({
+        distributor: distributorPda,
+        vault,
+        mint,
+        authorityTokenAccount,
+        authority: payer.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    expect((await getAccount(connection, vault)).amount).to.equal(1_000n);
+  });
+
+  it("pays a valid claim", async () => {
+    await claim(claimants[0], 0);
+
+    expect(await balance(claimants[0].publicKey)).to.equal(
+      entryOf(claimants[0]).amount
+    );
+    const distributor = await program.account.distributor.fetch(
+      distributorPda
+    );
+    expect(distributor.numClaimed).to.equal(1);
+  });
+
+  it("rejects a second claim of the same leaf", async () => {
+    try {
+      await claim(claimants[0], 0);
+      expect.fail("double claim should fail");
+    } catch (e: any) {
+      expect(e.message).to.include("AlreadyClaimed");
+    }
+  });
+
+  it("rejects a claim with a bad proof", async () => {
+    // Claiming more than the allocation changes the leaf
+    const entry = entryOf(claimants[1]);
+    try {
+      await claim(claimants[1], 0, entry.amount + 1n);
+      expect.fail("inflated claim should fail");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidMerkleProof");
+    }
+  });
+
+  it("rejects clawback before the deadline", async () => {
+    try {
+      await clawback();
+      expect.fail("early clawback should fail");
+    } catch (e: any) {
+      expect(e.message).to.include("ClawbackNotReady");
+    }
+  });
+
+  it("accepts claims against a rotated root only", async () => {
+    const oldTree = tree;
+    tree = buildTree([50, 250, 300]);
+    clawbackAt = (await chainTime()) + 20;
+
+    await program.methods
+      .rotateRoot(
+        tree.getRootArray(),
+        tree.getEntries().length,
+        new BN(clawbackAt)
+      )
+      .accountsPartial({
+        distributor: distributorPda,
+        previousBitmap: bitmapPda(0),
+        claimBitmap: bitmapPda(1),
+        authority: payer.publicKey,
+      })
+      .rpc();
+
+    // A proof against the old root no longer verifies
+    const stale = oldTree.getEntries().find((e) =>
+      e.address.equals(claimants[1].publicKey)
+    )!;
+    try {
+      await program.methods
+        .claimAirdrop(
+          stale.index,
+          new BN(stale.amount.toString()),
+          oldTree.getProof(stale.index).map((node) => Array.from(node))
+        )
+        .accountsPartial({
+          distributor: distributorPda,
+          claimBitmap: bitmapPda(1),
+          vault,
+          mint,
+          claimantTokenAccount: getAssociatedTokenAddressSync(
+            mint,
+            claimants[1].publicKey
+          ),
+          claimant: claimants[1].publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([claimants[1]])
+        .rpc();
+      expect.fail("old-root claim should fail");
+    } catch (e: any) {
+      expect(e.message).to.include("InvalidMerkleProof");
+    }
+
+    // The new round has its own bitmap, so the round 0 claimant can claim
+    const before = await balance(claimants[0].publicKey);
+    await claim(claimants[0], 1);
+    await claim(claimants[1], 1);
+
+    expect(await balance(claimants[0].publicKey)).to.equal(
+      before + entryOf(claimants[0]).amount
+    );
+    expect(await balance(claimants[1].publicKey)).to.equal(
+      entryOf(claimants[1]).amount
+    );
+  });
+
+  it("closes claims and claws back after the deadline", async () => {
+    while ((await chainTime()) < clawbackAt) {
+      await new Promise((resolve) => setTimeout(resolve, 2_000));
+    }
+
+    try {
+      await claim(claimants[2], 1);
+      expect.fail("late claim should fail");
+    } catch (e: any) {
+      expect(e.message).to.include("ClaimWindowClosed");
+    }
+
+    const remaining = (await getAccount(connection, vault)).amount;
+    const before = (await getAccount(connection, authorityTokenAccount))
+      .amount;
+    await clawback();
+
+    expect((await getAccount(connection, vault)).amount).to.equal(0n);
+    expect(
+      (await getAccount(connection, authorityTokenAccount)).amount
+    ).to.equal(before + remaining);
+  });
+});
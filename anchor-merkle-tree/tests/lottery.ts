@@ -15,7 +15,7 @@ import {
   MerkleTree,
   buildHolderEntries,
   proofToAnchorFormat,
-  computeSelectionPoint,
+  computeDrawPoint,
 } from "../scripts/merkle";
 
 describe("Merkle Tree Weighted Lottery", () => {
@@ -25,7 +25,8 @@ describe("Merkle Tree Weighted Lottery", () => {
     .AnchorMerkleTree as Program<AnchorMerkleTree>;
   const provider = anchor.AnchorProvider.env();
 
-  // Switchboard setup
+  // Switchboard setup; the program must be built with `--features devnet`
+  // to accept randomness accounts owned by the devnet program
   const sbQueue = ON_DEMAND_DEVNET_QUEUE;
   const sbProgramId = ON_DEMAND_DEVNET_PID;
 
@@ -36,6 +37,12 @@ describe("Merkle Tree Weighted Lottery", () => {
   // Lottery PDAs
   let lotteryPda: PublicKey;
   let lotteryBump: number;
+  let prizeVaultPda: PublicKey;
+
+  // SOL prize escrowed at initialization, claimable for one hour after VRF
+  const lotteryId = new BN(Date.now());
+  const prizeAmount = new BN(10_000_000);
+  const claimWindow = new BN(3600);
 
   // Test data
   let tree: MerkleTree;
@@ -50,7 +57,15 @@ describe("Merkle Tree Weighted Lottery", () => {
 
     // Derive lottery PDA
     [lotteryPda, lotteryBump] = PublicKey.findProgramAddressSync(
-      [Buffer.from("lottery"), payer.publicKey.toBuffer()],
+      [
+        Buffer.from("lottery"),
+        payer.publicKey.toBuffer(),
+        lotteryId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+    [prizeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("prize_vault"), lotteryPda.toBuffer()],
       program.programId
     );
 
@@ -103,10 +118,19 @@ describe("Merkle Tree Weighted Lottery", () => {
 
       try {
         await program.methods
-          .initializeLottery(merkleRoot as number[], new BN(totalWeight.toString()))
+          .initializeLottery(lotteryId, merkleRoot as number[], new BN(totalWeight.toString()), {
+            numWinners: 1,
+            prizeAmount,
+            claimWindow,
+          })
           .accountsStrict({
             lottery: lotteryPda,
+            prizeVault: prizeVaultPda,
+            prizeMint: null,
+            prizeTokenVault: null,
+            authorityTokenAccount: null,
             authority: payer.publicKey,
+            tokenProgram: null,
             systemProgram: SystemProgram.programId,
           })
           .signers([payer])
@@ -162,9 +186,18 @@ describe("Merkle Tree Weighted Lottery", () => {
 
       // Commit randomness
       const sbCommitIx = await randomness.commitIx(sbQueue);
+      // Bind the lottery to this randomness in the same transaction
+      const lotteryCommitIx = await program.methods
+        .commitRandomness()
+        .accountsStrict({
+          lottery: lotteryPda,
+          randomnessAccount: randomness.pubkey,
+          authority: payer.publicKey,
+        })
+        .instruction();
       const commitTx = await asV0Tx({
         connection: provider.connection,
-        ixs: [sbCommitIx],
+        ixs: [sbCommitIx, lotteryCommitIx],
         payer: payer.publicKey,
         signers: [payer],
         computeUnitPrice: 75_000,
@@ -201,9 +234,7 @@ describe("Merkle Tree Weighted Lottery", () => {
         .accountsStrict({
           lottery: lotteryPda,
           randomnessAccount: randomness.pubkey,
-          authority: payer.publicKey,
         })
-        .signers([payer])
         .rpc();
 
       // Verify VRF result is set
@@ -213,7 +244,7 @@ describe("Merkle Tree Weighted Lottery", () => {
       console.log("VRF result set:", vrfResult.toString("hex"));
 
       // Log selection point
-      const selectionPoint = computeSelectionPoint(vrfResult, totalWeight);
+      const selectionPoint = computeDrawPoint(vrfResult, 0, totalWeight);
       console.log("Selection point:", selectionPoint.toString());
     });
 
@@ -227,9 +258,9 @@ describe("Merkle Tree Weighted Lottery", () => {
         return;
       }
 
-      // Find winner
-      const { entry: winner, index } = tree.findWinner(vrfResult);
-      const selectionPoint = computeSelectionPoint(vrfResult, totalWeight);
+      // Find winner (first draw)
+      const [{ entry: winner }] = tree.drawWinners(vrfResult, 1);
+      const selectionPoint = computeDrawPoint(vrfResult, 0, totalWeight);
 
       console.log("\n=== Winner Selection ===");
       console.log("Selection point:", selectionPoint.toString());
@@ -251,7 +282,7 @@ describe("Merkle Tree Weighted Lottery", () => {
       }
 
       // Generate proof
-      const winnerProof = tree.generateWinnerProof(vrfResult);
+      const [winnerProof] = tree.generateWinnerProofs(vrfResult, 1);
       const anchorProof = proofToAnchorFormat(winnerProof);
 
       console.log("\n=== Claiming Winner ===");
@@ -268,7 +299,13 @@ describe("Merkle Tree Weighted Lottery", () => {
         })
         .accountsStrict({
           lottery: lotteryPda,
+          prizeVault: prizeVaultPda,
+          prizeMint: null,
+          prizeTokenVault: null,
+          claimantTokenAccount: null,
           claimant: payer.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
         })
         .signers([payer])
         .rpc();
@@ -276,14 +313,17 @@ describe("Merkle Tree Weighted Lottery", () => {
       // Verify lottery is finalized
       const finalLottery = await program.account.lottery.fetch(lotteryPda);
       expect(finalLottery.finalized).to.be.true;
-      expect(finalLottery.winner?.toBase58()).to.equal(winner.address.toBase58());
-      expect(finalLottery.winnerWeight.toString()).to.equal(
+      expect(finalLottery.winners).to.have.length(1);
+      expect(finalLottery.winners[0].address.toBase58()).to.equal(
+        winner.address.toBase58()
+      );
+      expect(finalLottery.winners[0].weight.toString()).to.equal(
         winner.balance.toString()
       );
 
       console.log("\n=== Winner Claimed Successfully! ===");
-      console.log("Winner:", finalLottery.winner?.toBase58());
-      console.log("Winner weight:", finalLottery.winnerWeight.toString());
+      console.log("Winner:", finalLottery.winners[0].address.toBase58());
+      console.log("Winner weight:", finalLottery.winners[0].weight.toString());
     });
   });
 
@@ -300,7 +340,8 @@ describe("Merkle Tree Weighted Lottery", () => {
     });
 
     it("rejects double claim", async () => {
-      // Would fail because lottery is already finalized
+      // Would fail with LeafAlreadyWon, or LotteryAlreadyFinalized once all
+      // winners are drawn
       console.log("Would test double claim rejection");
     });
   });
@@ -365,4 +406,28 @@ describe("Merkle Tree Unit Tests", () => {
     expect(Number(result.entry.prevCumulative)).to.equal(100);
     expect(Number(result.entry.cumulative)).to.equal(200);
   });
+
+  it("draws multiple winners without replacement", () => {
+    const holders: Record<string, string> = {
+      "11111111111111111111111111111112": "9000", // dominates most draws
+      "22222222222222222222222222222222": "500",
+      "33333333333333333333333333333333": "300",
+      "44444444444444444444444444444444": "200",
+    };
+
+    const tree = new MerkleTree(buildHolderEntries(holders));
+    const vrf = Buffer.alloc(32, 7);
+    const winners = tree.drawWinners(vrf, 3);
+
+    expect(winners).to.have.length(3);
+    expect(new Set(winners.map((w) => w.index)).size).to.equal(3);
+
+    // Draw indices are increasing, and each winner's point lies in its range
+    for (let i = 0; i < winners.length; i++) {
+      if (i > 0) expect(winners[i].draw).to.be.greaterThan(winners[i - 1].draw);
+      const point = computeDrawPoint(vrf, winners[i].draw, tree.getTotalWeight());
+      expect(point >= winners[i].entry.prevCumulative).to.be.true;
+      expect(point < winners[i].entry.cumulative).to.be.true;
+    }
+  });
 });
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum MultisigError {
+    #[msg("A multisig needs between 1 and 10 owners")]
+    InvalidOwners,
+    #[msg("Owners must be unique")]
+    DuplicateOwner,
+    #[msg("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("Signer is not an owner of this multisig")]
+    NotAnOwner,
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+    #[msg("Proposal has expired")]
+    ProposalExpired,
+    #[msg("Owners or threshold changed since this proposal was created")]
+    StaleProposal,
+    #[msg("Owner has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Not enough approvals to execute")]
+    ThresholdNotMet,
+    #[msg("Withdrawals need the proposal's destination account")]
+    DestinationMismatch,
+    #[msg("Only the proposer can cancel a proposal")]
+    Unauthorized,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Multisig, WithdrawProposal};
+
+#[derive(Accounts)]
+pub struct Approve<'info> {
+    pub owner: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            WithdrawProposal::SEED_PREFIX,
+            multisig.key().as_ref(),
+            &proposal.proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        has_one = multisig
+    )]
+    pub proposal: Account<'info, WithdrawProposal>,
+}
+
+impl<'info> Approve<'info> {
+    pub fn approve(&mut self) -> Result<()> {
+        let owner_index = self.multisig.owner_index(&self.owner.key())?;
+        self.proposal
+            .require_live(&self.multisig, Clock::get()?.unix_timestamp)?;
+        self.proposal.approve(owner_index)?;
+
+        msg!(
+            "Proposal {}: {}/{} approvals",
+            self.proposal.proposal_id,
+            self.proposal.approval_count(),
+            self.multisig.threshold
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MultisigError;
+use crate::state::{Multisig, WithdrawProposal};
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            WithdrawProposal::SEED_PREFIX,
+            multisig.key().as_ref(),
+            &proposal.proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        has_one = multisig,
+        has_one = proposer @ MultisigError::Unauthorized,
+        close = proposer
+    )]
+    pub proposal: Account<'info, WithdrawProposal>,
+}
+
+impl<'info> CancelProposal<'info> {
+    pub fn cancel_proposal(&mut self) -> Result<()> {
+        msg!("Proposal {} cancelled", self.proposal.proposal_id);
+        Ok(())
+    }
+}
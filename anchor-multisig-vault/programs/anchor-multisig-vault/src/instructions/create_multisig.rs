@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Multisig;
+
+#[derive(Accounts)]
+#[instruction(multisig_id: u64)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [Multisig::SEED_PREFIX, creator.key().as_ref(), &multisig_id.to_le_bytes()],
+        bump
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// System-owned PDA holding the multisig's SOL
+    #[account(
+        seeds = [Multisig::VAULT_SEED, multisig.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateMultisig<'info> {
+    pub fn create_multisig(
+        &mut self,
+        multisig_id: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        bumps: &CreateMultisigBumps,
+    ) -> Result<()> {
+        Multisig::validate_owners(&owners, threshold)?;
+
+        self.multisig.set_inner(Multisig {
+            creator: self.creator.key(),
+            multisig_id,
+            owners,
+            threshold,
+            proposal_count: 0,
+            owner_set_seqno: 0,
+            bump: bumps.multisig,
+            vault_bump: bumps.vault,
+        });
+
+        Ok(())
+    }
+}
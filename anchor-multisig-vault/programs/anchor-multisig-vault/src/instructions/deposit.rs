@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::error::MultisigError;
+use crate::state::Multisig;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    /// Anyone can fund the vault
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::VAULT_SEED, multisig.key().as_ref()],
+        bump = multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, MultisigError::InvalidAmount);
+
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.depositor.to_account_info(),
+                    to: self.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::error::MultisigError;
+use crate::state::{Multisig, ProposalAction, WithdrawProposal};
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    /// Any owner can execute once the threshold is met
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            WithdrawProposal::SEED_PREFIX,
+            multisig.key().as_ref(),
+            &proposal.proposal_id.to_le_bytes()
+        ],
+        bump = proposal.bump,
+        has_one = multisig,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Account<'info, WithdrawProposal>,
+
+    /// CHECK: Receives the proposal's rent back; checked by `has_one`
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Multisig::VAULT_SEED, multisig.key().as_ref()],
+        bump = multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Must match the destination of a withdrawal; omit otherwise
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Execute<'info> {
+    pub fn execute(&mut self) -> Result<()> {
+        self.multisig.owner_index(&self.executor.key())?;
+        self.proposal
+            .require_live(&self.multisig, Clock::get()?.unix_timestamp)?;
+        require_gte!(
+            self.proposal.approval_count(),
+            self.multisig.threshold as u32,
+            MultisigError::ThresholdNotMet
+        );
+
+        match self.proposal.action.clone() {
+            ProposalAction::Withdraw {
+                amount,
+                destination,
+            } => {
+                let to = self
+                    .destination
+                    .as_ref()
+                    .filter(|to| to.key() == destination)
+                    .ok_or(MultisigError::DestinationMismatch)?;
+
+                let multisig_key = self.multisig.key();
+                let signer_seeds: &[&[&[u8]]] = &[&[
+                    Multisig::VAULT_SEED,
+                    multisig_key.as_ref(),
+                    &[self.multisig.vault_bump],
+                ]];
+                transfer(
+                    CpiContext::new_with_signer(
+                        self.system_program.to_account_info(),
+                        Transfer {
+                            from: self.vault.to_account_info(),
+                            to: to.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+
+                msg!("Withdrew {} lamports to {}", amount, destination);
+            }
+            ProposalAction::SetOwners { owners } => {
+                // Re-checked, the threshold may have changed since proposing
+                Multisig::validate_owners(&owners, self.multisig.threshold)?;
+                self.multisig.owners = owners;
+                self.multisig.owner_set_seqno += 1;
+            }
+            ProposalAction::SetThreshold { threshold } => {
+                Multisig::validate_owners(&self.multisig.owners, threshold)?;
+                self.multisig.threshold = threshold;
+                self.multisig.owner_set_seqno += 1;
+            }
+        }
+
+        Ok(())
+    }
+}
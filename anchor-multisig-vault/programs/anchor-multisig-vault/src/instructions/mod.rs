@@ -0,0 +1,13 @@
+pub mod approve;
+pub mod cancel_proposal;
+pub mod create_multisig;
+pub mod deposit;
+pub mod execute;
+pub mod propose;
+
+pub use approve::*;
+pub use cancel_proposal::*;
+pub use create_multisig::*;
+pub use deposit::*;
+pub use execute::*;
+pub use propose::*;
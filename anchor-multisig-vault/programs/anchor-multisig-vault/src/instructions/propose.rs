@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MultisigError;
+use crate::state::{Multisig, ProposalAction, WithdrawProposal};
+
+#[derive(Accounts)]
+pub struct Propose<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + WithdrawProposal::INIT_SPACE,
+        seeds = [
+            WithdrawProposal::SEED_PREFIX,
+            multisig.key().as_ref(),
+            &multisig.proposal_count.to_le_bytes()
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, WithdrawProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Propose<'info> {
+    pub fn propose(
+        &mut self,
+        action: ProposalAction,
+        expires_at: i64,
+        bumps: &ProposeBumps,
+    ) -> Result<()> {
+        let multisig = &mut self.multisig;
+        let owner_index = multisig.owner_index(&self.proposer.key())?;
+
+        action.validate(multisig)?;
+        require_gt!(
+            expires_at,
+            Clock::get()?.unix_timestamp,
+            MultisigError::InvalidExpiry
+        );
+
+        let proposal = &mut self.proposal;
+        proposal.set_inner(WithdrawProposal {
+            multisig: multisig.key(),
+            proposer: self.proposer.key(),
+            proposal_id: multisig.proposal_count,
+            action,
+            expires_at,
+            approvals: 0,
+            owner_set_seqno: multisig.owner_set_seqno,
+            bump: bumps.proposal,
+        });
+        // Proposing counts as the proposer's approval
+        proposal.approve(owner_index)?;
+
+        multisig.proposal_count += 1;
+
+        msg!("Proposal {} created", proposal.proposal_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+
+use instructions::*;
+use state::ProposalAction;
+
+declare_id!("tqHa6mos5s64PuahY4kzkcQyfZLE1DxB2RDAT5Gw1b4");
+
+#[program]
+pub mod anchor_multisig_vault {
+    use super::*;
+
+    /// Create an M-of-N multisig and its SOL vault
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        multisig_id: u64,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_multisig(multisig_id, owners, threshold, &ctx.bumps)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount)
+    }
+
+    /// Propose a withdrawal or an owner/threshold change; counts as the
+    /// proposer's approval
+    pub fn propose(ctx: Context<Propose>, action: ProposalAction, expires_at: i64) -> Result<()> {
+        ctx.accounts.propose(action, expires_at, &ctx.bumps)
+    }
+
+    pub fn approve(ctx: Context<Approve>) -> Result<()> {
+        ctx.accounts.approve()
+    }
+
+    /// Carry out a proposal that reached the threshold, then close it
+    pub fn execute(ctx: Context<Execute>) -> Result<()> {
+        ctx.accounts.execute()
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        ctx.accounts.cancel_proposal()
+    }
+}
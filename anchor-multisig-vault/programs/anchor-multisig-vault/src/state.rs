@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::error::MultisigError;
+
+/// Most owners a multisig can have. Approvals are a bitmask over owner
+/// indices, so this must fit in `WithdrawProposal::approvals`.
+pub const MAX_OWNERS: usize = 10; // keep in sync with #[max_len] below
+
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    pub creator: Pubkey,
+    pub multisig_id: u64,
+    #[max_len(10)]
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Id of the next proposal
+    pub proposal_count: u64,
+    /// Bumped whenever owners or threshold change, so approvals collected
+    /// under the old set can't be replayed
+    pub owner_set_seqno: u32,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Multisig {
+    pub const SEED_PREFIX: &'static [u8] = b"multisig";
+    pub const VAULT_SEED: &'static [u8] = b"vault";
+
+    /// Check an owner set and threshold before they are stored
+    pub fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_OWNERS,
+            MultisigError::InvalidOwners
+        );
+        for (i, owner) in owners.iter().enumerate() {
+            require!(!owners[..i].contains(owner), MultisigError::DuplicateOwner);
+        }
+        require!(
+            threshold > 0 && threshold as usize <= owners.len(),
+            MultisigError::InvalidThreshold
+        );
+        Ok(())
+    }
+
+    pub fn owner_index(&self, key: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or(MultisigError::NotAnOwner.into())
+    }
+}
+
+/// What a proposal does once it reaches the threshold
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug, PartialEq, Eq)]
+pub enum ProposalAction {
+    /// Send `amount` lamports from the vault to `destination`
+    Withdraw {
+        amount: u64,
+        destination: Pubkey,
+    },
+    /// Replace the owner set, keeping the threshold
+    SetOwners {
+        #[max_len(10)]
+        owners: Vec<Pubkey>,
+    },
+    SetThreshold {
+        threshold: u8,
+    },
+}
+
+impl ProposalAction {
+    pub fn validate(&self, multisig: &Multisig) -> Result<()> {
+        match self {
+            Self::Withdraw { amount, .. } => require_gt!(*amount, 0, MultisigError::InvalidAmount),
+            Self::SetOwners { owners } => Multisig::validate_owners(owners, multisig.threshold)?,
+            Self::SetThreshold { threshold } => {
+                Multisig::validate_owners(&multisig.owners, *threshold)?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A pending vault withdrawal, or owner/threshold change, collecting
+/// approvals. Closed to the proposer once executed or cancelled.
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawProposal {
+    pub multisig: Pubkey,
+    pub proposer: Pubkey,
+    pub proposal_id: u64,
+    pub action: ProposalAction,
+    pub expires_at: i64,
+    /// Bit `i` is set once `multisig.owners[i]` has approved
+    pub approvals: u16,
+    /// `multisig.owner_set_seqno` when the proposal was created
+    pub owner_set_seqno: u32,
+    pub bump: u8,
+}
+
+impl WithdrawProposal {
+    pub const SEED_PREFIX: &'static [u8] = b"proposal";
+
+    pub fn approve(&mut self, owner_index: usize) -> Result<()> {
+        let bit = 1 << owner_index;
+        require!(self.approvals & bit == 0, MultisigError::AlreadyApproved);
+        self.approvals |= bit;
+        Ok(())
+    }
+
+    pub fn approval_count(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+
+    /// A proposal can only be approved or executed while it hasn't expired
+    /// and the owner set it was approved under is still current
+    pub fn require_live(&self, multisig: &Multisig, now: i64) -> Result<()> {
+        require_gt!(self.expires_at, now, MultisigError::ProposalExpired);
+        require_eq!(
+            self.owner_set_seqno,
+            multisig.owner_set_seqno,
+            MultisigError::StaleProposal
+        );
+        Ok(())
+    }
+}
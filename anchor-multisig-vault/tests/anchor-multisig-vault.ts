@@ -0,0 +1,224 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
+import { AnchorMultisigVault } from "../target/types/anchor_multisig_vault";
+
+describe("anchor-multisig-vault", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace
+    .AnchorMultisigVault as Program<AnchorMultisigVault>;
+
+  const alice = provider.wallet;
+  const bob = Keypair.generate();
+  const carol = Keypair.generate();
+  const outsider = Keypair.generate();
+  const destination = Keypair.generate().publicKey;
+
+  const multisigId = new BN(0);
+  const [multisig] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("multisig"),
+      alice.publicKey.toBuffer(),
+      multisigId.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+  const [vault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("vault"), multisig.toBuffer()],
+    program.programId
+  );
+
+  const proposalPda = (id: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("proposal"),
+        multisig.toBuffer(),
+        new BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const inAnHour = () => new BN(Math.floor(Date.now() / 1000) + 3600);
+
+  const propose = async (action: any, proposer?: Keypair) => {
+    const { proposalCount } = await program.account.multisig.fetch(multisig);
+    const proposal = proposalPda(proposalCount.toNumber());
+    const builder = program.methods
+      .propose(action, inAnHour())
+      .accountsPartial({
+        proposer: proposer ? proposer.publicKey : alice.publicKey,
+        multisig,
+        proposal,
+      });
+    await (proposer ? builder.signers([proposer]) : builder).rpc();
+    return proposal;
+  };
+
+  const approve = (proposal: PublicKey, owner: Keypair) =>
+    program.methods
+      .approve()
+      .accountsPartial({ owner: owner.publicKey, multisig, proposal })
+      .signers([owner])
+      .rpc();
+
+  const execute = (proposal: PublicKey, to: PublicKey | null = null) =>
+    program.methods
+      .execute()
+      .accountsPartial({
+        executor: alice.publicKey,
+        multisig,
+        proposal,
+        destination: to,
+      })
+      .rpc();
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      assert.fail(`Expected ${code}`);
+    } catch (e: any) {
+      assert.include(e.message, code);
+    }
+  };
+
+  before(async () => {
+    for (const kp of [bob, carol, outsider]) {
+      const sig = await provider.connection.requestAirdrop(
+        kp.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+  });
+
+  it("Creates a 2-of-3 multisig and funds its vault", async () => {
+    await program.methods
+      .createMultisig(
+        multisigId,
+        [alice.publicKey, bob.publicKey, carol.publicKey],
+        2
+      )
+      .accounts({ creator: alice.publicKey })
+      .rpc();
+
+    await program.methods
+      .deposit(new BN(2 * LAMPORTS_PER_SOL))
+      .accounts({ depositor: alice.publicKey, multisig })
+      .rpc();
+
+    const account = await program.account.multisig.fetch(multisig);
+    assert.equal(account.threshold, 2);
+    assert.equal(account.owners.length, 3);
+    assert.equal(
+      await provider.connection.getBalance(vault),
+      2 * LAMPORTS_PER_SOL
+    );
+  });
+
+  it("Rejects duplicate owners and out-of-range thresholds", async () => {
+    await expectError(
+      program.methods
+        .createMultisig(new BN(1), [alice.publicKey, alice.publicKey], 1)
+        .accounts({ creator: alice.publicKey })
+        .rpc(),
+      "DuplicateOwner"
+    );
+    await expectError(
+      program.methods
+        .createMultisig(new BN(1), [alice.publicKey, bob.publicKey], 3)
+        .accounts({ creator: alice.publicKey })
+        .rpc(),
+      "InvalidThreshold"
+    );
+  });
+
+  it("Withdraws once the threshold is met", async () => {
+    const amount = new BN(LAMPORTS_PER_SOL / 2);
+    const proposal = await propose({ withdraw: { amount, destination } });
+
+    // The proposer's approval alone is not enough
+    await expectError(execute(proposal, destination), "ThresholdNotMet");
+    await expectError(approve(proposal, outsider), "NotAnOwner");
+
+    await approve(proposal, bob);
+    await expectError(approve(proposal, bob), "AlreadyApproved");
+
+    await expectError(
+      execute(proposal, Keypair.generate().publicKey),
+      "DestinationMismatch"
+    );
+    await execute(proposal, destination);
+
+    assert.equal(
+      await provider.connection.getBalance(destination),
+      amount.toNumber()
+    );
+    assert.isNull(await provider.connection.getAccountInfo(proposal));
+  });
+
+  it("Rejects proposals from non-owners and past expiries", async () => {
+    await expectError(
+      propose({ setThreshold: { threshold: 1 } }, outsider),
+      "NotAnOwner"
+    );
+    await expectError(
+      program.methods
+        .propose(
+          { setThreshold: { threshold: 1 } },
+          new BN(Math.floor(Date.now() / 1000) - 60)
+        )
+        .accountsPartial({
+          proposer: alice.publicKey,
+          multisig,
+          proposal: proposalPda(
+            (
+              await program.account.multisig.fetch(multisig)
+            ).proposalCount.toNumber()
+          ),
+        })
+        .rpc(),
+      "InvalidExpiry"
+    );
+  });
+
+  it("Changes the threshold and invalidates pending proposals", async () => {
+    const pending = await propose({
+      withdraw: { amount: new BN(1_000), destination },
+    });
+
+    const change = await propose({ setThreshold: { threshold: 3 } });
+    await approve(change, carol);
+    await execute(change);
+
+    assert.equal((await program.account.multisig.fetch(multisig)).threshold, 3);
+    await expectError(approve(pending, bob), "StaleProposal");
+
+    await program.methods
+      .cancelProposal()
+      .accountsPartial({ proposer: alice.publicKey, multisig, proposal: pending })
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(pending));
+  });
+
+  it("Replaces the owner set through a proposal", async () => {
+    const newOwners = [alice.publicKey, bob.publicKey, outsider.publicKey];
+    const change = await propose({ setOwners: { owners: newOwners } });
+    await approve(change, bob);
+    await approve(change, carol);
+    await execute(change);
+
+    const account = await program.account.multisig.fetch(multisig);
+    assert.deepEqual(
+      account.owners.map((owner) => owner.toBase58()),
+      newOwners.map((owner) => owner.toBase58())
+    );
+
+    await expectError(
+      propose({ setThreshold: { threshold: 1 } }, carol),
+      "NotAnOwner"
+    );
+  });
+});
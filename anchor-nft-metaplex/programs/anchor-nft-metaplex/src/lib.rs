@@ -1,10 +1,16 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
     ID as MPL_CORE_PROGRAM_ID,
+    accounts::{BaseAssetV1, BaseCollectionV1},
     instructions::{
         CreateV2CpiBuilder, CreateCollectionV2CpiBuilder,
         TransferV1CpiBuilder, UpdateV2CpiBuilder, BurnV1CpiBuilder,
-    }
+        AddPluginV1CpiBuilder, AddCollectionPluginV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{
+        Attribute, Attributes, Creator, FreezeDelegate, Plugin, PluginAuthority, Royalties,
+        RuleSet, UpdateAuthority,
+    },
 };
 
 declare_id!("4AgaC13ZKg8PhS2LU8C3EsxyrhHyvdcGJgvqiUTkdwwS");
@@ -83,6 +89,122 @@ pub mod anchor_nft_metaplex {
             .invoke()?;
         Ok(())
     }
+
+    /// Royalties set on a collection apply to every asset in it that
+    /// doesn't carry its own
+    pub fn add_collection_royalties(
+        ctx: Context<AddCollectionPlugin>,
+        args: RoyaltiesArgs,
+    ) -> Result<()> {
+        args.validate()?;
+        add_collection_plugin(&ctx, Plugin::Royalties(args.into()))
+    }
+
+    pub fn add_asset_royalties(
+        ctx: Context<AddAssetPlugin>,
+        args: RoyaltiesArgs,
+    ) -> Result<()> {
+        args.validate()?;
+        add_asset_plugin(&ctx, Plugin::Royalties(args.into()), None)
+    }
+
+    pub fn add_collection_attributes(
+        ctx: Context<AddCollectionPlugin>,
+        args: AttributesArgs,
+    ) -> Result<()> {
+        add_collection_plugin(&ctx, Plugin::Attributes(args.into()))
+    }
+
+    pub fn add_asset_attributes(
+        ctx: Context<AddAssetPlugin>,
+        args: AttributesArgs,
+    ) -> Result<()> {
+        add_asset_plugin(&ctx, Plugin::Attributes(args.into()), None)
+    }
+
+    /// FreezeDelegate is owner-managed: `authority` must be the asset's
+    /// owner, and `delegate` becomes the only key that can freeze or thaw it
+    pub fn add_freeze_delegate(ctx: Context<AddAssetPlugin>, delegate: Pubkey) -> Result<()> {
+        add_asset_plugin(
+            &ctx,
+            Plugin::FreezeDelegate(FreezeDelegate { frozen: false }),
+            Some(PluginAuthority::Address { address: delegate }),
+        )
+    }
+
+    /// A frozen asset can't be transferred or burned; `authority` must be
+    /// the freeze delegate
+    pub fn set_frozen(ctx: Context<UpdateAssetPlugin>, frozen: bool) -> Result<()> {
+        let collection = ctx.accounts.collection.as_ref().map(|c| c.to_account_info());
+        UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(collection.as_ref())
+            .payer(&ctx.accounts.payer.to_account_info())
+            .authority(Some(&ctx.accounts.authority.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen }))
+            .invoke()?;
+        Ok(())
+    }
+}
+
+fn add_collection_plugin(ctx: &Context<AddCollectionPlugin>, plugin: Plugin) -> Result<()> {
+    AddCollectionPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .collection(&ctx.accounts.collection.to_account_info())
+        .payer(&ctx.accounts.payer.to_account_info())
+        .authority(Some(&ctx.accounts.authority.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .plugin(plugin)
+        .invoke()?;
+    Ok(())
+}
+
+/// Without `init_authority` mpl_core gives the plugin its default authority:
+/// the update authority for Royalties and Attributes
+fn add_asset_plugin(
+    ctx: &Context<AddAssetPlugin>,
+    plugin: Plugin,
+    init_authority: Option<PluginAuthority>,
+) -> Result<()> {
+    let mpl_core_program = ctx.accounts.mpl_core_program.to_account_info();
+    let asset = ctx.accounts.asset.to_account_info();
+    let collection = ctx.accounts.collection.as_ref().map(|c| c.to_account_info());
+    let payer = ctx.accounts.payer.to_account_info();
+    let authority = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+
+    let mut builder = AddPluginV1CpiBuilder::new(&mpl_core_program);
+    builder
+        .asset(&asset)
+        .collection(collection.as_ref())
+        .payer(&payer)
+        .authority(Some(&authority))
+        .system_program(&system_program)
+        .plugin(plugin);
+    if let Some(init_authority) = init_authority {
+        builder.init_authority(init_authority);
+    }
+    builder.invoke()?;
+    Ok(())
+}
+
+/// The collection an asset belongs to, if any; mpl_core records it as the
+/// asset's update authority
+fn asset_collection(asset: &BaseAssetV1) -> Option<Pubkey> {
+    match asset.update_authority {
+        UpdateAuthority::Collection(collection) => Some(collection),
+        _ => None,
+    }
+}
+
+#[error_code]
+pub enum NftError {
+    #[msg("Royalties can't exceed 10000 basis points")]
+    InvalidRoyaltyBasisPoints,
+    #[msg("Creator percentages must add up to 100")]
+    InvalidCreatorShares,
+    #[msg("Collection doesn't match the asset's collection")]
+    CollectionMismatch,
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
@@ -103,6 +225,80 @@ pub struct UpdateAssetArgs {
     pub new_uri: String,
 }
 
+/// Mirrors mpl_core's `Royalties`, which can't appear in this program's IDL
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct RoyaltiesArgs {
+    pub basis_points: u16,
+    pub creators: Vec<CreatorArgs>,
+    pub rule_set: RuleSetArgs,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct CreatorArgs {
+    pub address: Pubkey,
+    pub percentage: u8,
+}
+
+/// Programs allowed, or denied, to own the authority and new owner of a
+/// transfer. A marketplace that pays royalties can be allow-listed so
+/// trades through anything else are rejected.
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub enum RuleSetArgs {
+    None,
+    ProgramAllowList { programs: Vec<Pubkey> },
+    ProgramDenyList { programs: Vec<Pubkey> },
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AttributesArgs {
+    pub attributes: Vec<AttributeArgs>,
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize)]
+pub struct AttributeArgs {
+    pub key: String,
+    pub value: String,
+}
+
+impl RoyaltiesArgs {
+    fn validate(&self) -> Result<()> {
+        require!(self.basis_points <= 10_000, NftError::InvalidRoyaltyBasisPoints);
+        let shares: u16 = self.creators.iter().map(|c| c.percentage as u16).sum();
+        require!(shares == 100, NftError::InvalidCreatorShares);
+        Ok(())
+    }
+}
+
+impl From<RoyaltiesArgs> for Royalties {
+    fn from(args: RoyaltiesArgs) -> Self {
+        Royalties {
+            basis_points: args.basis_points,
+            creators: args
+                .creators
+                .into_iter()
+                .map(|c| Creator { address: c.address, percentage: c.percentage })
+                .collect(),
+            rule_set: match args.rule_set {
+                RuleSetArgs::None => RuleSet::None,
+                RuleSetArgs::ProgramAllowList { programs } => RuleSet::ProgramAllowList(programs),
+                RuleSetArgs::ProgramDenyList { programs } => RuleSet::ProgramDenyList(programs),
+            },
+        }
+    }
+}
+
+impl From<AttributesArgs> for Attributes {
+    fn from(args: AttributesArgs) -> Self {
+        Attributes {
+            attribute_list: args
+                .attributes
+                .into_iter()
+                .map(|a| Attribute { key: a.key, value: a.value })
+                .collect(),
+        }
+    }
+}
+
 #[derive(Accounts)]
 pub struct CreateCollection<'info> {
     #[account(mut)]
@@ -185,4 +381,57 @@ pub struct BurnAsset<'info> {
     #[account(address = MPL_CORE_PROGRAM_ID)]
     /// CHECK: address constraint
     pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddCollectionPlugin<'info> {
+    #[account(mut)]
+    pub collection: Account<'info, BaseCollectionV1>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(address = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: address constraint
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddAssetPlugin<'info> {
+    #[account(
+        mut,
+        constraint = asset_collection(&asset) == collection.as_ref().map(|c| c.key())
+            @ NftError::CollectionMismatch
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+    /// Required exactly when the asset belongs to a collection
+    #[account(mut)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(address = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: address constraint
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAssetPlugin<'info> {
+    #[account(
+        mut,
+        constraint = asset_collection(&asset) == collection.as_ref().map(|c| c.key())
+            @ NftError::CollectionMismatch
+    )]
+    pub asset: Account<'info, BaseAssetV1>,
+    /// Required exactly when the asset belongs to a collection
+    #[account(mut)]
+    pub collection: Option<Account<'info, BaseCollectionV1>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(address = MPL_CORE_PROGRAM_ID)]
+    /// CHECK: address constraint
+    pub mpl_core_program: UncheckedAccount<'info>,
 }
\ No newline at end of file
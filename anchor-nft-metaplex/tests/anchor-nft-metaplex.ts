@@ -177,6 +177,200 @@ describe("anchor-nft-metaplex", () => {
     });
   });
 
+  describe("Plugins", () => {
+    const pluginCollection = Keypair.generate();
+    const pluginAsset = Keypair.generate();
+    const creator = Keypair.generate();
+
+    const assetPluginAccounts = () => ({
+      asset: pluginAsset.publicKey,
+      collection: pluginCollection.publicKey,
+      payer: wallet.publicKey,
+      authority: wallet.publicKey,
+      systemProgram: SystemProgram.programId,
+      mplCoreProgram: MPL_CORE_PROGRAM_ID,
+    });
+
+    const transferPluginAsset = (from: Keypair, to: anchor.web3.PublicKey) =>
+      program.methods
+        .transferAsset()
+        .accountsPartial({
+          asset: pluginAsset.publicKey,
+          collection: pluginCollection.publicKey,
+          payer: from.publicKey,
+          authority: from.publicKey,
+          newOwner: to,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([from])
+        .rpc();
+
+    const accountData = async (address: anchor.web3.PublicKey) =>
+      (await provider.connection.getAccountInfo(address)).data;
+
+    before(async () => {
+      await program.methods
+        .createCollection({
+          name: "Plugin Collection",
+          uri: "https://example.com/plugin-collection.json",
+        })
+        .accountsPartial({
+          collection: pluginCollection.publicKey,
+          updateAuthority: wallet.publicKey,
+          payer: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([pluginCollection])
+        .rpc();
+
+      await program.methods
+        .createAsset({
+          name: "Plugin NFT",
+          uri: "https://example.com/plugin-nft.json",
+        })
+        .accountsPartial({
+          asset: pluginAsset.publicKey,
+          collection: pluginCollection.publicKey,
+          authority: wallet.publicKey,
+          payer: wallet.publicKey,
+          owner: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([pluginAsset])
+        .rpc();
+    });
+
+    it("rejects creator shares that don't add up to 100", async () => {
+      try {
+        await program.methods
+          .addCollectionRoyalties({
+            basisPoints: 500,
+            creators: [{ address: creator.publicKey, percentage: 90 }],
+            ruleSet: { none: {} },
+          })
+          .accountsPartial({
+            collection: pluginCollection.publicKey,
+            payer: wallet.publicKey,
+            authority: wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+            mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("InvalidCreatorShares");
+      }
+    });
+
+    it("adds royalties with a program allow list", async () => {
+      // Wallets are owned by the system program, so transfers between them
+      // stay allowed
+      await program.methods
+        .addCollectionRoyalties({
+          basisPoints: 500,
+          creators: [{ address: creator.publicKey, percentage: 100 }],
+          ruleSet: {
+            programAllowList: {
+              programs: [SystemProgram.programId, program.programId],
+            },
+          },
+        })
+        .accountsPartial({
+          collection: pluginCollection.publicKey,
+          payer: wallet.publicKey,
+          authority: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .rpc();
+
+      const data = await accountData(pluginCollection.publicKey);
+      expect(data.includes(creator.publicKey.toBuffer())).to.be.true;
+      expect(data.includes(program.programId.toBuffer())).to.be.true;
+    });
+
+    it("adds readable attributes to the asset", async () => {
+      await program.methods
+        .addAssetAttributes({
+          attributes: [
+            { key: "Background", value: "Blue" },
+            { key: "Rarity", value: "Legendary" },
+          ],
+        })
+        .accountsPartial(assetPluginAccounts())
+        .rpc();
+
+      const data = await accountData(pluginAsset.publicKey);
+      expect(data.includes(Buffer.from("Background"))).to.be.true;
+      expect(data.includes(Buffer.from("Legendary"))).to.be.true;
+    });
+
+    it("rejects a collection the asset isn't in", async () => {
+      const otherCollection = Keypair.generate();
+      await program.methods
+        .createCollection({
+          name: "Other Collection",
+          uri: "https://example.com/other-collection.json",
+        })
+        .accountsPartial({
+          collection: otherCollection.publicKey,
+          updateAuthority: wallet.publicKey,
+          payer: wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([otherCollection])
+        .rpc();
+
+      // Neither another collection nor none at all matches the asset's
+      for (const collection of [otherCollection.publicKey, null]) {
+        try {
+          await program.methods
+            .addAssetAttributes({
+              attributes: [{ key: "Background", value: "Red" }],
+            })
+            .accountsPartial({ ...assetPluginAccounts(), collection })
+            .rpc();
+          expect.fail("Should have thrown");
+        } catch (e: any) {
+          expect(e.message).to.include("CollectionMismatch");
+        }
+      }
+    });
+
+    it("blocks transfers while the asset is frozen", async () => {
+      await program.methods
+        .addFreezeDelegate(wallet.publicKey)
+        .accountsPartial(assetPluginAccounts())
+        .rpc();
+      await program.methods
+        .setFrozen(true)
+        .accountsPartial(assetPluginAccounts())
+        .rpc();
+
+      let transferred = true;
+      try {
+        await transferPluginAsset(wallet.payer, recipient.publicKey);
+      } catch {
+        transferred = false;
+      }
+      expect(transferred).to.be.false;
+      // The owner follows the one-byte account key
+      const owner = (await accountData(pluginAsset.publicKey)).subarray(1, 33);
+      expect(owner.equals(wallet.publicKey.toBuffer())).to.be.true;
+
+      await program.methods
+        .setFrozen(false)
+        .accountsPartial(assetPluginAccounts())
+        .rpc();
+      await transferPluginAsset(wallet.payer, recipient.publicKey);
+      await transferPluginAsset(recipient, wallet.publicKey);
+    });
+  });
+
   describe("Full Flow", () => {
     it("runs complete flow: collection -> asset -> transfer -> update -> burn", async () => {
       const newCollection = Keypair.generate();
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Insufficient balance for withdrawal")]
+    InsufficientBalance,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Vault state belongs to another user")]
+    Unauthorized,
+    #[msg("Deposit is too small to mint any shares")]
+    DepositTooSmall,
+    #[msg("Not enough shares to redeem")]
+    InsufficientShares,
+    #[msg("Only the pool's strategy can report yield")]
+    UnauthorizedStrategy,
+    #[msg("Yield can't be reported while no shares are outstanding")]
+    EmptyPool,
+    #[msg("Lock schedule must not end before it starts")]
+    InvalidSchedule,
+    #[msg("Nothing has vested yet")]
+    NothingVested,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DepositEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolDepositEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolWithdrawEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldReportedEvent {
+    pub strategy: Pubkey,
+    pub amount: u64,
+    pub total_assets: u64,
+    pub share_supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LockedDepositEvent {
+    pub depositor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub start: i64,
+    pub end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedWithdrawEvent {
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::VaultError;
+use crate::events::DepositEvent;
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = VaultState::SIZE,
+        seeds = [b"vault_state", user.key().as_ref()],
+        bump
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        // Check if amount is greater than 0
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        // Transfer SOL from user to vault
+        let cpi_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.user.to_account_info(),
+                to: self.vault.to_account_info(),
+            },
+        );
+
+        system_program::transfer(cpi_context, amount)?;
+
+        // The owner is recorded once, when the state account is created;
+        // later deposits must come from that same owner
+        let vault = &mut self.vault_state;
+        if vault.owner == Pubkey::default() {
+            vault.owner = self.user.key();
+        }
+        require_keys_eq!(vault.owner, self.user.key(), VaultError::Unauthorized);
+
+        // Update user's balance in vault account
+        vault.balance = vault
+            .balance
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(DepositEvent {
+            user: self.user.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::VaultError;
+use crate::events::LockedDepositEvent;
+use crate::state::{LockSchedule, LockedDeposit};
+
+#[derive(Accounts)]
+pub struct DepositLocked<'info> {
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    /// CHECK: Any address may receive a grant; may be the depositor
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = depositor,
+        space = 8 + LockedDeposit::INIT_SPACE,
+        seeds = [
+            LockedDeposit::SEED,
+            depositor.key().as_ref(),
+            beneficiary.key().as_ref()
+        ],
+        bump
+    )]
+    pub lock: Account<'info, LockedDeposit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositLocked<'info> {
+    pub fn deposit_locked(
+        &mut self,
+        amount: u64,
+        schedule: LockSchedule,
+        bumps: &DepositLockedBumps,
+    ) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        schedule.validate()?;
+
+        // Transfer SOL from depositor to vault
+        let cpi_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.depositor.to_account_info(),
+                to: self.vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, amount)?;
+
+        self.lock.set_inner(LockedDeposit {
+            depositor: self.depositor.key(),
+            beneficiary: self.beneficiary.key(),
+            schedule,
+            total: amount,
+            withdrawn: 0,
+            bump: bumps.lock,
+        });
+
+        emit!(LockedDepositEvent {
+            depositor: self.depositor.key(),
+            beneficiary: self.beneficiary.key(),
+            amount,
+            start: schedule.start,
+            end: schedule.end,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [Pool::SEED],
+        bump
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [Pool::VAULT_SEED],
+        bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [Pool::SHARE_MINT_SEED],
+        bump,
+        mint::decimals = Pool::SHARE_DECIMALS,
+        mint::authority = pool,
+        mint::token_program = token_program,
+    )]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePool<'info> {
+    pub fn initialize_pool(&mut self, strategy: Pubkey, bumps: &InitializePoolBumps) -> Result<()> {
+        self.pool.set_inner(Pool {
+            authority: self.authority.key(),
+            strategy,
+            share_mint: self.share_mint.key(),
+            total_assets: 0,
+            bump: bumps.pool,
+            vault_bump: bumps.pool_vault,
+            share_mint_bump: bumps.share_mint,
+        });
+
+        // Seed the vault with its rent-exempt minimum so partial withdrawals
+        // never leave it below rent exemption. This isn't part of total_assets.
+        let rent = Rent::get()?.minimum_balance(0);
+        let missing = rent.saturating_sub(self.pool_vault.lamports());
+        if missing > 0 {
+            let cpi_context = CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.authority.to_account_info(),
+                    to: self.pool_vault.to_account_info(),
+                },
+            );
+            system_program::transfer(cpi_context, missing)?;
+        }
+
+        msg!("Pool initialized with strategy {}", strategy);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+pub mod deposit;
+pub mod deposit_locked;
+pub mod initialize_pool;
+pub mod pool_deposit;
+pub mod pool_withdraw;
+pub mod report_yield;
+pub mod set_strategy;
+pub mod withdraw;
+pub mod withdraw_vested;
+
+pub use deposit::*;
+pub use deposit_locked::*;
+pub use initialize_pool::*;
+pub use pool_deposit::*;
+pub use pool_withdraw::*;
+pub use report_yield::*;
+pub use set_strategy::*;
+pub use withdraw::*;
+pub use withdraw_vested::*;
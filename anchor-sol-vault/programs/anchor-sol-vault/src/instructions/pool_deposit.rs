@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::PoolDepositEvent;
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct PoolDeposit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED],
+        bump = pool.bump,
+        has_one = share_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [Pool::VAULT_SEED],
+        bump = pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = share_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PoolDeposit<'info> {
+    pub fn pool_deposit(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        // 1. Price the deposit before it changes the pool
+        let shares = self
+            .pool
+            .shares_for_deposit(amount, self.share_mint.supply)?;
+        require!(shares > 0, VaultError::DepositTooSmall);
+
+        // 2. Move the SOL into the pool vault
+        let cpi_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.user.to_account_info(),
+                to: self.pool_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, amount)?;
+
+        // 3. Mint the shares, signed by the pool
+        let signer_seeds: &[&[&[u8]]] = &[&[Pool::SEED, &[self.pool.bump]]];
+        let cpi_context = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            MintTo {
+                mint: self.share_mint.to_account_info(),
+                to: self.user_shares.to_account_info(),
+                authority: self.pool.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token_interface::mint_to(cpi_context, shares)?;
+
+        // 4. Account for the new assets
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(PoolDepositEvent {
+            user: self.user.key(),
+            amount,
+            shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
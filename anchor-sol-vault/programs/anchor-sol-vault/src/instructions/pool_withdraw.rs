@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::events::PoolWithdrawEvent;
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct PoolWithdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED],
+        bump = pool.bump,
+        has_one = share_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [Pool::VAULT_SEED],
+        bump = pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = share_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_shares: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PoolWithdraw<'info> {
+    pub fn pool_withdraw(&mut self, shares: u64) -> Result<()> {
+        require!(shares > 0, VaultError::InvalidAmount);
+        require!(
+            self.user_shares.amount >= shares,
+            VaultError::InsufficientShares
+        );
+
+        // 1. Price the redemption at the current exchange rate
+        let amount = self
+            .pool
+            .assets_for_shares(shares, self.share_mint.supply)?;
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        // 2. Burn the shares
+        let cpi_context = CpiContext::new(
+            self.token_program.to_account_info(),
+            Burn {
+                mint: self.share_mint.to_account_info(),
+                from: self.user_shares.to_account_info(),
+                authority: self.user.to_account_info(),
+            },
+        );
+        token_interface::burn(cpi_context, shares)?;
+
+        // 3. Pay out the SOL, signed by the pool vault
+        let signer_seeds: &[&[&[u8]]] = &[&[Pool::VAULT_SEED, &[self.pool.vault_bump]]];
+        let cpi_context = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.pool_vault.to_account_info(),
+                to: self.user.to_account_info(),
+            },
+            signer_seeds,
+        );
+        system_program::transfer(cpi_context, amount)?;
+
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_sub(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(PoolWithdrawEvent {
+            user: self.user.key(),
+            amount,
+            shares,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+use crate::error::VaultError;
+use crate::events::YieldReportedEvent;
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct ReportYield<'info> {
+    #[account(mut)]
+    pub strategy: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED],
+        bump = pool.bump,
+        has_one = strategy @ VaultError::UnauthorizedStrategy,
+        has_one = share_mint
+    )]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [Pool::VAULT_SEED],
+        bump = pool.vault_bump
+    )]
+    pub pool_vault: SystemAccount<'info>,
+
+    pub share_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReportYield<'info> {
+    /// The strategy returns `amount` lamports of earnings to the vault. No
+    /// shares are minted, so every share is worth proportionally more.
+    pub fn report_yield(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, VaultError::InvalidAmount);
+        // Yield landing in an empty pool would be captured by the next depositor
+        require!(self.share_mint.supply > 0, VaultError::EmptyPool);
+
+        let cpi_context = CpiContext::new(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.strategy.to_account_info(),
+                to: self.pool_vault.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, amount)?;
+
+        self.pool.total_assets = self
+            .pool
+            .total_assets
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(YieldReportedEvent {
+            strategy: self.strategy.key(),
+            amount,
+            total_assets: self.pool.total_assets,
+            share_supply: self.share_mint.supply,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
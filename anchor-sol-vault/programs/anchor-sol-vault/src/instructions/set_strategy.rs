@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Pool;
+
+#[derive(Accounts)]
+pub struct SetStrategy<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Pool::SEED],
+        bump = pool.bump,
+        has_one = authority
+    )]
+    pub pool: Account<'info, Pool>,
+}
+
+impl<'info> SetStrategy<'info> {
+    pub fn set_strategy(&mut self, strategy: Pubkey) -> Result<()> {
+        self.pool.strategy = strategy;
+
+        msg!("Pool strategy set to {}", strategy);
+
+        Ok(())
+    }
+}
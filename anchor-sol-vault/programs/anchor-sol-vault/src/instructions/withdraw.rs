@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::VaultError;
+use crate::events::WithdrawEvent;
+use crate::state::VaultState;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault_state", user.key().as_ref()],
+        bump,
+        constraint = vault_state.owner == user.key()
+    )]
+    pub vault_state: Account<'info, VaultState>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    pub fn withdraw(&mut self, amount: u64, bumps: &WithdrawBumps) -> Result<()> {
+        // Check if amount is greater than 0
+        require!(amount > 0, VaultError::InvalidAmount);
+
+        let vault = &mut self.vault_state;
+
+        // Check if user has sufficient balance
+        require!(vault.balance >= amount, VaultError::InsufficientBalance);
+
+        // Create the seeds array first to extend its lifetime
+        let bump_seed = bumps.vault;
+        let seeds: &[&[&[u8]]] = &[&[b"vault", &[bump_seed]]];
+
+        // Transfer SOL from vault to user
+        let cpi_context = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.vault.to_account_info(),
+                to: self.user.to_account_info(),
+            },
+            seeds,
+        );
+
+        system_program::transfer(cpi_context, amount)?;
+
+        // Update user's balance
+        vault.balance = vault
+            .balance
+            .checked_sub(amount)
+            .ok_or(VaultError::Overflow)?;
+
+        emit!(WithdrawEvent {
+            user: self.user.key(),
+            amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::error::VaultError;
+use crate::events::VestedWithdrawEvent;
+use crate::state::LockedDeposit;
+
+#[derive(Accounts)]
+pub struct WithdrawVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// CHECK: Receives the lock's rent once it is fully withdrawn
+    #[account(mut)]
+    pub depositor: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            LockedDeposit::SEED,
+            depositor.key().as_ref(),
+            beneficiary.key().as_ref()
+        ],
+        bump = lock.bump,
+        has_one = depositor,
+        has_one = beneficiary
+    )]
+    pub lock: Account<'info, LockedDeposit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawVested<'info> {
+    /// Withdraw everything vested so far
+    pub fn withdraw_vested(&mut self, bumps: &WithdrawVestedBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self.lock.withdrawable(now);
+        require!(amount > 0, VaultError::NothingVested);
+
+        // Transfer SOL from vault to beneficiary
+        let seeds: &[&[&[u8]]] = &[&[b"vault", &[bumps.vault]]];
+        let cpi_context = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            system_program::Transfer {
+                from: self.vault.to_account_info(),
+                to: self.beneficiary.to_account_info(),
+            },
+            seeds,
+        );
+        system_program::transfer(cpi_context, amount)?;
+
+        let lock = &mut self.lock;
+        lock.withdrawn += amount;
+        let remaining = lock.total - lock.withdrawn;
+
+        emit!(VestedWithdrawEvent {
+            beneficiary: self.beneficiary.key(),
+            amount,
+            remaining,
+            timestamp: now,
+        });
+
+        // Fully withdrawn: return the lock's rent to the depositor
+        if remaining == 0 {
+            self.lock.close(self.depositor.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+#[account]
+pub struct VaultState {
+    pub owner: Pubkey,
+    pub balance: u64,
+}
+
+impl VaultState {
+    pub const SIZE: usize = 8 + 32 + 8; // discriminator + pubkey + u64
+}
+
+/// Pooled SOL vault whose depositors hold share tokens
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub authority: Pubkey,
+    /// The only signer allowed to report yield
+    pub strategy: Pubkey,
+    pub share_mint: Pubkey,
+    /// Lamports owed to shareholders: deposits and reported yield, less
+    /// withdrawals. Tracked here rather than read from the vault balance so
+    /// that stray transfers into the vault can't move the share price.
+    pub total_assets: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub share_mint_bump: u8,
+}
+
+impl Pool {
+    pub const SEED: &'static [u8] = b"pool";
+    pub const VAULT_SEED: &'static [u8] = b"pool_vault";
+    pub const SHARE_MINT_SEED: &'static [u8] = b"share_mint";
+    /// Shares use the same precision as lamports
+    pub const SHARE_DECIMALS: u8 = 9;
+
+    /// Shares minted for depositing `amount` lamports, rounded down.
+    /// An empty pool mints shares 1:1.
+    pub fn shares_for_deposit(&self, amount: u64, share_supply: u64) -> Result<u64> {
+        if share_supply == 0 || self.total_assets == 0 {
+            return Ok(amount);
+        }
+        mul_div(amount, share_supply, self.total_assets)
+    }
+
+    /// Lamports paid out for burning `shares`, rounded down
+    pub fn assets_for_shares(&self, shares: u64, share_supply: u64) -> Result<u64> {
+        require!(share_supply > 0, VaultError::InsufficientShares);
+        mul_div(shares, self.total_assets, share_supply)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockKind {
+    /// Everything unlocks at `end`
+    Cliff,
+    /// Unlocks linearly from `start` to `end`
+    Linear,
+}
+
+/// When locked funds become withdrawable
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug)]
+pub struct LockSchedule {
+    pub kind: LockKind,
+    pub start: i64,
+    pub end: i64,
+}
+
+impl LockSchedule {
+    pub fn validate(&self) -> Result<()> {
+        match self.kind {
+            LockKind::Cliff => require!(self.start <= self.end, VaultError::InvalidSchedule),
+            LockKind::Linear => require!(self.start < self.end, VaultError::InvalidSchedule),
+        }
+        Ok(())
+    }
+
+    /// How much of `total` has unlocked at `now`
+    pub fn vested(&self, total: u64, now: i64) -> u64 {
+        if now >= self.end {
+            return total;
+        }
+        match self.kind {
+            LockKind::Cliff => 0,
+            LockKind::Linear if now <= self.start => 0,
+            LockKind::Linear => {
+                // In i128 so extreme timestamps can't overflow the subtraction
+                let elapsed = (now as i128 - self.start as i128) as u128;
+                let duration = (self.end as i128 - self.start as i128) as u128;
+                (total as u128 * elapsed / duration) as u64
+            }
+        }
+    }
+}
+
+/// SOL held in the vault for `beneficiary` until it vests
+#[account]
+#[derive(InitSpace)]
+pub struct LockedDeposit {
+    pub depositor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub schedule: LockSchedule,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl LockedDeposit {
+    pub const SEED: &'static [u8] = b"lock";
+
+    /// Vested lamports not yet withdrawn
+    pub fn withdrawable(&self, now: i64) -> u64 {
+        self.schedule.vested(self.total, now) - self.withdrawn
+    }
+}
+
+fn mul_div(value: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result = value as u128 * numerator as u128 / denominator as u128;
+    u64::try_from(result).map_err(|_| error!(VaultError::Overflow))
+}
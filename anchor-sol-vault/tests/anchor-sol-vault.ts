@@ -1,7 +1,13 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AnchorSolVault } from "../target/types/anchor_sol_vault";
-import { PublicKey, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { PublicKey, LAMPORTS_PER_SOL, SystemProgram, Keypair } from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import { expect } from "chai";
 
 describe("sol-vault", () => {
@@ -219,4 +225,279 @@ describe("sol-vault", () => {
       }
     });
   });
+
+  describe("pool", () => {
+    const strategy = Keypair.generate();
+    const alice = Keypair.generate();
+    const bob = Keypair.generate();
+
+    const [poolPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool")],
+      program.programId
+    );
+    const [poolVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_vault")],
+      program.programId
+    );
+    const [shareMintPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint")],
+      program.programId
+    );
+    const sharesOf = (owner: PublicKey) =>
+      getAssociatedTokenAddressSync(shareMintPDA, owner);
+
+    const poolDeposit = (depositor: Keypair, amount: number) =>
+      program.methods
+        .poolDeposit(new anchor.BN(amount))
+        .accountsStrict({
+          user: depositor.publicKey,
+          pool: poolPDA,
+          poolVault: poolVaultPDA,
+          shareMint: shareMintPDA,
+          userShares: sharesOf(depositor.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([depositor])
+        .rpc();
+
+    const reportYield = (reporter: Keypair, amount: number) =>
+      program.methods
+        .reportYield(new anchor.BN(amount))
+        .accountsStrict({
+          strategy: reporter.publicKey,
+          pool: poolPDA,
+          poolVault: poolVaultPDA,
+          shareMint: shareMintPDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([reporter])
+        .rpc();
+
+    const shareBalance = async (owner: PublicKey) =>
+      Number((await getAccount(provider.connection, sharesOf(owner))).amount);
+
+    before(async () => {
+      for (const account of [strategy, alice, bob]) {
+        const signature = await provider.connection.requestAirdrop(
+          account.publicKey,
+          5 * LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(signature);
+      }
+
+      await program.methods
+        .initializePool(strategy.publicKey)
+        .accountsStrict({
+          authority: provider.wallet.publicKey,
+          pool: poolPDA,
+          poolVault: poolVaultPDA,
+          shareMint: shareMintPDA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
+    it("mints shares 1:1 into an empty pool", async () => {
+      await poolDeposit(alice, LAMPORTS_PER_SOL);
+
+      const pool = await program.account.pool.fetch(poolPDA);
+      expect(pool.totalAssets.toNumber()).to.equal(LAMPORTS_PER_SOL);
+      expect(await shareBalance(alice.publicKey)).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("only the strategy can report yield", async () => {
+      try {
+        await reportYield(bob, LAMPORTS_PER_SOL / 2);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.toString()).to.include("UnauthorizedStrategy");
+      }
+    });
+
+    it("reported yield raises the share price for later depositors", async () => {
+      await reportYield(strategy, LAMPORTS_PER_SOL / 2);
+
+      // 1.5 SOL backs 1 share-SOL, so 1.5 SOL buys another 1 share-SOL
+      await poolDeposit(bob, (3 * LAMPORTS_PER_SOL) / 2);
+
+      const pool = await program.account.pool.fetch(poolPDA);
+      expect(pool.totalAssets.toNumber()).to.equal(3 * LAMPORTS_PER_SOL);
+      expect(await shareBalance(bob.publicKey)).to.equal(LAMPORTS_PER_SOL);
+    });
+
+    it("redeems shares at the current exchange rate", async () => {
+      const before = await provider.connection.getBalance(alice.publicKey);
+
+      await program.methods
+        .poolWithdraw(new anchor.BN(LAMPORTS_PER_SOL))
+        .accountsStrict({
+          user: alice.publicKey,
+          pool: poolPDA,
+          poolVault: poolVaultPDA,
+          shareMint: shareMintPDA,
+          userShares: sharesOf(alice.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([alice])
+        .rpc();
+
+      const after = await provider.connection.getBalance(alice.publicKey);
+      expect(after - before).to.be.approximately(
+        (3 * LAMPORTS_PER_SOL) / 2,
+        1000000 // Allow for transaction fees
+      );
+      expect(await shareBalance(alice.publicKey)).to.equal(0);
+
+      const pool = await program.account.pool.fetch(poolPDA);
+      expect(pool.totalAssets.toNumber()).to.equal((3 * LAMPORTS_PER_SOL) / 2);
+    });
+  });
+
+  describe("locked deposits", () => {
+    const employer = Keypair.generate();
+    const employee = Keypair.generate();
+
+    const lockPda = (depositor: PublicKey, beneficiary: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("lock"), depositor.toBuffer(), beneficiary.toBuffer()],
+        program.programId
+      )[0];
+
+    const depositLocked = (
+      depositor: Keypair,
+      beneficiary: PublicKey,
+      amount: number,
+      schedule: { kind: any; start: number; end: number }
+    ) =>
+      program.methods
+        .depositLocked(new anchor.BN(amount), {
+          kind: schedule.kind,
+          start: new anchor.BN(schedule.start),
+          end: new anchor.BN(schedule.end),
+        })
+        .accountsStrict({
+          depositor: depositor.publicKey,
+          beneficiary,
+          vault: vaultPDA,
+          lock: lockPda(depositor.publicKey, beneficiary),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([depositor])
+        .rpc();
+
+    const withdrawVested = (beneficiary: Keypair, depositor: PublicKey) =>
+      program.methods
+        .withdrawVested()
+        .accountsStrict({
+          beneficiary: beneficiary.publicKey,
+          depositor,
+          vault: vaultPDA,
+          lock: lockPda(depositor, beneficiary.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([beneficiary])
+        .rpc();
+
+    const now = () => Math.floor(Date.now() / 1000);
+
+    before(async () => {
+      for (const account of [employer, employee]) {
+        const signature = await provider.connection.requestAirdrop(
+          account.publicKey,
+          5 * LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(signature);
+      }
+    });
+
+    it("a cliff lock can't be withdrawn before it unlocks", async () => {
+      await depositLocked(employer, employee.publicKey, LAMPORTS_PER_SOL, {
+        kind: { cliff: {} },
+        start: now(),
+        end: now() + 3600,
+      });
+
+      try {
+        await withdrawVested(employee, employer.publicKey);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.toString()).to.include("NothingVested");
+      }
+    });
+
+    it("a linear lock pays out what has vested and keeps the rest", async () => {
+      const depositor = Keypair.generate();
+      const signature = await provider.connection.requestAirdrop(
+        depositor.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+
+      // Halfway through a two-hour vest
+      await depositLocked(depositor, employee.publicKey, LAMPORTS_PER_SOL, {
+        kind: { linear: {} },
+        start: now() - 3600,
+        end: now() + 3600,
+      });
+      await withdrawVested(employee, depositor.publicKey);
+
+      const lock = await program.account.lockedDeposit.fetch(
+        lockPda(depositor.publicKey, employee.publicKey)
+      );
+      expect(lock.withdrawn.toNumber()).to.be.approximately(
+        LAMPORTS_PER_SOL / 2,
+        LAMPORTS_PER_SOL / 50
+      );
+    });
+
+    it("only the beneficiary can withdraw and the lock closes once drained", async () => {
+      const depositor = Keypair.generate();
+      const signature = await provider.connection.requestAirdrop(
+        depositor.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(signature);
+
+      await depositLocked(depositor, employee.publicKey, LAMPORTS_PER_SOL, {
+        kind: { cliff: {} },
+        start: now() - 7200,
+        end: now() - 3600,
+      });
+
+      try {
+        await withdrawVested(employer, depositor.publicKey);
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.toString()).to.include("AccountNotInitialized");
+      }
+
+      const before = await provider.connection.getBalance(employee.publicKey);
+      await withdrawVested(employee, depositor.publicKey);
+      const after = await provider.connection.getBalance(employee.publicKey);
+
+      expect(after - before).to.be.approximately(LAMPORTS_PER_SOL, 1000000);
+      expect(
+        await provider.connection.getAccountInfo(
+          lockPda(depositor.publicKey, employee.publicKey)
+        )
+      ).to.be.null;
+    });
+
+    it("rejects a linear schedule that ends before it starts", async () => {
+      try {
+        await depositLocked(employee, employer.publicKey, LAMPORTS_PER_SOL, {
+          kind: { linear: {} },
+          start: now(),
+          end: now(),
+        });
+        expect.fail("Expected transaction to fail");
+      } catch (error) {
+        expect(error.toString()).to.include("InvalidSchedule");
+      }
+    });
+  });
 });
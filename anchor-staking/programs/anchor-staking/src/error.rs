@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StakingError {
+    #[msg("Amount must be greater than 0")]
+    InvalidAmount,
+    #[msg("Cooldown can't be negative")]
+    InvalidCooldown,
+    #[msg("Not enough staked tokens")]
+    InsufficientStake,
+    #[msg("No unstaked tokens are waiting to be withdrawn")]
+    NothingToWithdraw,
+    #[msg("The unstake cooldown hasn't elapsed yet")]
+    CooldownNotElapsed,
+    #[msg("No rewards to claim")]
+    NothingToClaim,
+    #[msg("Only the pool admin can do this")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::{StakePool, UserStake};
+use crate::transfer::pay_from_vault;
+
+#[derive(Accounts)]
+pub struct Claim<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = reward_mint
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = pool,
+        constraint = user_stake.owner == user.key() @ StakingError::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [StakePool::REWARD_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.reward_vault_bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Claim<'info> {
+    pub fn claim(&mut self) -> Result<()> {
+        let pool = &mut self.pool;
+        let user_stake = &mut self.user_stake;
+
+        pool.update(Clock::get()?.unix_timestamp)?;
+        user_stake.settle(pool)?;
+
+        let amount = user_stake.pending_rewards;
+        require_gt!(amount, 0, StakingError::NothingToClaim);
+        user_stake.pending_rewards = 0;
+
+        pay_from_vault(
+            &self.pool,
+            &self.reward_mint,
+            &self.reward_vault,
+            &self.user_reward_account,
+            &self.token_program,
+            amount,
+        )?;
+
+        msg!("{} claimed {} in rewards", self.user.key(), amount);
+        Ok(())
+    }
+}
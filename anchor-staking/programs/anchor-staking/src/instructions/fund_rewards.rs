@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::StakePool;
+use crate::transfer::deposit_to_vault;
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = admin @ StakingError::Unauthorized,
+        has_one = reward_mint
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = admin,
+        token::token_program = token_program,
+    )]
+    pub admin_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [StakePool::REWARD_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.reward_vault_bump
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> FundRewards<'info> {
+    pub fn fund_rewards(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, StakingError::InvalidAmount);
+
+        // Settle emission at the old budget before topping it up
+        self.pool.update(Clock::get()?.unix_timestamp)?;
+
+        let received = deposit_to_vault(
+            &self.reward_mint,
+            &self.admin_reward_account,
+            &mut self.reward_vault,
+            &self.admin,
+            &self.token_program,
+            amount,
+        )?;
+
+        self.pool.rewards_remaining = self
+            .pool
+            .rewards_remaining
+            .checked_add(received)
+            .ok_or(StakingError::Overflow)?;
+
+        msg!("Funded {} rewards", received);
+        Ok(())
+    }
+}
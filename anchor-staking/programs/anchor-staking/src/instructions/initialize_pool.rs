@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::StakePool;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + StakePool::INIT_SPACE,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            stake_mint.key().as_ref(),
+            reward_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [StakePool::STAKE_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [StakePool::REWARD_VAULT_SEED, pool.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = pool,
+        token::token_program = token_program,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializePool<'info> {
+    pub fn initialize_pool(
+        &mut self,
+        reward_rate: u64,
+        cooldown: i64,
+        bumps: &InitializePoolBumps,
+    ) -> Result<()> {
+        require_gte!(cooldown, 0, StakingError::InvalidCooldown);
+
+        self.pool.set_inner(StakePool {
+            admin: self.admin.key(),
+            stake_mint: self.stake_mint.key(),
+            reward_mint: self.reward_mint.key(),
+            reward_rate,
+            cooldown,
+            reward_per_token: 0,
+            last_update: Clock::get()?.unix_timestamp,
+            total_staked: 0,
+            rewards_remaining: 0,
+            bump: bumps.pool,
+            stake_vault_bump: bumps.stake_vault,
+            reward_vault_bump: bumps.reward_vault,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+pub mod claim;
+pub mod fund_rewards;
+pub mod initialize_pool;
+pub mod set_rate;
+pub mod stake;
+pub mod unstake;
+pub mod withdraw_unstaked;
+
+pub use claim::*;
+pub use fund_rewards::*;
+pub use initialize_pool::*;
+pub use set_rate::*;
+pub use stake::*;
+pub use unstake::*;
+pub use withdraw_unstaked::*;
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::error::StakingError;
+use crate::state::StakePool;
+
+#[derive(Accounts)]
+pub struct SetRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = admin @ StakingError::Unauthorized
+    )]
+    pub pool: Account<'info, StakePool>,
+}
+
+impl<'info> SetRate<'info> {
+    pub fn set_rate(&mut self, reward_rate: u64) -> Result<()> {
+        // Everything up to now is emitted at the old rate
+        self.pool.update(Clock::get()?.unix_timestamp)?;
+        self.pool.reward_rate = reward_rate;
+
+        msg!("Reward rate set to {} per second", reward_rate);
+        Ok(())
+    }
+}
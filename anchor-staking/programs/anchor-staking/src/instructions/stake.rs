@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::{StakePool, UserStake};
+use crate::transfer::deposit_to_vault;
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = stake_mint
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserStake::INIT_SPACE,
+        seeds = [UserStake::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [StakePool::STAKE_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.stake_vault_bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'info> {
+    pub fn stake(&mut self, amount: u64, bumps: &StakeBumps) -> Result<()> {
+        require_gt!(amount, 0, StakingError::InvalidAmount);
+
+        let pool = &mut self.pool;
+        let user_stake = &mut self.user_stake;
+        if user_stake.owner == Pubkey::default() {
+            user_stake.owner = self.user.key();
+            user_stake.pool = pool.key();
+            user_stake.bump = bumps.user_stake;
+        }
+
+        pool.update(Clock::get()?.unix_timestamp)?;
+        user_stake.settle(pool)?;
+
+        let received = deposit_to_vault(
+            &self.stake_mint,
+            &self.user_token_account,
+            &mut self.stake_vault,
+            &self.user,
+            &self.token_program,
+            amount,
+        )?;
+
+        user_stake.amount = user_stake
+            .amount
+            .checked_add(received)
+            .ok_or(StakingError::Overflow)?;
+        pool.total_staked = pool
+            .total_staked
+            .checked_add(received)
+            .ok_or(StakingError::Overflow)?;
+        user_stake.sync(pool)?;
+
+        msg!("{} staked {}", self.user.key(), received);
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::{StakePool, UserStake};
+use crate::transfer::pay_from_vault;
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = stake_mint
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = pool,
+        constraint = user_stake.owner == user.key() @ StakingError::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [StakePool::STAKE_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.stake_vault_bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> Unstake<'info> {
+    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+        require_gt!(amount, 0, StakingError::InvalidAmount);
+        require_gte!(
+            self.user_stake.amount,
+            amount,
+            StakingError::InsufficientStake
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let pool = &mut self.pool;
+        let user_stake = &mut self.user_stake;
+
+        pool.update(now)?;
+        user_stake.settle(pool)?;
+
+        user_stake.amount -= amount;
+        pool.total_staked -= amount;
+        user_stake.sync(pool)?;
+
+        if pool.cooldown == 0 {
+            pay_from_vault(
+                &self.pool,
+                &self.stake_mint,
+                &self.stake_vault,
+                &self.user_token_account,
+                &self.token_program,
+                amount,
+            )?;
+            msg!("{} unstaked {}", self.user.key(), amount);
+            return Ok(());
+        }
+
+        // A new unstake restarts the cooldown for everything still waiting
+        user_stake.unstaking = user_stake
+            .unstaking
+            .checked_add(amount)
+            .ok_or(StakingError::Overflow)?;
+        user_stake.unstake_available_at = now
+            .checked_add(pool.cooldown)
+            .ok_or(StakingError::Overflow)?;
+
+        msg!(
+            "{} unstaked {}, available at {}",
+            self.user.key(),
+            amount,
+            user_stake.unstake_available_at
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::StakingError;
+use crate::state::{StakePool, UserStake};
+use crate::transfer::pay_from_vault;
+
+#[derive(Accounts)]
+pub struct WithdrawUnstaked<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [
+            StakePool::SEED_PREFIX,
+            pool.stake_mint.as_ref(),
+            pool.reward_mint.as_ref()
+        ],
+        bump = pool.bump,
+        has_one = stake_mint
+    )]
+    pub pool: Account<'info, StakePool>,
+
+    #[account(
+        mut,
+        seeds = [UserStake::SEED_PREFIX, pool.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump,
+        has_one = pool,
+        constraint = user_stake.owner == user.key() @ StakingError::Unauthorized
+    )]
+    pub user_stake: Account<'info, UserStake>,
+
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [StakePool::STAKE_VAULT_SEED, pool.key().as_ref()],
+        bump = pool.stake_vault_bump
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawUnstaked<'info> {
+    pub fn withdraw_unstaked(&mut self) -> Result<()> {
+        let amount = self.user_stake.unstaking;
+        require_gt!(amount, 0, StakingError::NothingToWithdraw);
+        require_gte!(
+            Clock::get()?.unix_timestamp,
+            self.user_stake.unstake_available_at,
+            StakingError::CooldownNotElapsed
+        );
+
+        self.user_stake.unstaking = 0;
+        pay_from_vault(
+            &self.pool,
+            &self.stake_mint,
+            &self.stake_vault,
+            &self.user_token_account,
+            &self.token_program,
+            amount,
+        )?;
+
+        msg!("{} withdrew {}", self.user.key(), amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod transfer;
+
+use instructions::*;
+
+declare_id!("8WxDdmKLz39BwZnGNfwv5AQiav3Y2C8i8dwSCKNHLtpj");
+
+#[program]
+pub mod anchor_staking {
+    use super::*;
+
+    /// Create a pool staking `stake_mint` for `reward_mint` rewards
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        reward_rate: u64,
+        cooldown: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .initialize_pool(reward_rate, cooldown, &ctx.bumps)
+    }
+
+    /// Stake tokens; rewards accrued so far are settled first
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        ctx.accounts.stake(amount, &ctx.bumps)
+    }
+
+    /// Stop staking tokens, starting the pool's cooldown if it has one
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake(amount)
+    }
+
+    /// Withdraw unstaked tokens once the cooldown has elapsed
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstaked>) -> Result<()> {
+        ctx.accounts.withdraw_unstaked()
+    }
+
+    /// Pay out the signer's accrued rewards
+    pub fn claim(ctx: Context<Claim>) -> Result<()> {
+        ctx.accounts.claim()
+    }
+
+    /// Admin-only: add reward tokens to the pool's emission budget
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rewards(amount)
+    }
+
+    /// Admin-only: change the per-second reward rate
+    pub fn set_rate(ctx: Context<SetRate>, reward_rate: u64) -> Result<()> {
+        ctx.accounts.set_rate(reward_rate)
+    }
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::error::StakingError;
+
+/// Fixed-point scale of `StakePool::reward_per_token`
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakePool {
+    pub admin: Pubkey,
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    /// Reward tokens emitted per second, shared by all stakers
+    pub reward_rate: u64,
+    /// Seconds between `unstake` and `withdraw_unstaked`; 0 pays out at once
+    pub cooldown: i64,
+    /// Rewards earned by one staked token since the pool was created,
+    /// scaled by `REWARD_PRECISION`
+    pub reward_per_token: u128,
+    pub last_update: i64,
+    pub total_staked: u64,
+    /// Funded rewards not yet emitted. Emission stops when it runs out, so
+    /// every accrued reward is backed by the reward vault.
+    pub rewards_remaining: u64,
+    pub bump: u8,
+    pub stake_vault_bump: u8,
+    pub reward_vault_bump: u8,
+}
+
+impl StakePool {
+    pub const SEED_PREFIX: &'static [u8] = b"stake_pool";
+    pub const STAKE_VAULT_SEED: &'static [u8] = b"stake_vault";
+    pub const REWARD_VAULT_SEED: &'static [u8] = b"reward_vault";
+
+    /// Accrue the rewards emitted since `last_update` into `reward_per_token`
+    ///
+    /// Nothing is emitted while the pool is empty, so no rewards are lost to
+    /// periods without stakers.
+    pub fn update(&mut self, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_update).max(0) as u64;
+        let emitted = self
+            .reward_rate
+            .saturating_mul(elapsed)
+            .min(self.rewards_remaining);
+        if self.total_staked == 0 || emitted == 0 {
+            self.last_update = now;
+            return Ok(());
+        }
+
+        let total_staked = self.total_staked as u128;
+        let increment = emitted as u128 * REWARD_PRECISION / total_staked;
+        // Too little to move `reward_per_token` yet: leave the time to
+        // accrue so it isn't lost to rounding
+        if increment == 0 {
+            return Ok(());
+        }
+
+        // Only charge the budget for what stakers were actually credited;
+        // the rounding dust stays funded for later
+        let distributed = increment * total_staked / REWARD_PRECISION;
+        self.rewards_remaining -= distributed as u64;
+        self.reward_per_token = self
+            .reward_per_token
+            .checked_add(increment)
+            .ok_or(StakingError::Overflow)?;
+        self.last_update = now;
+
+        Ok(())
+    }
+
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            Self::SEED_PREFIX,
+            self.stake_mint.as_ref(),
+            self.reward_mint.as_ref(),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserStake {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    /// `amount * reward_per_token` at the last settlement; what the stake
+    /// had already been credited for
+    pub reward_debt: u128,
+    /// Settled rewards waiting to be claimed
+    pub pending_rewards: u64,
+    /// Unstaked tokens waiting out the cooldown; they no longer earn rewards
+    pub unstaking: u64,
+    pub unstake_available_at: i64,
+    pub bump: u8,
+}
+
+impl UserStake {
+    pub const SEED_PREFIX: &'static [u8] = b"user_stake";
+
+    /// Credit the rewards earned since the last settlement. The pool must
+    /// be updated first; call `sync` after changing `amount`.
+    pub fn settle(&mut self, pool: &StakePool) -> Result<()> {
+        let accrued = self.accrued(pool)?;
+        let earned = (accrued - self.reward_debt) / REWARD_PRECISION;
+        self.pending_rewards = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.pending_rewards.checked_add(earned))
+            .ok_or(StakingError::Overflow)?;
+        self.reward_debt = accrued;
+        Ok(())
+    }
+
+    pub fn sync(&mut self, pool: &StakePool) -> Result<()> {
+        self.reward_debt = self.accrued(pool)?;
+        Ok(())
+    }
+
+    fn accrued(&self, pool: &StakePool) -> Result<u128> {
+        (self.amount as u128)
+            .checked_mul(pool.reward_per_token)
+            .ok_or(StakingError::Overflow.into())
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::StakingError;
+use crate::state::StakePool;
+
+/// Move `amount` from a user's token account into one of the pool's vaults
+/// and return what the vault actually received
+///
+/// Token-2022 transfer fees are withheld from the destination, so only the
+/// vault's balance delta can be credited.
+pub fn deposit_to_vault<'info>(
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<u64> {
+    let balance_before = vault.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: vault.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+    vault.reload()?;
+    vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(StakingError::Overflow.into())
+}
+
+/// Move `amount` out of one of the pool's vaults, signed by the pool PDA
+pub fn pay_from_vault<'info>(
+    pool: &Account<'info, StakePool>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let seeds = pool.signer_seeds();
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: vault.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: pool.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    transfer_checked(cpi_ctx, amount, mint.decimals)
+}
@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  createAssociatedTokenAccount,
+  createMint,
+  getAccount,
+  mintTo,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import { AnchorStaking } from "../target/types/anchor_staking";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+describe("anchor-staking", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const connection = provider.connection;
+
+  const program = anchor.workspace.anchorStaking as Program<AnchorStaking>;
+
+  const admin = Keypair.generate();
+  const alice = Keypair.generate();
+  const bob = Keypair.generate();
+
+  let stakeMint: PublicKey;
+  let rewardMint: PublicKey;
+  let pool: PublicKey;
+  const tokenAccounts = new Map<string, { stake: PublicKey; reward: PublicKey }>();
+
+  const rewardRate = 1_000;
+  const cooldown = 2;
+
+  const userStakePda = (user: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("user_stake"), pool.toBuffer(), user.toBuffer()],
+      program.programId
+    )[0];
+
+  const balance = async (account: PublicKey) =>
+    Number((await getAccount(connection, account)).amount);
+
+  const stake = (user: Keypair, amount: number) =>
+    program.methods
+      .stake(new BN(amount))
+      .accountsPartial({
+        user: user.publicKey,
+        pool,
+        stakeMint,
+        userTokenAccount: tokenAccounts.get(user.publicKey.toBase58()).stake,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const unstake = (user: Keypair, amount: number) =>
+    program.methods
+      .unstake(new BN(amount))
+      .accountsPartial({
+        user: user.publicKey,
+        pool,
+        stakeMint,
+        userTokenAccount: tokenAccounts.get(user.publicKey.toBase58()).stake,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const withdrawUnstaked = (user: Keypair) =>
+    program.methods
+      .withdrawUnstaked()
+      .accountsPartial({
+        user: user.publicKey,
+        pool,
+        stakeMint,
+        userTokenAccount: tokenAccounts.get(user.publicKey.toBase58()).stake,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  const claim = (user: Keypair) =>
+    program.methods
+      .claim()
+      .accountsPartial({
+        user: user.publicKey,
+        pool,
+        rewardMint,
+        userRewardAccount: tokenAccounts.get(user.publicKey.toBase58()).reward,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    for (const kp of [admin, alice, bob]) {
+      const sig = await connection.requestAirdrop(
+        kp.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await connection.confirmTransaction(sig);
+    }
+
+    stakeMint = await createMint(connection, admin, admin.publicKey, null, 0);
+    rewardMint = await createMint(connection, admin, admin.publicKey, null, 0);
+    [pool] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_pool"), stakeMint.toBuffer(), rewardMint.toBuffer()],
+      program.programId
+    );
+
+    for (const kp of [admin, alice, bob]) {
+      const accounts = {
+        stake: await createAssociatedTokenAccount(
+          connection,
+          admin,
+          stakeMint,
+          kp.publicKey
+        ),
+        reward: await createAssociatedTokenAccount(
+          connection,
+          admin,
+          rewardMint,
+          kp.publicKey
+        ),
+      };
+      await mintTo(connection, admin, stakeMint, accounts.stake, admin, 1_000);
+      tokenAccounts.set(kp.publicKey.toBase58(), accounts);
+    }
+    await mintTo(
+      connection,
+      admin,
+      rewardMint,
+      tokenAccounts.get(admin.publicKey.toBase58()).reward,
+      admin,
+      1_000_000
+    );
+  });
+
+  it("Initializes and funds a pool", async () => {
+    await program.methods
+      .initializePool(new BN(rewardRate), new BN(cooldown))
+      .accountsPartial({
+        admin: admin.publicKey,
+        stakeMint,
+        rewardMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .fundRewards(new BN(100_000))
+      .accountsPartial({
+        admin: admin.publicKey,
+        pool,
+        rewardMint,
+        adminRewardAccount: tokenAccounts.get(admin.publicKey.toBase58())
+          .reward,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+
+    const state = await program.account.stakePool.fetch(pool);
+    assert.equal(state.rewardRate.toNumber(), rewardRate);
+    assert.equal(state.rewardsRemaining.toNumber(), 100_000);
+  });
+
+  it("Rejects rate changes from anyone but the admin", async () => {
+    try {
+      await program.methods
+        .setRate(new BN(1))
+        .accountsPartial({ admin: alice.publicKey, pool })
+        .signers([alice])
+        .rpc();
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "Unauthorized");
+    }
+  });
+
+  it("Splits rewards in proportion to stake", async () => {
+    await stake(alice, 300);
+    await stake(bob, 100);
+    await sleep(3000);
+
+    await claim(alice);
+    await claim(bob);
+
+    const aliceRewards = await balance(
+      tokenAccounts.get(alice.publicKey.toBase58()).reward
+    );
+    const bobRewards = await balance(
+      tokenAccounts.get(bob.publicKey.toBase58()).reward
+    );
+    assert.isAbove(aliceRewards, 0);
+    assert.isAbove(bobRewards, 0);
+    // Alice earned alone until Bob joined, then three times Bob's share
+    assert.isAbove(aliceRewards, 2 * bobRewards);
+
+    const state = await program.account.stakePool.fetch(pool);
+    assert.equal(state.totalStaked.toNumber(), 400);
+  });
+
+  it("Holds unstaked tokens until the cooldown elapses", async () => {
+    const aliceStake = tokenAccounts.get(alice.publicKey.toBase58()).stake;
+    const before = await balance(aliceStake);
+
+    await unstake(alice, 100);
+    const userStake = await program.account.userStake.fetch(
+      userStakePda(alice.publicKey)
+    );
+    assert.equal(userStake.amount.toNumber(), 200);
+    assert.equal(userStake.unstaking.toNumber(), 100);
+
+    try {
+      await withdrawUnstaked(alice);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "CooldownNotElapsed");
+    }
+
+    await sleep((cooldown + 1) * 1000);
+    await withdrawUnstaked(alice);
+    assert.equal(await balance(aliceStake), before + 100);
+  });
+
+  it("Rejects unstaking more than is staked", async () => {
+    try {
+      await unstake(bob, 101);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "InsufficientStake");
+    }
+  });
+});
@@ -0,0 +1,65 @@
+//! Integer Elo ratings
+//!
+//! Floating point is expensive on-chain, so expected scores come from a
+//! table of the logistic curve, interpolated between 25-point rating gaps.
+
+/// Rating change for a completely unexpected result
+pub const K_FACTOR: i64 = 32;
+
+/// Step between `EXPECTED_BPS` entries
+const GAP_STEP: u32 = 25;
+
+/// Expected score of the lower-rated player, in basis points, at rating gaps
+/// of 0, 25, ..., 800: `10_000 / (1 + 10^(gap / 400))`. Gaps beyond 800 are
+/// treated as 800.
+const EXPECTED_BPS: [u32; 33] = [
+    5000, 4641, 4285, 3937, 3599, 3275, 2966, 2675, 2403, 2150, 1917, 1704, 1510, 1334, 1177, 1035,
+    909, 797, 698, 610, 532, 464, 405, 352, 307, 267, 232, 201, 175, 152, 132, 114, 99,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Tie,
+}
+
+impl Outcome {
+    fn score_bps(self) -> u32 {
+        match self {
+            Outcome::Win => 10_000,
+            Outcome::Loss => 0,
+            Outcome::Tie => 5_000,
+        }
+    }
+}
+
+/// Expected score of a player rated `rating` against `opponent`, in basis
+/// points
+pub fn expected_score_bps(rating: u32, opponent: u32) -> u32 {
+    let max_gap = GAP_STEP * (EXPECTED_BPS.len() as u32 - 1);
+    let gap = rating.abs_diff(opponent).min(max_gap);
+
+    let index = (gap / GAP_STEP) as usize;
+    let lower = EXPECTED_BPS[index];
+    let underdog = match EXPECTED_BPS.get(index + 1) {
+        Some(&next) => lower - (lower - next) * (gap % GAP_STEP) / GAP_STEP,
+        None => lower,
+    };
+
+    if rating >= opponent {
+        10_000 - underdog
+    } else {
+        underdog
+    }
+}
+
+/// Rating change for a player rated `rating` after `outcome` against
+/// `opponent`, rounded half away from zero. The opponent's change is exactly
+/// the negation, so rating points are conserved.
+pub fn rating_change(rating: u32, opponent: u32, outcome: Outcome) -> i32 {
+    let surprise = outcome.score_bps() as i64 - expected_score_bps(rating, opponent) as i64;
+    let scaled = K_FACTOR * surprise;
+    let rounded = (scaled.abs() + 5_000) / 10_000;
+    (rounded * scaled.signum()) as i32
+}
@@ -7,4 +7,23 @@ pub enum TicTacToeError {
     GameAlreadyOver,
     NotPlayersTurn,
     GameAlreadyStarted,
+    NotAPlayer,
+    CannotPlayAgainstSelf,
+    InvalidTurnTimeout,
+    TurnNotTimedOut,
+    GameNotJoined,
+    GameAlreadyJoined,
+    GameNotOver,
+    InvalidFee,
+    InvalidStake,
+    StakeBelowRentExemption,
+    MissingStakeAccounts,
+    StakeMintMismatch,
+    InvalidBoardSize,
+    InvalidWinLength,
+    LobbyFull,
+    ChallengeNotFound,
+    ChallengeAlreadyPosted,
+    MissingProfiles,
+    ChallengeNotExpired,
 }
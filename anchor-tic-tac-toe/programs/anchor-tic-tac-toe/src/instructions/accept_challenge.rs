@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::*;
+use crate::wager::{deposit_stake, TokenStake};
+
+#[derive(Accounts)]
+#[instruction(challenger: Pubkey, game_id: u64)]
+pub struct AcceptChallenge<'info> {
+    #[account(mut, seeds = [Lobby::SEED_PREFIX], bump = lobby.bump)]
+    pub lobby: Account<'info, Lobby>,
+
+    #[account(seeds = [House::SEED_PREFIX], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    #[account(
+        init,
+        payer = player_two,
+        space = 8 + Game::INIT_SPACE,
+        seeds = [Game::SEED_PREFIX, challenger.as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit for SOL stakes
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_two,
+        token::token_program = token_program,
+    )]
+    pub player_two_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [Profile::SEED_PREFIX, challenger.as_ref()],
+        bump = challenger_profile.bump
+    )]
+    pub challenger_profile: Account<'info, Profile>,
+
+    #[account(
+        seeds = [Profile::SEED_PREFIX, player_two.key().as_ref()],
+        bump = player_two_profile.bump
+    )]
+    pub player_two_profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub player_two: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptChallenge<'info> {
+    /// Take an open challenge, creating its game with both stakes escrowed
+    pub fn accept_challenge(
+        &mut self,
+        challenger: Pubkey,
+        game_id: u64,
+        bumps: &AcceptChallengeBumps,
+    ) -> Result<()> {
+        let challenge = self.lobby.take(&challenger, game_id)?;
+
+        let game = &mut self.game;
+        game.start([challenger, self.player_two.key()], &challenge.config)?;
+        game.game_id = game_id;
+        game.last_move_at = Clock::get()?.unix_timestamp;
+        game.bump = bumps.game;
+        game.stake_mint = challenge.stake_mint;
+        game.treasury = self.house.treasury;
+        game.fee_bps = self.house.fee_bps;
+        game.joined = true;
+        game.vault_bump = bumps.vault;
+        game.rated = true;
+        game.rent_payer = self.player_two.key();
+
+        let token_stake = TokenStake::new(
+            challenge.stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        deposit_stake(
+            &self.player_two,
+            self.player_two_token_account.as_ref(),
+            &self.vault,
+            &self.system_program,
+            token_stake.as_ref(),
+            challenge.config.stake,
+        )?;
+
+        msg!(
+            "{} accepted challenge {} from {}",
+            self.player_two.key(),
+            game_id,
+            challenger
+        );
+
+        Ok(())
+    }
+}
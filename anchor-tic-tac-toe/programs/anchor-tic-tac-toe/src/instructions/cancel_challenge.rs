@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::*;
+use crate::state::*;
+use crate::wager::{Escrow, TokenStake};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CancelChallenge<'info> {
+    #[account(mut, seeds = [Lobby::SEED_PREFIX], bump = lobby.bump)]
+    pub lobby: Account<'info, Lobby>,
+
+    /// CHECK: The not-yet-created game whose vaults hold the stake
+    #[account(
+        seeds = [Game::SEED_PREFIX, challenger.key().as_ref(), &game_id.to_le_bytes()],
+        bump,
+        constraint = game.data_is_empty() @ TicTacToeError::GameAlreadyStarted
+    )]
+    pub game: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit for SOL stakes
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelChallenge<'info> {
+    pub fn cancel_challenge(&mut self, game_id: u64, bumps: &CancelChallengeBumps) -> Result<()> {
+        let challenge = self.lobby.take(&self.challenger.key(), game_id)?;
+
+        let token_stake = TokenStake::new(
+            challenge.stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        let escrow = Escrow {
+            game: self.game.to_account_info(),
+            player_one: self.challenger.key(),
+            game_id,
+            bump: bumps.game,
+            vault_bump: bumps.vault,
+        };
+        let challenger = self.challenger.to_account_info();
+
+        escrow.pay_out(
+            &self.vault,
+            &challenger,
+            self.challenger_token_account.as_ref(),
+            &self.system_program,
+            token_stake.as_ref(),
+            challenge.config.stake,
+        )?;
+        if let Some(token_stake) = &token_stake {
+            escrow.close_token_vault(token_stake, &challenger)?;
+        }
+
+        msg!("Challenge {} withdrawn", game_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::*;
+use crate::state::*;
+use crate::wager::{Escrow, TokenStake};
+
+#[derive(Accounts)]
+pub struct CancelGame<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        constraint = !game.joined @ TicTacToeError::GameAlreadyJoined,
+        close = player_one
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump = game.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit for SOL stakes
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_one,
+        token::token_program = token_program,
+    )]
+    pub player_one_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = game.players[0] @ TicTacToeError::NotAPlayer)]
+    pub player_one: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelGame<'info> {
+    /// Player one withdraws an invite that was never accepted
+    pub fn cancel_game(&mut self) -> Result<()> {
+        let token_stake = TokenStake::new(
+            self.game.stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        let escrow = Escrow::for_game(&self.game);
+        let player_one = self.player_one.to_account_info();
+
+        escrow.pay_out(
+            &self.vault,
+            &player_one,
+            self.player_one_token_account.as_ref(),
+            &self.system_program,
+            token_stake.as_ref(),
+            self.game.stake,
+        )?;
+        if let Some(token_stake) = &token_stake {
+            escrow.close_token_vault(token_stake, &player_one)?;
+        }
+
+        msg!("Game {} cancelled", self.game.game_id);
+
+        Ok(())
+    }
+}
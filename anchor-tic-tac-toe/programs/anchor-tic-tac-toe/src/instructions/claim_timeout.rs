@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct ClaimTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump
+    )]
+    pub game: Account<'info, Game>,
+
+    /// The player waiting on a stalled opponent
+    pub player: Signer<'info>,
+}
+
+impl<'info> ClaimTimeout<'info> {
+    pub fn claim_timeout(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.game.claim_timeout(&self.player.key(), now)?;
+
+        msg!("{} wins on timeout", self.player.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct CreateProfile<'info> {
+    #[account(
+        init,
+        payer = player,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [Profile::SEED_PREFIX, player.key().as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProfile<'info> {
+    pub fn create_profile(&mut self, bumps: &CreateProfileBumps) -> Result<()> {
+        self.profile.set_inner(Profile {
+            player: self.player.key(),
+            rating: Profile::INITIAL_RATING,
+            wins: 0,
+            losses: 0,
+            ties: 0,
+            bump: bumps.profile,
+        });
+
+        msg!("Profile created for {}", self.player.key());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeHouse<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + House::INIT_SPACE,
+        seeds = [House::SEED_PREFIX],
+        bump
+    )]
+    pub house: Account<'info, House>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeHouse<'info> {
+    pub fn initialize_house(
+        &mut self,
+        treasury: Pubkey,
+        fee_bps: u16,
+        bumps: &InitializeHouseBumps,
+    ) -> Result<()> {
+        require!(fee_bps <= House::MAX_FEE_BPS, TicTacToeError::InvalidFee);
+
+        self.house.set_inner(House {
+            admin: self.admin.key(),
+            treasury,
+            fee_bps,
+            bump: bumps.house,
+        });
+
+        msg!("House fee {} bps paid to {}", fee_bps, treasury);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct InitializeLobby<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Lobby::INIT_SPACE,
+        seeds = [Lobby::SEED_PREFIX],
+        bump
+    )]
+    pub lobby: Account<'info, Lobby>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeLobby<'info> {
+    pub fn initialize_lobby(&mut self, bumps: &InitializeLobbyBumps) -> Result<()> {
+        self.lobby.set_inner(Lobby {
+            challenges: Vec::new(),
+            bump: bumps.lobby,
+        });
+
+        msg!("Lobby opened");
+
+        Ok(())
+    }
+}
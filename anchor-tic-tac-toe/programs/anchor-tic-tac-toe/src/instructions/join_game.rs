@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::*;
+use crate::state::*;
+use crate::wager::{deposit_stake, TokenStake};
+
+#[derive(Accounts)]
+pub struct JoinGame<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        constraint = !game.joined @ TicTacToeError::GameAlreadyJoined
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump = game.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit for SOL stakes
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_two,
+        token::token_program = token_program,
+    )]
+    pub player_two_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The invited player matches player one's stake
+    #[account(mut, address = game.players[1] @ TicTacToeError::NotAPlayer)]
+    pub player_two: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> JoinGame<'info> {
+    pub fn join_game(&mut self) -> Result<()> {
+        let token_stake = TokenStake::new(
+            self.game.stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        deposit_stake(
+            &self.player_two,
+            self.player_two_token_account.as_ref(),
+            &self.vault,
+            &self.system_program,
+            token_stake.as_ref(),
+            self.game.stake,
+        )?;
+
+        // The first turn's clock starts once both stakes are in
+        let game = &mut self.game;
+        game.joined = true;
+        game.last_move_at = Clock::get()?.unix_timestamp;
+
+        msg!("{} joined game {}", self.player_two.key(), game.game_id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+pub mod accept_challenge;
+pub mod cancel_challenge;
+pub mod cancel_game;
+pub mod claim_timeout;
+pub mod create_profile;
+pub mod expire_challenge;
+pub mod initialize_house;
+pub mod initialize_lobby;
+pub mod join_game;
+pub mod play;
+pub mod post_challenge;
+pub mod resign;
+pub mod settle_game;
+pub mod setup_game;
+pub mod update_house;
+
+pub use accept_challenge::*;
+pub use cancel_challenge::*;
+pub use cancel_game::*;
+pub use claim_timeout::*;
+pub use create_profile::*;
+pub use expire_challenge::*;
+pub use initialize_house::*;
+pub use initialize_lobby::*;
+pub use join_game::*;
+pub use play::*;
+pub use post_challenge::*;
+pub use resign::*;
+pub use settle_game::*;
+pub use setup_game::*;
+pub use update_house::*;
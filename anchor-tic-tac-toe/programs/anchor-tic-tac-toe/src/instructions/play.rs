@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct Play<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump
+    )]
+    pub game: Account<'info, Game>,
+
+    pub player: Signer<'info>,
+}
+
+impl<'info> Play<'info> {
+    pub fn play(&mut self, tile: Tile) -> Result<()> {
+        let game = &mut self.game;
+
+        require!(game.is_active(), TicTacToeError::GameAlreadyOver);
+        require_keys_eq!(
+            game.current_player(),
+            self.player.key(),
+            TicTacToeError::NotPlayersTurn
+        );
+
+        game.play(&tile)?;
+        game.last_move_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::*;
+use crate::state::*;
+use crate::wager::{deposit_stake, TokenStake};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct PostChallenge<'info> {
+    #[account(mut, seeds = [Lobby::SEED_PREFIX], bump = lobby.bump)]
+    pub lobby: Account<'info, Lobby>,
+
+    /// CHECK: The game created when the challenge is accepted; it must not
+    /// exist yet. The stake is escrowed in its vaults meanwhile.
+    #[account(
+        seeds = [Game::SEED_PREFIX, challenger.key().as_ref(), &game_id.to_le_bytes()],
+        bump,
+        constraint = game.data_is_empty() @ TicTacToeError::GameAlreadyStarted
+    )]
+    pub game: UncheckedAccount<'info>,
+
+    /// Holds SOL stakes; unused for token stakes
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit to stake SOL
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = challenger,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = game,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = challenger,
+        token::token_program = token_program,
+    )]
+    pub challenger_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Lobby games are rated, so the challenger needs a profile
+    #[account(
+        seeds = [Profile::SEED_PREFIX, challenger.key().as_ref()],
+        bump = challenger_profile.bump
+    )]
+    pub challenger_profile: Account<'info, Profile>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostChallenge<'info> {
+    pub fn post_challenge(&mut self, game_id: u64, config: GameConfig) -> Result<()> {
+        let stake_mint = self.stake_mint.as_ref().map(|mint| mint.key());
+        config.validate(stake_mint)?;
+
+        self.lobby.post(Challenge {
+            challenger: self.challenger.key(),
+            game_id,
+            config,
+            stake_mint,
+            posted_at: Clock::get()?.unix_timestamp,
+        })?;
+
+        let token_stake = TokenStake::new(
+            stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        deposit_stake(
+            &self.challenger,
+            self.challenger_token_account.as_ref(),
+            &self.vault,
+            &self.system_program,
+            token_stake.as_ref(),
+            config.stake,
+        )?;
+
+        msg!(
+            "{} posted challenge {} ({}x{}, {} in a row, stake {})",
+            self.challenger.key(),
+            game_id,
+            config.board_size,
+            config.board_size,
+            config.win_length,
+            config.stake
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct Resign<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump
+    )]
+    pub game: Account<'info, Game>,
+
+    pub player: Signer<'info>,
+}
+
+impl<'info> Resign<'info> {
+    pub fn resign(&mut self) -> Result<()> {
+        self.game.resign(&self.player.key())?;
+
+        msg!("{} resigned", self.player.key());
+
+        Ok(())
+    }
+}
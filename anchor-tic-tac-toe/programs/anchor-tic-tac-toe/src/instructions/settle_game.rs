@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::*;
+use crate::state::*;
+use crate::wager::{Escrow, TokenStake};
+
+#[derive(Accounts)]
+pub struct SettleGame<'info> {
+    #[account(
+        mut,
+        seeds = [Game::SEED_PREFIX, game.players[0].as_ref(), &game.game_id.to_le_bytes()],
+        bump = game.bump,
+        constraint = !game.is_active() @ TicTacToeError::GameNotOver,
+        close = rent_payer
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump = game.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Receives player one's share
+    #[account(mut, address = game.players[0] @ TicTacToeError::NotAPlayer)]
+    pub player_one: UncheckedAccount<'info>,
+
+    /// CHECK: Receives player two's share
+    #[account(mut, address = game.players[1] @ TicTacToeError::NotAPlayer)]
+    pub player_two: UncheckedAccount<'info>,
+
+    /// CHECK: Receives the house fee
+    #[account(mut, address = game.treasury)]
+    pub treasury: UncheckedAccount<'info>,
+
+    /// CHECK: Paid the game's rent and gets it back
+    #[account(mut, address = game.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Stake mint - omit for SOL stakes
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_one,
+        token::token_program = token_program,
+    )]
+    pub player_one_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_two,
+        token::token_program = token_program,
+    )]
+    pub player_two_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = treasury,
+        token::token_program = token_program,
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Required for rated games
+    #[account(
+        mut,
+        seeds = [Profile::SEED_PREFIX, game.players[0].as_ref()],
+        bump = player_one_profile.bump
+    )]
+    pub player_one_profile: Option<Account<'info, Profile>>,
+
+    /// Required for rated games
+    #[account(
+        mut,
+        seeds = [Profile::SEED_PREFIX, game.players[1].as_ref()],
+        bump = player_two_profile.bump
+    )]
+    pub player_two_profile: Option<Account<'info, Profile>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleGame<'info> {
+    /// Pay out the pot of a finished game and close it. Anyone may crank this.
+    pub fn settle_game(&mut self) -> Result<()> {
+        let payouts = self.game.payouts()?;
+        let token_stake = TokenStake::new(
+            self.game.stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+
+        let escrow = Escrow::for_game(&self.game);
+        let player_one = self.player_one.to_account_info();
+        for (recipient, recipient_token_account, amount) in [
+            (
+                &player_one,
+                self.player_one_token_account.as_ref(),
+                payouts.player_one,
+            ),
+            (
+                &self.player_two.to_account_info(),
+                self.player_two_token_account.as_ref(),
+                payouts.player_two,
+            ),
+            (
+                &self.treasury.to_account_info(),
+                self.treasury_token_account.as_ref(),
+                payouts.fee,
+            ),
+        ] {
+            escrow.pay_out(
+                &self.vault,
+                recipient,
+                recipient_token_account,
+                &self.system_program,
+                token_stake.as_ref(),
+                amount,
+            )?;
+        }
+
+        if let Some(token_stake) = &token_stake {
+            escrow.close_token_vault(token_stake, &player_one)?;
+        }
+
+        if self.game.rated {
+            self.update_profiles()?;
+        }
+
+        msg!(
+            "Game {} settled: {} / {}, fee {}",
+            self.game.game_id,
+            payouts.player_one,
+            payouts.player_two,
+            payouts.fee
+        );
+
+        Ok(())
+    }
+
+    fn update_profiles(&mut self) -> Result<()> {
+        let [outcome_one, outcome_two] = self.game.outcomes()?;
+        let (Some(player_one), Some(player_two)) = (
+            self.player_one_profile.as_mut(),
+            self.player_two_profile.as_mut(),
+        ) else {
+            return err!(TicTacToeError::MissingProfiles);
+        };
+
+        let (rating_one, rating_two) = (player_one.rating, player_two.rating);
+        player_one.record(rating_two, outcome_one);
+        player_two.record(rating_one, outcome_two);
+
+        msg!(
+            "Ratings: {} -> {}, {} -> {}",
+            rating_one,
+            player_one.rating,
+            rating_two,
+            player_two.rating
+        );
+
+        Ok(())
+    }
+}
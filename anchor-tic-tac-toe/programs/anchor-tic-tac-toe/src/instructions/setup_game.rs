@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::*;
+use crate::wager::{deposit_stake, TokenStake};
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct SetupGame<'info> {
+    #[account(
+        init,
+        payer = player_one,
+        space = 8 + Game::INIT_SPACE,
+        seeds = [Game::SEED_PREFIX, player_one.key().as_ref(), &game_id.to_le_bytes()],
+        bump
+    )]
+    pub game: Account<'info, Game>,
+
+    #[account(seeds = [House::SEED_PREFIX], bump = house.bump)]
+    pub house: Account<'info, House>,
+
+    /// Holds SOL stakes; unused for token stakes
+    #[account(
+        mut,
+        seeds = [Game::VAULT_SEED, game.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Stake mint - omit to stake SOL
+    pub stake_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = player_one,
+        seeds = [Game::TOKEN_VAULT_SEED, game.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = game,
+        token::token_program = token_program,
+    )]
+    pub token_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = stake_mint,
+        token::authority = player_one,
+        token::token_program = token_program,
+    )]
+    pub player_one_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub player_one: Signer<'info>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetupGame<'info> {
+    pub fn setup_game(
+        &mut self,
+        game_id: u64,
+        player_two: Pubkey,
+        config: GameConfig,
+        bumps: &SetupGameBumps,
+    ) -> Result<()> {
+        let stake_mint = self.stake_mint.as_ref().map(|mint| mint.key());
+        config.validate(stake_mint)?;
+
+        let game = &mut self.game;
+        game.start([self.player_one.key(), player_two], &config)?;
+        game.game_id = game_id;
+        game.last_move_at = Clock::get()?.unix_timestamp;
+        game.bump = bumps.game;
+        game.rent_payer = self.player_one.key();
+        game.stake_mint = stake_mint;
+        game.treasury = self.house.treasury;
+        game.fee_bps = self.house.fee_bps;
+        // A friendly game needs nothing from player two before play starts
+        game.joined = config.stake == 0;
+        game.vault_bump = bumps.vault;
+
+        let token_stake = TokenStake::new(
+            stake_mint,
+            self.stake_mint.as_ref(),
+            self.token_vault.as_ref(),
+            self.token_program.as_ref(),
+        )?;
+        deposit_stake(
+            &self.player_one,
+            self.player_one_token_account.as_ref(),
+            &self.vault,
+            &self.system_program,
+            token_stake.as_ref(),
+            config.stake,
+        )?;
+
+        msg!("Game {} set up, {} invited", game_id, player_two);
+        msg!(
+            "{}x{} board, {} in a row wins",
+            config.board_size,
+            config.board_size,
+            config.win_length
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::error::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct UpdateHouse<'info> {
+    #[account(
+        mut,
+        seeds = [House::SEED_PREFIX],
+        bump = house.bump,
+        has_one = admin
+    )]
+    pub house: Account<'info, House>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> UpdateHouse<'info> {
+    /// Only affects games set up afterwards; live games keep their terms
+    pub fn update_house(&mut self, treasury: Pubkey, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= House::MAX_FEE_BPS, TicTacToeError::InvalidFee);
+
+        self.house.treasury = treasury;
+        self.house.fee_bps = fee_bps;
+
+        msg!("House fee {} bps paid to {}", fee_bps, treasury);
+
+        Ok(())
+    }
+}
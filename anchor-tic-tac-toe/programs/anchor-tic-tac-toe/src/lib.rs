@@ -1,12 +1,13 @@
+pub mod elo;
 pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod wager;
 
 use anchor_lang::prelude::*;
 
-use num_derive::*;
-use num_traits::*;
-use std::usize;
-
-use crate::error::*;
+use instructions::*;
+use state::{GameConfig, Tile};
 
 declare_id!("mBuuWhoYBjXk2vqCUL25CU9zPkn4GE9QdvBfTkDcudj");
 
@@ -14,133 +15,95 @@ declare_id!("mBuuWhoYBjXk2vqCUL25CU9zPkn4GE9QdvBfTkDcudj");
 pub mod anchor_tic_tac_toe {
     use super::*;
 
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("Greetings from: {:?}", ctx.program_id);
-        Ok(())
+    /// Configure the treasury and fee taken from wagered games
+    pub fn initialize_house(
+        ctx: Context<InitializeHouse>,
+        treasury: Pubkey,
+        fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.initialize_house(treasury, fee_bps, &ctx.bumps)
     }
-}
 
-#[derive(Accounts)]
-pub struct Initialize {}
+    pub fn update_house(ctx: Context<UpdateHouse>, treasury: Pubkey, fee_bps: u16) -> Result<()> {
+        ctx.accounts.update_house(treasury, fee_bps)
+    }
 
-// State Accounts
-#[account]
-pub struct Game {
-    pub players: [Pubkey; 2],          // 32*2
-    pub turn: u8,                      // 1
-    pub board: [[Option<Sign>; 3]; 3], //  9 * (1+1) = 18
-    pub state: GameState,              // 32 + 1
-}
+    /// Player one creates the game, invites player two and escrows the stake
+    pub fn setup_game(
+        ctx: Context<SetupGame>,
+        game_id: u64,
+        player_two: Pubkey,
+        config: GameConfig,
+    ) -> Result<()> {
+        ctx.accounts
+            .setup_game(game_id, player_two, config, &ctx.bumps)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum GameState {
-    Active,
-    Tie,
-    Won { winner: Pubkey },
-}
+    pub fn create_profile(ctx: Context<CreateProfile>) -> Result<()> {
+        ctx.accounts.create_profile(&ctx.bumps)
+    }
 
-#[derive(
-    AnchorSerialize, AnchorDeserialize, FromPrimitive, ToPrimitive, Copy, Clone, PartialEq, Eq,
-)]
-pub enum Sign {
-    X,
-    O,
-}
+    pub fn initialize_lobby(ctx: Context<InitializeLobby>) -> Result<()> {
+        ctx.accounts.initialize_lobby(&ctx.bumps)
+    }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct Tile {
-    row: u8,
-    column: u8,
-}
+    /// Post an open, rated challenge to the lobby and escrow the stake
+    pub fn post_challenge(
+        ctx: Context<PostChallenge>,
+        game_id: u64,
+        config: GameConfig,
+    ) -> Result<()> {
+        ctx.accounts.post_challenge(game_id, config)
+    }
+
+    /// Accept a lobby challenge, creating the game
+    pub fn accept_challenge(
+        ctx: Context<AcceptChallenge>,
+        challenger: Pubkey,
+        game_id: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .accept_challenge(challenger, game_id, &ctx.bumps)
+    }
+
+    /// Withdraw an unaccepted challenge and its stake
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>, game_id: u64) -> Result<()> {
+        ctx.accounts.cancel_challenge(game_id, &ctx.bumps)
+    }
 
-// Game Logic
-impl Game {
-    pub const MAXIMUM_SIZE: usize = (32 * 2) + 1 + (9 * (1 + 1)) + 32 + 1;
-    pub fn start(&mut self, players: [Pubkey; 2]) -> Result<()> {
-        require_eq!(self.turn, 0, TicTacToeError::GameAlreadyStarted);
-        self.players = players;
-        self.turn = 1;
-        Ok(())
+    /// Anyone clears a challenge left open past its TTL, refunding the stake
+    pub fn expire_challenge(ctx: Context<ExpireChallenge>, game_id: u64) -> Result<()> {
+        ctx.accounts.expire_challenge(game_id, &ctx.bumps)
     }
 
-    pub fn is_active(&self) -> bool {
-        self.state == GameState::Active
+    /// Player two matches the stake of a wagered game
+    pub fn join_game(ctx: Context<JoinGame>) -> Result<()> {
+        ctx.accounts.join_game()
     }
 
-    pub fn current_player_index(&self) -> usize {
-        ((self.turn - 1) % 2) as usize
+    /// Player one withdraws an unanswered invite and their stake
+    pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
+        ctx.accounts.cancel_game()
     }
 
-    pub fn current_player(&self) -> Pubkey {
-        self.players[self.current_player_index()]
+    /// Place the current player's sign on `tile`
+    pub fn play(ctx: Context<Play>, tile: Tile) -> Result<()> {
+        ctx.accounts.play(tile)
     }
 
-    pub fn play(&mut self, tile: &Tile) -> Result<()> {
-        require!(self.is_active(), TicTacToeError::GameAlreadyOver);
-
-        match tile {
-            tile @ Tile {
-                row: 0..=2,
-                column: 0..=2,
-            } => match self.board[tile.row as usize][tile.column as usize] {
-                Some(_) => return Err(TicTacToeError::TileAlreadySet.into()),
-                None => {
-                    self.board[tile.row as usize][tile.column as usize] =
-                        Some(Sign::from_usize(self.current_player_index()).unwrap());
-                }
-            },
-            _ => return Err(TicTacToeError::TileOutOfBounds.into()),
-        }
-
-        self.update_state();
-
-        if GameState::Active == self.state {
-            self.turn += 1;
-        }
-        Ok(())
+    /// Give up; the opponent wins
+    pub fn resign(ctx: Context<Resign>) -> Result<()> {
+        ctx.accounts.resign()
     }
 
-    fn is_winning_trio(&self, trio: [(usize, usize); 3]) -> bool {
-        let [first, second, third] = trio;
-        self.board[first.0][first.1].is_some()
-            && self.board[first.0][first.1] == self.board[second.0][second.1]
-            && self.board[first.0][first.1] == self.board[third.0][third.1]
+    /// Win against an opponent who let their turn time out
+    pub fn claim_timeout(ctx: Context<ClaimTimeout>) -> Result<()> {
+        ctx.accounts.claim_timeout()
     }
 
-    fn update_state(&mut self) {
-        for i in 0..=2 {
-            if self.is_winning_trio([(i, 0), (i, 1), (i, 2)]) {
-                self.state = GameState::Won {
-                    winner: self.current_player(),
-                };
-                return;
-            }
-
-            if self.is_winning_trio([(0, i), (1, i), (2, i)]) {
-                self.state = GameState::Won {
-                    winner: self.current_player(),
-                };
-                return;
-            }
-        }
-
-        if self.is_winning_trio([(0, 0), (1, 1), (2, 2)])
-            || self.is_winning_trio([(0, 2), (1, 1), (2, 0)])
-        {
-            self.state = GameState::Won {
-                winner: self.current_player(),
-            };
-            return;
-        }
-
-        for row in 0..=2 {
-            for column in 0..=2 {
-                if self.board[row][column].is_none() {
-                    return;
-                }
-            }
-        }
-
-        self.state = GameState::Tie;
+    /// Pay out a finished game's pot, update ratings of a rated game and
+    /// close it
+    pub fn settle_game(ctx: Context<SettleGame>) -> Result<()> {
+        ctx.accounts.settle_game()
     }
 }
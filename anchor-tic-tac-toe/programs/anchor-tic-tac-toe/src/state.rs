@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+
+use crate::elo::{rating_change, Outcome};
+use crate::error::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Game {
+    pub players: [Pubkey; 2],
+    pub turn: u8,
+    pub board_size: u8,
+    pub win_length: u8,
+    /// Row-major `board_size * board_size` tiles
+    #[max_len(225)]
+    pub board: Vec<Option<Sign>>,
+    pub state: GameState,
+    pub game_id: u64,
+    pub turn_timeout: i64,
+    pub last_move_at: i64,
+    pub bump: u8,
+    pub stake: u64,
+    pub stake_mint: Option<Pubkey>,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub joined: bool,
+    pub vault_bump: u8,
+    /// Lobby games update both players' profiles when settled
+    pub rated: bool,
+    /// Paid the game account's rent, refunded when it closes
+    pub rent_payer: Pubkey,
+}
+
+/// House settings applied to every wagered game at setup
+#[account]
+#[derive(InitSpace)]
+pub struct House {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
+
+impl House {
+    pub const SEED_PREFIX: &'static [u8] = b"house";
+    /// The house never takes more than 10% of a pot
+    pub const MAX_FEE_BPS: u16 = 1_000;
+}
+
+/// A player's record and Elo rating across rated games
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub player: Pubkey,
+    pub rating: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub bump: u8,
+}
+
+impl Profile {
+    pub const SEED_PREFIX: &'static [u8] = b"profile";
+    pub const INITIAL_RATING: u32 = 1200;
+
+    pub fn record(&mut self, opponent_rating: u32, outcome: Outcome) {
+        let change = rating_change(self.rating, opponent_rating, outcome);
+        self.rating = self.rating.saturating_add_signed(change);
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Loss => self.losses += 1,
+            Outcome::Tie => self.ties += 1,
+        }
+    }
+}
+
+/// Open challenges waiting for an opponent
+#[account]
+#[derive(InitSpace)]
+pub struct Lobby {
+    #[max_len(32)]
+    pub challenges: Vec<Challenge>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Debug)]
+pub struct Challenge {
+    pub challenger: Pubkey,
+    /// Id of the game the challenge creates once accepted
+    pub game_id: u64,
+    pub config: GameConfig,
+    pub stake_mint: Option<Pubkey>,
+    pub posted_at: i64,
+}
+
+impl Lobby {
+    pub const SEED_PREFIX: &'static [u8] = b"lobby";
+    /// Keep `#[max_len]` on `challenges` in sync
+    pub const MAX_CHALLENGES: usize = 32;
+    /// After a day anyone can clear a challenge, refunding its stake
+    pub const CHALLENGE_TTL: i64 = 24 * 60 * 60;
+
+    pub fn find(&self, challenger: &Pubkey, game_id: u64) -> Option<usize> {
+        self.challenges
+            .iter()
+            .position(|c| c.challenger == *challenger && c.game_id == game_id)
+    }
+
+    pub fn post(&mut self, challenge: Challenge) -> Result<()> {
+        // One open challenge per wallet, so no one can fill the lobby alone
+        require!(
+            self.challenges
+                .iter()
+                .all(|c| c.challenger != challenge.challenger),
+            TicTacToeError::ChallengeAlreadyPosted
+        );
+        require!(
+            self.challenges.len() < Self::MAX_CHALLENGES,
+            TicTacToeError::LobbyFull
+        );
+        self.challenges.push(challenge);
+        Ok(())
+    }
+
+    pub fn take(&mut self, challenger: &Pubkey, game_id: u64) -> Result<Challenge> {
+        let index = self
+            .find(challenger, game_id)
+            .ok_or(TicTacToeError::ChallengeNotFound)?;
+        Ok(self.challenges.remove(index))
+    }
+
+    /// Take a challenge that has waited longer than `CHALLENGE_TTL`
+    pub fn take_expired(
+        &mut self,
+        challenger: &Pubkey,
+        game_id: u64,
+        now: i64,
+    ) -> Result<Challenge> {
+        let index = self
+            .find(challenger, game_id)
+            .ok_or(TicTacToeError::ChallengeNotFound)?;
+        require!(
+            now >= self.challenges[index].posted_at + Self::CHALLENGE_TTL,
+            TicTacToeError::ChallengeNotExpired
+        );
+        Ok(self.challenges.remove(index))
+    }
+}
+
+/// How a finished game's pot is split
+#[derive(Debug, PartialEq, Eq)]
+pub struct Payouts {
+    pub player_one: u64,
+    pub player_two: u64,
+    pub fee: u64,
+}
+
+/// Board, timing and stake chosen by player one at setup
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug)]
+pub struct GameConfig {
+    pub turn_timeout: i64,
+    pub stake: u64,
+    pub board_size: u8,
+    pub win_length: u8,
+}
+
+impl GameConfig {
+    pub fn validate(&self, stake_mint: Option<Pubkey>) -> Result<()> {
+        require!(self.turn_timeout > 0, TicTacToeError::InvalidTurnTimeout);
+        require!(
+            (Game::MIN_BOARD_SIZE..=Game::MAX_BOARD_SIZE).contains(&self.board_size),
+            TicTacToeError::InvalidBoardSize
+        );
+        require!(
+            (Game::MIN_BOARD_SIZE..=self.board_size).contains(&self.win_length),
+            TicTacToeError::InvalidWinLength
+        );
+        require!(
+            self.stake.checked_mul(2).is_some(),
+            TicTacToeError::InvalidStake
+        );
+        require!(
+            self.stake > 0 || stake_mint.is_none(),
+            TicTacToeError::InvalidStake
+        );
+
+        if stake_mint.is_none() && self.stake > 0 {
+            // A zero-data system account must be rent exempt once funded
+            require!(
+                self.stake >= Rent::get()?.minimum_balance(0),
+                TicTacToeError::StakeBelowRentExemption
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, PartialEq, Eq, Debug)]
+pub enum GameState {
+    Active,
+    Tie,
+    Won { winner: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Sign {
+    X,
+    O,
+}
+
+impl Sign {
+    pub fn from_player_index(index: usize) -> Self {
+        match index {
+            0 => Sign::X,
+            _ => Sign::O,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct Tile {
+    pub row: u8,
+    pub column: u8,
+}
+
+// Game Logic
+impl Game {
+    /// Largest supported board is 15x15 (gomoku); keep `#[max_len]` on
+    /// `board` in sync
+    pub const MAX_BOARD_SIZE: u8 = 15;
+    pub const MIN_BOARD_SIZE: u8 = 3;
+    pub const SEED_PREFIX: &'static [u8] = b"game";
+    /// System account holding SOL stakes
+    pub const VAULT_SEED: &'static [u8] = b"vault";
+    /// Token account holding SPL stakes, owned by the game PDA
+    pub const TOKEN_VAULT_SEED: &'static [u8] = b"token_vault";
+
+    pub fn start(&mut self, players: [Pubkey; 2], config: &GameConfig) -> Result<()> {
+        require_eq!(self.turn, 0, TicTacToeError::GameAlreadyStarted);
+        require_keys_neq!(
+            players[0],
+            players[1],
+            TicTacToeError::CannotPlayAgainstSelf
+        );
+        self.players = players;
+        self.turn = 1;
+        self.board_size = config.board_size;
+        self.win_length = config.win_length;
+        self.board = vec![None; config.board_size as usize * config.board_size as usize];
+        self.turn_timeout = config.turn_timeout;
+        self.stake = config.stake;
+        Ok(())
+    }
+
+    pub fn tile(&self, row: usize, column: usize) -> Option<Sign> {
+        self.board[row * self.board_size as usize + column]
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state == GameState::Active
+    }
+
+    pub fn current_player_index(&self) -> usize {
+        ((self.turn - 1) % 2) as usize
+    }
+
+    pub fn current_player(&self) -> Pubkey {
+        self.players[self.current_player_index()]
+    }
+
+    pub fn player_index(&self, player: &Pubkey) -> Result<usize> {
+        self.players
+            .iter()
+            .position(|p| p == player)
+            .ok_or_else(|| TicTacToeError::NotAPlayer.into())
+    }
+
+    /// Total of both players' stakes
+    pub fn pot(&self) -> u64 {
+        self.stake * 2
+    }
+
+    /// Each player's result in a finished game
+    pub fn outcomes(&self) -> Result<[Outcome; 2]> {
+        match self.state {
+            GameState::Active => err!(TicTacToeError::GameNotOver),
+            GameState::Tie => Ok([Outcome::Tie, Outcome::Tie]),
+            GameState::Won { winner } if winner == self.players[0] => {
+                Ok([Outcome::Win, Outcome::Loss])
+            }
+            GameState::Won { .. } => Ok([Outcome::Loss, Outcome::Win]),
+        }
+    }
+
+    /// Split the pot of a finished game: the winner takes it all (a timeout
+    /// forfeit is a win for the waiting player), a tie splits it, and the
+    /// house fee comes off the top either way
+    pub fn payouts(&self) -> Result<Payouts> {
+        let pot = self.pot();
+        let fee = (pot as u128 * self.fee_bps as u128 / 10_000) as u64;
+        let prize = pot - fee;
+
+        let (player_one, player_two) = match self.outcomes()? {
+            [Outcome::Win, _] => (prize, 0),
+            [_, Outcome::Win] => (0, prize),
+            _ => (prize - prize / 2, prize / 2),
+        };
+
+        Ok(Payouts {
+            player_one,
+            player_two,
+            fee,
+        })
+    }
+
+    pub fn play(&mut self, tile: &Tile) -> Result<()> {
+        require!(self.is_active(), TicTacToeError::GameAlreadyOver);
+        require!(self.joined, TicTacToeError::GameNotJoined);
+
+        let size = self.board_size as usize;
+        let (row, column) = (tile.row as usize, tile.column as usize);
+        require!(row < size && column < size, TicTacToeError::TileOutOfBounds);
+        let index = row * size + column;
+        require!(self.board[index].is_none(), TicTacToeError::TileAlreadySet);
+        self.board[index] = Some(Sign::from_player_index(self.current_player_index()));
+
+        self.update_state(row, column);
+
+        if GameState::Active == self.state {
+            self.turn += 1;
+        }
+        Ok(())
+    }
+
+    /// `player` gives up; the opponent wins
+    pub fn resign(&mut self, player: &Pubkey) -> Result<()> {
+        require!(self.is_active(), TicTacToeError::GameAlreadyOver);
+        require!(self.joined, TicTacToeError::GameNotJoined);
+        let index = self.player_index(player)?;
+        self.state = GameState::Won {
+            winner: self.players[1 - index],
+        };
+        Ok(())
+    }
+
+    /// `claimant` wins because the player to move let the turn timeout pass
+    pub fn claim_timeout(&mut self, claimant: &Pubkey, now: i64) -> Result<()> {
+        require!(self.is_active(), TicTacToeError::GameAlreadyOver);
+        require!(self.joined, TicTacToeError::GameNotJoined);
+        let index = self.player_index(claimant)?;
+        require!(
+            index != self.current_player_index(),
+            TicTacToeError::NotPlayersTurn
+        );
+        require!(
+            now > self.last_move_at.saturating_add(self.turn_timeout),
+            TicTacToeError::TurnNotTimedOut
+        );
+        self.state = GameState::Won { winner: *claimant };
+        Ok(())
+    }
+
+    /// Number of consecutive `sign` tiles walking from (`row`, `column`) in
+    /// direction (`dr`, `dc`), not counting the starting tile
+    fn run_length(&self, row: usize, column: usize, (dr, dc): (isize, isize), sign: Sign) -> u8 {
+        let size = self.board_size as isize;
+        let (mut r, mut c) = (row as isize + dr, column as isize + dc);
+        let mut length = 0;
+        while r >= 0 && r < size && c >= 0 && c < size {
+            if self.tile(r as usize, c as usize) != Some(sign) {
+                break;
+            }
+            length += 1;
+            r += dr;
+            c += dc;
+        }
+        length
+    }
+
+    /// Only lines through the last move can have been completed by it, so
+    /// check the four directions around it rather than the whole board
+    fn update_state(&mut self, row: usize, column: usize) {
+        let sign = Sign::from_player_index(self.current_player_index());
+
+        for (dr, dc) in [(0, 1), (1, 0), (1, 1), (1, -1)] {
+            let line = 1
+                + self.run_length(row, column, (dr, dc), sign)
+                + self.run_length(row, column, (-dr, -dc), sign);
+            if line >= self.win_length {
+                self.state = GameState::Won {
+                    winner: self.current_player(),
+                };
+                return;
+            }
+        }
+
+        // Every move fills one tile, so the board is full on the last turn
+        if self.turn as usize == self.board.len() {
+            self.state = GameState::Tie;
+        }
+    }
+}
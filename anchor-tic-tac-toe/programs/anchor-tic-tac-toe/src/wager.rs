@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::*;
+use crate::state::*;
+
+/// Token accounts needed to move an SPL stake in or out of a game
+pub struct TokenStake<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenStake<'a, 'info> {
+    /// Collect the optional token accounts of an instruction. Returns `None`
+    /// for stakes in SOL; all accounts must be present otherwise.
+    pub fn new(
+        stake_mint: Option<Pubkey>,
+        mint: Option<&'a InterfaceAccount<'info, Mint>>,
+        vault: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+        token_program: Option<&'a Interface<'info, TokenInterface>>,
+    ) -> Result<Option<Self>> {
+        let Some(stake_mint) = stake_mint else {
+            return Ok(None);
+        };
+
+        match (mint, vault, token_program) {
+            (Some(mint), Some(vault), Some(token_program)) => {
+                require_keys_eq!(mint.key(), stake_mint, TicTacToeError::StakeMintMismatch);
+                Ok(Some(Self {
+                    mint,
+                    vault,
+                    token_program,
+                }))
+            }
+            _ => err!(TicTacToeError::MissingStakeAccounts),
+        }
+    }
+}
+
+/// The game PDA and vault bumps that sign for a game's escrow
+///
+/// A lobby challenge escrows into the vaults of the game it will create, so
+/// the game account need not exist yet.
+pub struct Escrow<'info> {
+    pub game: AccountInfo<'info>,
+    pub player_one: Pubkey,
+    pub game_id: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl<'info> Escrow<'info> {
+    pub fn for_game(game: &Account<'info, Game>) -> Self {
+        Self {
+            game: game.to_account_info(),
+            player_one: game.players[0],
+            game_id: game.game_id,
+            bump: game.bump,
+            vault_bump: game.vault_bump,
+        }
+    }
+
+    /// Pay `amount` out of the vault to `recipient`
+    ///
+    /// SOL leaves the system-owned vault, signed by the vault's own seeds.
+    /// Tokens leave the token vault, signed by the game PDA.
+    pub fn pay_out(
+        &self,
+        vault: &SystemAccount<'info>,
+        recipient: &AccountInfo<'info>,
+        recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+        system_program: &Program<'info, System>,
+        token_stake: Option<&TokenStake<'_, 'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        match token_stake {
+            None => {
+                let game_key = self.game.key();
+                let signer_seeds: &[&[&[u8]]] =
+                    &[&[Game::VAULT_SEED, game_key.as_ref(), &[self.vault_bump]]];
+                let cpi_accounts = Transfer {
+                    from: vault.to_account_info(),
+                    to: recipient.clone(),
+                };
+                let cpi_context = CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                system_program::transfer(cpi_context, amount)?;
+            }
+            Some(token_stake) => {
+                let to = recipient_token_account.ok_or(TicTacToeError::MissingStakeAccounts)?;
+                let game_id = self.game_id.to_le_bytes();
+                let signer_seeds: &[&[&[u8]]] = &[&[
+                    Game::SEED_PREFIX,
+                    self.player_one.as_ref(),
+                    &game_id,
+                    &[self.bump],
+                ]];
+                let cpi_accounts = TransferChecked {
+                    mint: token_stake.mint.to_account_info(),
+                    from: token_stake.vault.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: self.game.clone(),
+                };
+                let cpi_context = CpiContext::new_with_signer(
+                    token_stake.token_program.to_account_info(),
+                    cpi_accounts,
+                    signer_seeds,
+                );
+                token_interface::transfer_checked(cpi_context, amount, token_stake.mint.decimals)?;
+            }
+        }
+
+        msg!("{} paid to {}", amount, recipient.key());
+
+        Ok(())
+    }
+
+    /// Close the emptied token vault, returning its rent to `destination`
+    pub fn close_token_vault(
+        &self,
+        token_stake: &TokenStake<'_, 'info>,
+        destination: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let game_id = self.game_id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            Game::SEED_PREFIX,
+            self.player_one.as_ref(),
+            &game_id,
+            &[self.bump],
+        ]];
+        let cpi_accounts = CloseAccount {
+            account: token_stake.vault.to_account_info(),
+            destination: destination.clone(),
+            authority: self.game.clone(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            token_stake.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token_interface::close_account(cpi_context)
+    }
+}
+
+/// Move `amount` from `depositor` into a game's vault
+pub fn deposit_stake<'info>(
+    depositor: &Signer<'info>,
+    depositor_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    vault: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+    token_stake: Option<&TokenStake<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match token_stake {
+        None => {
+            let cpi_accounts = Transfer {
+                from: depositor.to_account_info(),
+                to: vault.to_account_info(),
+            };
+            let cpi_context = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_context, amount)?;
+        }
+        Some(token_stake) => {
+            let from = depositor_token_account.ok_or(TicTacToeError::MissingStakeAccounts)?;
+            let cpi_accounts = TransferChecked {
+                mint: token_stake.mint.to_account_info(),
+                from: from.to_account_info(),
+                to: token_stake.vault.to_account_info(),
+                authority: depositor.to_account_info(),
+            };
+            let cpi_context =
+                CpiContext::new(token_stake.token_program.to_account_info(), cpi_accounts);
+            token_interface::transfer_checked(cpi_context, amount, token_stake.mint.decimals)?;
+        }
+    }
+
+    msg!("{} staked {}", depositor.key(), amount);
+
+    Ok(())
+}
@@ -1,16 +1,728 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { AnchorError, BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { expect } from "chai";
 import { AnchorTicTacToe } from "../target/types/anchor_tic_tac_toe";
 
+type Tile = { row: number; column: number };
+
 describe("anchor-tic-tac-toe", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.AnchorTicTacToe as Program<AnchorTicTacToe>;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  const playerOne = Keypair.generate();
+  const playerTwo = Keypair.generate();
+  const treasury = Keypair.generate();
+  const TURN_TIMEOUT = new BN(3600);
+  const FEE_BPS = 250;
+
+  const [house] = PublicKey.findProgramAddressSync([Buffer.from("house")], program.programId);
+
+  let nextGameId = 0;
+
+  const gamePda = (gameId: BN): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("game"),
+        playerOne.publicKey.toBuffer(),
+        gameId.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const vaultPda = (game: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), game.toBuffer()],
+      program.programId
+    )[0];
+
+  const tokenVaultPda = (game: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("token_vault"), game.toBuffer()],
+      program.programId
+    )[0];
+
+  // Token accounts of a wagered SPL game; all null for SOL or friendly games
+  type StakeAccounts = {
+    mint: PublicKey;
+    playerOne: PublicKey;
+    playerTwo: PublicKey;
+    treasury: PublicKey;
+  } | null;
+
+  type GameConfig = {
+    turnTimeout?: BN;
+    stake?: BN;
+    boardSize?: number;
+    winLength?: number;
+  };
+
+  // Classic 3x3 friendly game unless overridden
+  const gameConfig = ({
+    turnTimeout = TURN_TIMEOUT,
+    stake = new BN(0),
+    boardSize = 3,
+    winLength = 3,
+  }: GameConfig = {}) => ({ turnTimeout, stake, boardSize, winLength });
+
+  const setupGame = async (
+    config: GameConfig = {},
+    tokens: StakeAccounts = null
+  ): Promise<PublicKey> => {
+    const gameId = new BN(nextGameId++);
+    const game = gamePda(gameId);
+    await program.methods
+      .setupGame(gameId, playerTwo.publicKey, gameConfig(config))
+      .accountsStrict({
+        game,
+        house,
+        vault: vaultPda(game),
+        stakeMint: tokens?.mint ?? null,
+        tokenVault: tokens ? tokenVaultPda(game) : null,
+        playerOneTokenAccount: tokens?.playerOne ?? null,
+        playerOne: playerOne.publicKey,
+        tokenProgram: tokens ? TOKEN_PROGRAM_ID : null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([playerOne])
+      .rpc();
+    return game;
+  };
+
+  const joinGame = (game: PublicKey, tokens: StakeAccounts = null) =>
+    program.methods
+      .joinGame()
+      .accountsStrict({
+        game,
+        vault: vaultPda(game),
+        stakeMint: tokens?.mint ?? null,
+        tokenVault: tokens ? tokenVaultPda(game) : null,
+        playerTwoTokenAccount: tokens?.playerTwo ?? null,
+        playerTwo: playerTwo.publicKey,
+        tokenProgram: tokens ? TOKEN_PROGRAM_ID : null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([playerTwo])
+      .rpc();
+
+  const profilePda = (player: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("profile"), player.toBuffer()],
+      program.programId
+    )[0];
+
+  const [lobby] = PublicKey.findProgramAddressSync([Buffer.from("lobby")], program.programId);
+
+  const settleGame = (game: PublicKey, tokens: StakeAccounts = null, rated = false) =>
+    program.methods
+      .settleGame()
+      .accountsStrict({
+        game,
+        vault: vaultPda(game),
+        playerOne: playerOne.publicKey,
+        playerTwo: playerTwo.publicKey,
+        treasury: treasury.publicKey,
+        // Lobby games are rated, and created by player two accepting
+        rentPayer: rated ? playerTwo.publicKey : playerOne.publicKey,
+        stakeMint: tokens?.mint ?? null,
+        tokenVault: tokens ? tokenVaultPda(game) : null,
+        playerOneTokenAccount: tokens?.playerOne ?? null,
+        playerTwoTokenAccount: tokens?.playerTwo ?? null,
+        treasuryTokenAccount: tokens?.treasury ?? null,
+        playerOneProfile: rated ? profilePda(playerOne.publicKey) : null,
+        playerTwoProfile: rated ? profilePda(playerTwo.publicKey) : null,
+        tokenProgram: tokens ? TOKEN_PROGRAM_ID : null,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  const balance = (key: PublicKey) => provider.connection.getBalance(key);
+
+  const play = (game: PublicKey, player: Keypair, tile: Tile) =>
+    program.methods
+      .play(tile)
+      .accountsStrict({ game, player: player.publicKey })
+      .signers([player])
+      .rpc();
+
+  // Alternates X (player one) and O (player two), starting with X
+  const playMoves = async (game: PublicKey, moves: Tile[]) => {
+    for (const [i, tile] of moves.entries()) {
+      await play(game, i % 2 === 0 ? playerOne : playerTwo, tile);
+    }
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`expected ${code}`);
+    } catch (err) {
+      expect(err).to.be.instanceOf(AnchorError);
+      expect((err as AnchorError).error.errorCode.code).to.equal(code);
+    }
+  };
+
+  const t = (row: number, column: number): Tile => ({ row, column });
+
+  before(async () => {
+    // The treasury is funded so small fees keep it rent exempt
+    for (const player of [playerOne, playerTwo, treasury]) {
+      const sig = await provider.connection.requestAirdrop(
+        player.publicKey,
+        10 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    await program.methods
+      .initializeHouse(treasury.publicKey, FEE_BPS)
+      .accountsStrict({
+        house,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("sets up a game between two players", async () => {
+    const game = await setupGame();
+    const state = await program.account.game.fetch(game);
+
+    expect(state.players[0].toBase58()).to.equal(playerOne.publicKey.toBase58());
+    expect(state.players[1].toBase58()).to.equal(playerTwo.publicKey.toBase58());
+    expect(state.turn).to.equal(1);
+    expect(state.state).to.deep.equal({ active: {} });
+    expect(state.turnTimeout.toNumber()).to.equal(TURN_TIMEOUT.toNumber());
+    expect(state.boardSize).to.equal(3);
+    expect(state.winLength).to.equal(3);
+    expect(state.board).to.have.length(9);
+  });
+
+  it("rejects inviting yourself", async () => {
+    const gameId = new BN(nextGameId++);
+    await expectError(
+      program.methods
+        .setupGame(gameId, playerOne.publicKey, gameConfig())
+        .accountsStrict({
+          game: gamePda(gameId),
+          house,
+          vault: vaultPda(gamePda(gameId)),
+          stakeMint: null,
+          tokenVault: null,
+          playerOneTokenAccount: null,
+          playerOne: playerOne.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerOne])
+        .rpc(),
+      "CannotPlayAgainstSelf"
+    );
+  });
+
+  // X fills the line; O plays the filler tiles, which never complete a line
+  const winLines: [string, Tile[], Tile[]][] = [
+    ["row 0", [t(0, 0), t(0, 1), t(0, 2)], [t(1, 0), t(1, 1)]],
+    ["row 1", [t(1, 0), t(1, 1), t(1, 2)], [t(0, 0), t(0, 1)]],
+    ["row 2", [t(2, 0), t(2, 1), t(2, 2)], [t(0, 0), t(0, 1)]],
+    ["column 0", [t(0, 0), t(1, 0), t(2, 0)], [t(0, 1), t(1, 1)]],
+    ["column 1", [t(0, 1), t(1, 1), t(2, 1)], [t(0, 0), t(1, 0)]],
+    ["column 2", [t(0, 2), t(1, 2), t(2, 2)], [t(0, 0), t(1, 0)]],
+    ["diagonal", [t(0, 0), t(1, 1), t(2, 2)], [t(0, 1), t(0, 2)]],
+    ["anti-diagonal", [t(0, 2), t(1, 1), t(2, 0)], [t(0, 0), t(0, 1)]],
+  ];
+
+  for (const [name, line, filler] of winLines) {
+    it(`player one wins on ${name}`, async () => {
+      const game = await setupGame();
+      await playMoves(game, [line[0], filler[0], line[1], filler[1], line[2]]);
+
+      const state = await program.account.game.fetch(game);
+      expect(state.state).to.deep.equal({
+        won: { winner: playerOne.publicKey },
+      });
+      expect(state.turn).to.equal(5);
+
+      await expectError(play(game, playerTwo, t(2, 2)), "GameAlreadyOver");
+    });
+  }
+
+  it("player two can win", async () => {
+    const game = await setupGame();
+    await playMoves(game, [t(0, 0), t(1, 0), t(0, 1), t(1, 1), t(2, 2), t(1, 2)]);
+
+    const state = await program.account.game.fetch(game);
+    expect(state.state).to.deep.equal({
+      won: { winner: playerTwo.publicKey },
+    });
+  });
+
+  it("ends in a tie when the board fills without a line", async () => {
+    const game = await setupGame();
+    // X O X
+    // X O O
+    // O X X
+    await playMoves(game, [
+      t(0, 0),
+      t(0, 1),
+      t(0, 2),
+      t(1, 1),
+      t(1, 0),
+      t(1, 2),
+      t(2, 1),
+      t(2, 0),
+      t(2, 2),
+    ]);
+
+    const state = await program.account.game.fetch(game);
+    expect(state.state).to.deep.equal({ tie: {} });
+    expect(state.turn).to.equal(9);
+  });
+
+  it("rejects out-of-turn moves", async () => {
+    const game = await setupGame();
+
+    await expectError(play(game, playerTwo, t(0, 0)), "NotPlayersTurn");
+    await play(game, playerOne, t(0, 0));
+    await expectError(play(game, playerOne, t(1, 1)), "NotPlayersTurn");
+
+    const outsider = Keypair.generate();
+    await expectError(play(game, outsider, t(1, 1)), "NotPlayersTurn");
+  });
+
+  it("rejects taken and out-of-bounds tiles", async () => {
+    const game = await setupGame();
+    await play(game, playerOne, t(0, 0));
+
+    await expectError(play(game, playerTwo, t(0, 0)), "TileAlreadySet");
+    await expectError(play(game, playerTwo, t(3, 0)), "TileOutOfBounds");
+  });
+
+  it("resigning hands the win to the opponent", async () => {
+    const game = await setupGame();
+    await play(game, playerOne, t(1, 1));
+
+    // Either player may resign, even out of turn
+    await program.methods
+      .resign()
+      .accountsStrict({ game, player: playerOne.publicKey })
+      .signers([playerOne])
+      .rpc();
+
+    const state = await program.account.game.fetch(game);
+    expect(state.state).to.deep.equal({
+      won: { winner: playerTwo.publicKey },
+    });
+  });
+
+  it("only the waiting player can claim a timeout, once it has passed", async () => {
+    const game = await setupGame({ turnTimeout: new BN(1) });
+    await play(game, playerOne, t(1, 1));
+
+    const claimTimeout = (player: Keypair) =>
+      program.methods
+        .claimTimeout()
+        .accountsStrict({ game, player: player.publicKey })
+        .signers([player])
+        .rpc();
+
+    // The stalling player can't claim against their opponent
+    await expectError(claimTimeout(playerTwo), "NotPlayersTurn");
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await claimTimeout(playerOne);
+
+    const state = await program.account.game.fetch(game);
+    expect(state.state).to.deep.equal({
+      won: { winner: playerOne.publicKey },
+    });
+  });
+
+  it("a timeout can't be claimed early", async () => {
+    const game = await setupGame();
+    await play(game, playerOne, t(1, 1));
+
+    await expectError(
+      program.methods
+        .claimTimeout()
+        .accountsStrict({ game, player: playerOne.publicKey })
+        .signers([playerOne])
+        .rpc(),
+      "TurnNotTimedOut"
+    );
+  });
+
+  describe("larger boards", () => {
+    it("plays five in a row on a 15x15 board", async () => {
+      const game = await setupGame({ boardSize: 15, winLength: 5 });
+      // X builds a diagonal from (5, 5); O plays along the top edge
+      const moves: Tile[] = [];
+      for (let i = 0; i < 5; i++) {
+        moves.push(t(5 + i, 5 + i));
+        if (i < 4) moves.push(t(0, i));
+      }
+
+      await playMoves(game, moves.slice(0, -1));
+      expect((await program.account.game.fetch(game)).state).to.deep.equal({ active: {} });
+
+      await play(game, playerOne, moves[moves.length - 1]);
+      expect((await program.account.game.fetch(game)).state).to.deep.equal({
+        won: { winner: playerOne.publicKey },
+      });
+    });
+
+    it("detects a line completed in its middle", async () => {
+      const game = await setupGame({ boardSize: 4, winLength: 3 });
+      // X fills (3, 0) and (3, 2) first, then joins them with (3, 1)
+      await playMoves(game, [t(3, 0), t(0, 0), t(3, 2), t(0, 1), t(3, 1)]);
+
+      expect((await program.account.game.fetch(game)).state).to.deep.equal({
+        won: { winner: playerOne.publicKey },
+      });
+    });
+
+    it("rejects tiles outside the board", async () => {
+      const game = await setupGame({ boardSize: 4, winLength: 4 });
+      await play(game, playerOne, t(3, 3));
+      await expectError(play(game, playerTwo, t(4, 0)), "TileOutOfBounds");
+    });
+
+    it("rejects unsupported board sizes and win lengths", async () => {
+      for (const [config, code] of [
+        [{ boardSize: 16, winLength: 5 }, "InvalidBoardSize"],
+        [{ boardSize: 2, winLength: 2 }, "InvalidBoardSize"],
+        [{ boardSize: 5, winLength: 6 }, "InvalidWinLength"],
+      ] as [GameConfig, string][]) {
+        await expectError(setupGame(config), code);
+      }
+    });
+  });
+
+  describe("wagers", () => {
+    const STAKE = new BN(LAMPORTS_PER_SOL);
+    const POT = 2 * LAMPORTS_PER_SOL;
+    const FEE = (POT * FEE_BPS) / 10_000;
+
+    it("the invited player must match the stake before play starts", async () => {
+      const game = await setupGame({ stake: STAKE });
+      expect(await balance(vaultPda(game))).to.equal(STAKE.toNumber());
+
+      await expectError(play(game, playerOne, t(1, 1)), "GameNotJoined");
+
+      await joinGame(game);
+      expect(await balance(vaultPda(game))).to.equal(POT);
+      await expectError(joinGame(game), "GameAlreadyJoined");
+
+      await play(game, playerOne, t(1, 1));
+    });
+
+    it("the winner sweeps the pot less the house fee", async () => {
+      const game = await setupGame({ stake: STAKE });
+      await joinGame(game);
+      await playMoves(game, [t(0, 0), t(1, 0), t(0, 1), t(1, 1), t(0, 2)]);
+
+      const playerOneBefore = await balance(playerOne.publicKey);
+      const playerTwoBefore = await balance(playerTwo.publicKey);
+      const treasuryBefore = await balance(treasury.publicKey);
+      const gameRent = await balance(game);
+
+      await settleGame(game);
+
+      expect(await balance(playerOne.publicKey)).to.equal(
+        playerOneBefore + POT - FEE + gameRent
+      );
+      expect(await balance(playerTwo.publicKey)).to.equal(playerTwoBefore);
+      expect(await balance(treasury.publicKey)).to.equal(treasuryBefore + FEE);
+      expect(await balance(vaultPda(game))).to.equal(0);
+      expect(await provider.connection.getAccountInfo(game)).to.be.null;
+    });
+
+    it("a tie splits the pot", async () => {
+      const game = await setupGame({ stake: STAKE });
+      await joinGame(game);
+      await playMoves(game, [
+        t(0, 0),
+        t(0, 1),
+        t(0, 2),
+        t(1, 1),
+        t(1, 0),
+        t(1, 2),
+        t(2, 1),
+        t(2, 0),
+        t(2, 2),
+      ]);
+
+      const playerTwoBefore = await balance(playerTwo.publicKey);
+      const treasuryBefore = await balance(treasury.publicKey);
+
+      await settleGame(game);
+
+      expect(await balance(playerTwo.publicKey)).to.equal(
+        playerTwoBefore + Math.floor((POT - FEE) / 2)
+      );
+      expect(await balance(treasury.publicKey)).to.equal(treasuryBefore + FEE);
+    });
+
+    it("a timeout forfeit pays the waiting player", async () => {
+      const game = await setupGame({ turnTimeout: new BN(1), stake: STAKE });
+      await joinGame(game);
+      await play(game, playerOne, t(1, 1));
+
+      await expectError(settleGame(game), "GameNotOver");
+
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+      await program.methods
+        .claimTimeout()
+        .accountsStrict({ game, player: playerOne.publicKey })
+        .signers([playerOne])
+        .rpc();
+
+      const playerTwoBefore = await balance(playerTwo.publicKey);
+      const treasuryBefore = await balance(treasury.publicKey);
+
+      await settleGame(game);
+
+      expect(await balance(playerTwo.publicKey)).to.equal(playerTwoBefore);
+      expect(await balance(treasury.publicKey)).to.equal(treasuryBefore + FEE);
+    });
+
+    it("player one can cancel an unanswered invite", async () => {
+      const game = await setupGame({ stake: STAKE });
+
+      await program.methods
+        .cancelGame()
+        .accountsStrict({
+          game,
+          vault: vaultPda(game),
+          stakeMint: null,
+          tokenVault: null,
+          playerOneTokenAccount: null,
+          playerOne: playerOne.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerOne])
+        .rpc();
+
+      expect(await balance(vaultPda(game))).to.equal(0);
+      expect(await provider.connection.getAccountInfo(game)).to.be.null;
+    });
+
+    it("SPL stakes are escrowed and paid out in tokens", async () => {
+      const payer = (provider.wallet as anchor.Wallet).payer;
+      const mint = await createMint(provider.connection, payer, payer.publicKey, null, 6);
+      const [one, two, fees] = await Promise.all(
+        [playerOne, playerTwo, treasury].map((owner) =>
+          getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, owner.publicKey)
+        )
+      );
+      await mintTo(provider.connection, payer, mint, one.address, payer, 1_000_000);
+      await mintTo(provider.connection, payer, mint, two.address, payer, 1_000_000);
+
+      const tokens = {
+        mint,
+        playerOne: one.address,
+        playerTwo: two.address,
+        treasury: fees.address,
+      };
+      const tokenStake = new BN(1_000_000);
+      const game = await setupGame({ stake: tokenStake }, tokens);
+      await joinGame(game, tokens);
+      expect(
+        Number((await getAccount(provider.connection, tokenVaultPda(game))).amount)
+      ).to.equal(2_000_000);
+
+      await program.methods
+        .resign()
+        .accountsStrict({ game, player: playerOne.publicKey })
+        .signers([playerOne])
+        .rpc();
+      await settleGame(game, tokens);
+
+      const fee = (2_000_000 * FEE_BPS) / 10_000;
+      expect(Number((await getAccount(provider.connection, two.address)).amount)).to.equal(
+        2_000_000 - fee
+      );
+      expect(Number((await getAccount(provider.connection, fees.address)).amount)).to.equal(
+        fee
+      );
+      expect(await provider.connection.getAccountInfo(tokenVaultPda(game))).to.be.null;
+    });
+  });
+
+  describe("lobby and ratings", () => {
+    const STAKE = new BN(LAMPORTS_PER_SOL);
+
+    const postChallenge = async (config: GameConfig = {}): Promise<BN> => {
+      const gameId = new BN(nextGameId++);
+      const game = gamePda(gameId);
+      await program.methods
+        .postChallenge(gameId, gameConfig(config))
+        .accountsStrict({
+          lobby,
+          game,
+          vault: vaultPda(game),
+          stakeMint: null,
+          tokenVault: null,
+          challengerTokenAccount: null,
+          challengerProfile: profilePda(playerOne.publicKey),
+          challenger: playerOne.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerOne])
+        .rpc();
+      return gameId;
+    };
+
+    const acceptChallenge = async (gameId: BN): Promise<PublicKey> => {
+      const game = gamePda(gameId);
+      await program.methods
+        .acceptChallenge(playerOne.publicKey, gameId)
+        .accountsStrict({
+          lobby,
+          house,
+          game,
+          vault: vaultPda(game),
+          stakeMint: null,
+          tokenVault: null,
+          playerTwoTokenAccount: null,
+          challengerProfile: profilePda(playerOne.publicKey),
+          playerTwoProfile: profilePda(playerTwo.publicKey),
+          playerTwo: playerTwo.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerTwo])
+        .rpc();
+      return game;
+    };
+
+    before(async () => {
+      await program.methods
+        .initializeLobby()
+        .accountsStrict({
+          lobby,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      for (const player of [playerOne, playerTwo]) {
+        await program.methods
+          .createProfile()
+          .accountsStrict({
+            profile: profilePda(player.publicKey),
+            player: player.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player])
+          .rpc();
+      }
+    });
+
+    it("accepting a challenge creates a rated game and updates Elo on settlement", async () => {
+      const gameId = await postChallenge({ stake: STAKE, boardSize: 4, winLength: 3 });
+      const posted = await program.account.lobby.fetch(lobby);
+      expect(posted.challenges).to.have.length(1);
+      expect(posted.challenges[0].gameId.toNumber()).to.equal(gameId.toNumber());
+      expect(await balance(vaultPda(gamePda(gameId)))).to.equal(STAKE.toNumber());
+
+      const game = await acceptChallenge(gameId);
+      expect((await program.account.lobby.fetch(lobby)).challenges).to.have.length(0);
+      const state = await program.account.game.fetch(game);
+      expect(state.rated).to.be.true;
+      expect(state.joined).to.be.true;
+      expect(state.boardSize).to.equal(4);
+      expect(await balance(vaultPda(game))).to.equal(2 * STAKE.toNumber());
+
+      await playMoves(game, [t(0, 0), t(1, 0), t(0, 1), t(1, 1), t(0, 2)]);
+
+      await expectError(settleGame(game), "MissingProfiles");
+      await settleGame(game, null, true);
+
+      // Equal ratings: the winner gains half the K factor of 32
+      const one = await program.account.profile.fetch(profilePda(playerOne.publicKey));
+      const two = await program.account.profile.fetch(profilePda(playerTwo.publicKey));
+      expect([one.rating, one.wins, one.losses]).to.deep.equal([1216, 1, 0]);
+      expect([two.rating, two.wins, two.losses]).to.deep.equal([1184, 0, 1]);
+    });
+
+    it("a tie moves ratings toward each other", async () => {
+      const game = await acceptChallenge(await postChallenge());
+      await playMoves(game, [
+        t(0, 0),
+        t(0, 1),
+        t(0, 2),
+        t(1, 1),
+        t(1, 0),
+        t(1, 2),
+        t(2, 1),
+        t(2, 0),
+        t(2, 2),
+      ]);
+      await settleGame(game, null, true);
+
+      const one = await program.account.profile.fetch(profilePda(playerOne.publicKey));
+      const two = await program.account.profile.fetch(profilePda(playerTwo.publicKey));
+      expect(one.rating).to.be.below(1216);
+      expect(two.rating).to.be.above(1184);
+      expect(one.rating + two.rating).to.equal(2400);
+      expect([one.ties, two.ties]).to.deep.equal([1, 1]);
+    });
+
+    it("the challenger can withdraw an open challenge", async () => {
+      const gameId = await postChallenge({ stake: STAKE });
+      const game = gamePda(gameId);
+
+      await expectError(postChallenge(), "ChallengeAlreadyPosted");
+      await expectError(
+        program.methods
+          .expireChallenge(gameId)
+          .accountsStrict({
+            lobby,
+            game,
+            vault: vaultPda(game),
+            stakeMint: null,
+            tokenVault: null,
+            challengerTokenAccount: null,
+            challenger: playerOne.publicKey,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc(),
+        "ChallengeNotExpired"
+      );
+
+      await program.methods
+        .cancelChallenge(gameId)
+        .accountsStrict({
+          lobby,
+          game,
+          vault: vaultPda(game),
+          stakeMint: null,
+          tokenVault: null,
+          challengerTokenAccount: null,
+          challenger: playerOne.publicKey,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([playerOne])
+        .rpc();
+
+      expect((await program.account.lobby.fetch(lobby)).challenges).to.have.length(0);
+      expect(await balance(vaultPda(game))).to.equal(0);
+      await expectError(acceptChallenge(gameId), "ChallengeNotFound");
+    });
   });
 });
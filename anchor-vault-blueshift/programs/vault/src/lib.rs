@@ -13,10 +13,47 @@ pub mod blueshift_anchor_vault {
         Ok(())
     }
 
-    // Verifies the vault currently holds zero lamports (it must not already exist).
-    // Ensures the deposit amount exceeds the rent-exempt minimum for a SystemAccount.
+    // Tops up the signer's vault, creating it on the first deposit.
+    // A new vault must receive more than the rent-exempt minimum for a SystemAccount.
     // Transfers lamports from the signer to the vault via a CPI to the System Program.
+    // Instruction discriminators match the Pinocchio vault, so either program
+    // accepts the same instruction data.
+    #[instruction(discriminator = 0)]
     pub fn deposit(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
+        if ctx.accounts.vault.lamports() == 0 {
+            require_gt!(
+                amount,
+                Rent::get()?.minimum_balance(0),
+                VaultError::InvalidAmount
+            );
+        } else {
+            require_gt!(amount, 0, VaultError::InvalidAmount);
+        }
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    // Same as `deposit`, but into `beneficiary`'s vault and under a lock schedule.
+    // The beneficiary may be the depositor themselves (a plain timelock), and
+    // must sign so nobody can lock a vault they don't own.
+    // The schedule lives in a lock PDA next to the vault that `withdraw` enforces.
+    #[instruction(discriminator = 2)]
+    pub fn deposit_locked(
+        ctx: Context<DepositLocked>,
+        amount: u64,
+        schedule: LockSchedule,
+    ) -> Result<()> {
         require_eq!(
             ctx.accounts.vault.lamports(),
             0,
@@ -27,6 +64,7 @@ pub mod blueshift_anchor_vault {
             Rent::get()?.minimum_balance(0),
             VaultError::InvalidAmount
         );
+        schedule.validate()?;
 
         transfer(
             CpiContext::new(
@@ -39,16 +77,53 @@ pub mod blueshift_anchor_vault {
             amount,
         )?;
 
+        ctx.accounts.lock.set_inner(VaultLock {
+            depositor: ctx.accounts.signer.key(),
+            schedule,
+            total: amount,
+            withdrawn: 0,
+            bump: ctx.bumps.lock,
+        });
+
         Ok(())
     }
 
     // Uses the vault’s PDA to sign the transfer out of the vault on its own behalf.
-    // Transfers all lamports in the vault back to the signer.
+    // Transfers `amount` back to the signer, limited to the vested part when the
+    // vault is locked. The vault is either left rent exempt or emptied entirely.
+    // Emptying a locked vault closes its lock and refunds the rent to whoever
+    // paid it, passed as `depositor` unless that was the signer.
     // TODO: Print the signer.key()
-    pub fn withdraw(ctx: Context<VaultAction>) -> Result<()> {
+    #[instruction(discriminator = 1)]
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
         let bindings = ctx.accounts.signer.key();
         let signer_seeds = &[b"vault", bindings.as_ref(), &[ctx.bumps.vault]];
 
+        let lock_info = ctx.accounts.lock.to_account_info();
+        let mut lock = if lock_info.data_is_empty() {
+            None
+        } else {
+            require_keys_eq!(*lock_info.owner, crate::ID, VaultError::InvalidLock);
+            Some(VaultLock::try_deserialize(
+                &mut &lock_info.data.borrow()[..],
+            )?)
+        };
+
+        let balance = ctx.accounts.vault.lamports();
+        let rent_minimum = Rent::get()?.minimum_balance(0);
+        require!(amount > 0 && amount <= balance, VaultError::InvalidAmount);
+        require!(
+            amount == balance || balance - amount >= rent_minimum,
+            VaultError::BelowRentExemption
+        );
+
+        if let Some(lock) = &lock {
+            let available =
+                lock.withdrawable_from(balance, Clock::get()?.unix_timestamp, rent_minimum);
+            require_gt!(available, 0, VaultError::NothingVested);
+            require_gte!(available, amount, VaultError::AmountNotVested);
+        }
+
         transfer(
             CpiContext::new_with_signer(
                 ctx.accounts.system_program.to_account_info(),
@@ -58,9 +133,234 @@ pub mod blueshift_anchor_vault {
                 },
                 &[&signer_seeds[..]],
             ),
-            ctx.accounts.vault.lamports(),
+            amount,
         )?;
 
+        if let Some(lock) = lock.as_mut() {
+            if amount == balance {
+                // Drained: close the lock so the vault can be reused
+                let refund_to = if lock.depositor == ctx.accounts.signer.key() {
+                    ctx.accounts.signer.to_account_info()
+                } else {
+                    let depositor = ctx
+                        .accounts
+                        .depositor
+                        .as_ref()
+                        .ok_or(VaultError::InvalidDepositor)?;
+                    require_keys_eq!(
+                        depositor.key(),
+                        lock.depositor,
+                        VaultError::InvalidDepositor
+                    );
+                    depositor.to_account_info()
+                };
+                let rent = lock_info.lamports();
+                **lock_info.try_borrow_mut_lamports()? = 0;
+                **refund_to.try_borrow_mut_lamports()? += rent;
+                lock_info.assign(&System::id());
+                lock_info.resize(0)?;
+            } else {
+                lock.withdrawn += amount;
+                lock.try_serialize(&mut &mut lock_info.data.borrow_mut()[..])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Grants `delegate` an allowance on the signer's vault: at most
+    // `max_per_period` lamports per rolling `period` seconds, only to
+    // `destinations`. Calling it again for the same delegate replaces the
+    // allowance and clears what it has spent.
+    #[instruction(discriminator = 3)]
+    pub fn set_delegate(
+        ctx: Context<SetDelegate>,
+        max_per_period: u64,
+        period: i64,
+        destinations: Vec<Pubkey>,
+    ) -> Result<()> {
+        require_gt!(max_per_period, 0, VaultError::InvalidAllowance);
+        require_gt!(period, 0, VaultError::InvalidAllowance);
+        require!(
+            !destinations.is_empty() && destinations.len() <= MAX_DESTINATIONS,
+            VaultError::InvalidDestinations
+        );
+
+        let mut allowed = [Pubkey::default(); MAX_DESTINATIONS];
+        allowed[..destinations.len()].copy_from_slice(&destinations);
+
+        ctx.accounts.allowance.set_inner(DelegateAllowance {
+            owner: ctx.accounts.signer.key(),
+            delegate: ctx.accounts.delegate.key(),
+            max_per_period,
+            period,
+            last_spend: Clock::get()?.unix_timestamp,
+            spent: 0,
+            destination_count: destinations.len() as u8,
+            destinations: allowed,
+            bump: ctx.bumps.allowance,
+        });
+
+        Ok(())
+    }
+
+    // Closes a delegate's allowance, returning its rent to the owner.
+    #[instruction(discriminator = 4)]
+    pub fn revoke_delegate(_ctx: Context<RevokeDelegate>) -> Result<()> {
+        Ok(())
+    }
+
+    // Lets a delegate move lamports out of the owner's vault, within its
+    // allowance and only to an allowed destination. Locked vaults can only be
+    // withdrawn from by their owner.
+    #[instruction(discriminator = 5)]
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+        require!(ctx.accounts.lock.data_is_empty(), VaultError::VaultLocked);
+
+        let allowance = &mut ctx.accounts.allowance;
+        require!(
+            allowance.allows(&ctx.accounts.destination.key()),
+            VaultError::DestinationNotAllowed
+        );
+
+        let balance = ctx.accounts.vault.lamports();
+        require!(amount > 0 && amount <= balance, VaultError::InvalidAmount);
+        require!(
+            amount == balance || balance - amount >= Rent::get()?.minimum_balance(0),
+            VaultError::BelowRentExemption
+        );
+
+        allowance.spend(amount, Clock::get()?.unix_timestamp)?;
+
+        let owner = ctx.accounts.owner.key();
+        let signer_seeds = &[b"vault", owner.as_ref(), &[ctx.bumps.vault]];
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.destination.to_account_info(),
+                },
+                &[&signer_seeds[..]],
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockKind {
+    // Everything unlocks at `end`
+    Cliff,
+    // Unlocks linearly from `start` to `end`
+    Linear,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug)]
+pub struct LockSchedule {
+    pub kind: LockKind,
+    pub start: i64,
+    pub end: i64,
+}
+
+impl LockSchedule {
+    pub fn validate(&self) -> Result<()> {
+        match self.kind {
+            LockKind::Cliff => require_gte!(self.end, self.start, VaultError::InvalidSchedule),
+            LockKind::Linear => require_gt!(self.end, self.start, VaultError::InvalidSchedule),
+        }
+        Ok(())
+    }
+
+    // How much of `total` has unlocked at `now`
+    pub fn vested(&self, total: u64, now: i64) -> u64 {
+        if now >= self.end {
+            return total;
+        }
+        match self.kind {
+            LockKind::Cliff => 0,
+            LockKind::Linear if now <= self.start => 0,
+            LockKind::Linear => {
+                // In i128 so extreme timestamps can't overflow the subtraction
+                let elapsed = (now as i128 - self.start as i128) as u128;
+                let duration = (self.end as i128 - self.start as i128) as u128;
+                (total as u128 * elapsed / duration) as u64
+            }
+        }
+    }
+}
+
+// Lock schedule of a vault, stored at ["lock", vault]
+#[account]
+#[derive(InitSpace)]
+pub struct VaultLock {
+    pub depositor: Pubkey,
+    pub schedule: LockSchedule,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl VaultLock {
+    // Lamports that can leave a vault holding `balance` at `now`.
+    // Until everything has vested, the vault keeps its rent-exempt minimum
+    // so a partial withdrawal never leaves it below rent exemption.
+    pub fn withdrawable_from(&self, balance: u64, now: i64, rent_exempt_minimum: u64) -> u64 {
+        let vested = self.schedule.vested(self.total, now);
+        if vested == self.total {
+            return balance;
+        }
+        (vested - self.withdrawn).min(balance.saturating_sub(rent_exempt_minimum))
+    }
+}
+
+// Most destinations a delegate can be allowed to send to
+pub const MAX_DESTINATIONS: usize = 4;
+
+// A delegate's spending allowance on a vault, stored at
+// ["delegate", vault, delegate]. Destinations are a fixed array so the
+// layout matches the Pinocchio vault's.
+#[account]
+#[derive(InitSpace)]
+pub struct DelegateAllowance {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub max_per_period: u64,
+    // Seconds it takes a fully used allowance to free up again
+    pub period: i64,
+    pub last_spend: i64,
+    // Lamports still counted against the allowance as of `last_spend`
+    pub spent: u64,
+    pub destination_count: u8,
+    pub destinations: [Pubkey; MAX_DESTINATIONS],
+    pub bump: u8,
+}
+
+impl DelegateAllowance {
+    pub fn allows(&self, destination: &Pubkey) -> bool {
+        self.destinations[..self.destination_count as usize].contains(destination)
+    }
+
+    // Records `amount` against the allowance. Spending decays linearly,
+    // freeing `max_per_period` lamports every `period` seconds, so a full
+    // allowance only comes back a whole period after it was used and can't be
+    // doubled up around a period boundary.
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.last_spend).max(0) as u128;
+        let recovered = self.max_per_period as u128 * elapsed / self.period as u128;
+        let recovered = u64::try_from(recovered).unwrap_or(u64::MAX);
+        self.spent = self.spent.saturating_sub(recovered);
+        self.last_spend = now;
+
+        let spent = self
+            .spent
+            .checked_add(amount)
+            .filter(|spent| *spent <= self.max_per_period)
+            .ok_or(VaultError::AllowanceExceeded)?;
+        self.spent = spent;
+
         Ok(())
     }
 }
@@ -87,10 +387,174 @@ pub struct VaultAction<'info> {
     pub system_program: Program<'info, System>, // Since vault : SystemAccount implies System Program ownership, Anchor expects the system_program to be available for potential operations.
 }
 
+#[derive(Accounts)]
+pub struct DepositLocked<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // Owner of the vault being funded; only they can withdraw from it
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", beneficiary.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultLock::INIT_SPACE,
+        seeds = [b"lock", vault.key().as_ref()],
+        bump
+    )]
+    pub lock: Account<'info, VaultLock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Like VaultAction, plus the vault's lock PDA. It is always required, and
+// simply empty when the vault isn't locked, so a lock can't be skipped by
+// leaving it out.
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", signer.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Deserialized in `withdraw` when the vault is locked
+    #[account(
+        mut,
+        seeds = [b"lock", vault.key().as_ref()],
+        bump
+    )]
+    pub lock: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the lock's depositor when the lock is closed
+    #[account(mut)]
+    pub depositor: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Any key can be a delegate
+    pub delegate: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + DelegateAllowance::INIT_SPACE,
+        seeds = [b"delegate", vault.key().as_ref(), delegate.key().as_ref()],
+        bump
+    )]
+    pub allowance: Account<'info, DelegateAllowance>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", signer.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"delegate", vault.key().as_ref(), allowance.delegate.as_ref()],
+        bump = allowance.bump,
+        close = signer
+    )]
+    pub allowance: Account<'info, DelegateAllowance>,
+}
+
+// The lock PDA comes last, as in Withdraw, so a locked vault can't be
+// drained by a delegate by leaving it out.
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    pub delegate: Signer<'info>,
+
+    /// CHECK: Owner of the vault; checked against the allowance
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", owner.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"delegate", vault.key().as_ref(), delegate.key().as_ref()],
+        bump = allowance.bump,
+        has_one = owner,
+        has_one = delegate
+    )]
+    pub allowance: Account<'info, DelegateAllowance>,
+
+    /// CHECK: Must be in the allowance's destinations
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: Must be empty; delegates can't withdraw from locked vaults
+    #[account(
+        seeds = [b"lock", vault.key().as_ref()],
+        bump
+    )]
+    pub lock: UncheckedAccount<'info>,
+}
+
 #[error_code]
 pub enum VaultError {
     #[msg("Vault already exists")]
     VaultAlreadyExists,
     #[msg("Invalid amount")]
     InvalidAmount,
+    #[msg("Lock schedule must not end before it starts")]
+    InvalidSchedule,
+    #[msg("Nothing has vested yet")]
+    NothingVested,
+    #[msg("Lock account is not owned by this program")]
+    InvalidLock,
+    #[msg("Withdrawal would leave the vault below rent exemption")]
+    BelowRentExemption,
+    #[msg("Amount exceeds what has vested")]
+    AmountNotVested,
+    #[msg("Allowance and period must be greater than 0")]
+    InvalidAllowance,
+    #[msg("A delegate needs between 1 and 4 destinations")]
+    InvalidDestinations,
+    #[msg("Destination is not allowed for this delegate")]
+    DestinationNotAllowed,
+    #[msg("Withdrawal exceeds the delegate's remaining allowance")]
+    AllowanceExceeded,
+    #[msg("Delegates can't withdraw from a locked vault")]
+    VaultLocked,
+    #[msg("Lock rent must be refunded to the lock's depositor")]
+    InvalidDepositor,
 }
@@ -1,16 +1,251 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { assert } from "chai";
 import { Vault } from "../target/types/vault";
 
 describe("vault", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.vault as Program<Vault>;
 
+  const vaultPda = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), owner.toBuffer()],
+      program.programId
+    )[0];
+
+  const lockPda = (vault: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), vault.toBuffer()],
+      program.programId
+    )[0];
+
+  const now = () => Math.floor(Date.now() / 1000);
+
   it("Is initialized!", async () => {
     // Add your test here.
     const tx = await program.methods.initialize().rpc();
     console.log("Your transaction signature", tx);
   });
+
+  it("Tops up and partially withdraws an unlocked vault", async () => {
+    const vault = vaultPda(provider.wallet.publicKey);
+    const deposit = (amount: number) =>
+      program.methods
+        .deposit(new BN(amount))
+        .accounts({ signer: provider.wallet.publicKey })
+        .rpc();
+    const withdraw = (amount: number) =>
+      program.methods
+        .withdraw(new BN(amount))
+        .accounts({ signer: provider.wallet.publicKey, depositor: null })
+        .rpc();
+
+    await deposit(LAMPORTS_PER_SOL);
+    await deposit(LAMPORTS_PER_SOL);
+    assert.equal(
+      await provider.connection.getBalance(vault),
+      2 * LAMPORTS_PER_SOL
+    );
+
+    await withdraw(LAMPORTS_PER_SOL / 2);
+    assert.equal(
+      await provider.connection.getBalance(vault),
+      1.5 * LAMPORTS_PER_SOL
+    );
+
+    // Leaving a few lamports would drop the vault below rent exemption
+    try {
+      await withdraw(1.5 * LAMPORTS_PER_SOL - 1);
+      assert.fail("Should have thrown");
+    } catch (e: any) {
+      assert.include(e.message, "BelowRentExemption");
+    }
+
+    await withdraw(1.5 * LAMPORTS_PER_SOL);
+    assert.equal(await provider.connection.getBalance(vault), 0);
+  });
+
+  describe("locked deposits", () => {
+    const fund = async (keypair: Keypair) => {
+      const sig = await provider.connection.requestAirdrop(
+        keypair.publicKey,
+        2 * LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(sig);
+    };
+
+    it("Rejects a withdrawal before the cliff", async () => {
+      const beneficiary = Keypair.generate();
+      await fund(beneficiary);
+
+      await program.methods
+        .depositLocked(new BN(LAMPORTS_PER_SOL), {
+          kind: { cliff: {} },
+          start: new BN(now()),
+          end: new BN(now() + 3600),
+        })
+        .accounts({ beneficiary: beneficiary.publicKey })
+        .signers([beneficiary])
+        .rpc();
+
+      try {
+        await program.methods
+          .withdraw(new BN(LAMPORTS_PER_SOL))
+          .accounts({
+            signer: beneficiary.publicKey,
+            depositor: provider.wallet.publicKey,
+          })
+          .signers([beneficiary])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "NothingVested");
+      }
+    });
+
+    it("Releases a matured lock and closes it", async () => {
+      const beneficiary = Keypair.generate();
+      await fund(beneficiary);
+      const vault = vaultPda(beneficiary.publicKey);
+
+      await program.methods
+        .depositLocked(new BN(LAMPORTS_PER_SOL), {
+          kind: { linear: {} },
+          start: new BN(now() - 7200),
+          end: new BN(now() - 3600),
+        })
+        .accounts({ beneficiary: beneficiary.publicKey })
+        .signers([beneficiary])
+        .rpc();
+
+      // The lock's rent goes back to the depositor, not the beneficiary
+      const depositor = provider.wallet.publicKey;
+      const rent = await provider.connection.getBalance(lockPda(vault));
+      const before = await provider.connection.getBalance(depositor);
+      await program.methods
+        .withdraw(new BN(LAMPORTS_PER_SOL))
+        .accounts({ signer: beneficiary.publicKey, depositor })
+        .signers([beneficiary])
+        .rpc();
+
+      assert.equal(await provider.connection.getBalance(vault), 0);
+      assert.isNull(await provider.connection.getAccountInfo(lockPda(vault)));
+      // The provider wallet also paid the 2-signature fee
+      assert.equal(
+        await provider.connection.getBalance(depositor),
+        before + rent - 10_000
+      );
+    });
+
+    it("Rejects a schedule that ends before it starts", async () => {
+      const beneficiary = Keypair.generate();
+
+      try {
+        await program.methods
+          .depositLocked(new BN(LAMPORTS_PER_SOL), {
+            kind: { linear: {} },
+            start: new BN(now()),
+            end: new BN(now() - 1),
+          })
+          .accounts({ beneficiary: beneficiary.publicKey })
+          .signers([beneficiary])
+          .rpc();
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "InvalidSchedule");
+      }
+    });
+  });
+
+  describe("delegates", () => {
+    const owner = Keypair.generate();
+    const bot = Keypair.generate();
+    const payee = Keypair.generate().publicKey;
+    const vault = vaultPda(owner.publicKey);
+    const [allowance] = PublicKey.findProgramAddressSync(
+      [Buffer.from("delegate"), vault.toBuffer(), bot.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const delegateWithdraw = (amount: number, destination = payee) =>
+      program.methods
+        .delegateWithdraw(new BN(amount))
+        .accounts({
+          delegate: bot.publicKey,
+          owner: owner.publicKey,
+          destination,
+        })
+        .signers([bot])
+        .rpc();
+
+    before(async () => {
+      for (const kp of [owner, bot]) {
+        const sig = await provider.connection.requestAirdrop(
+          kp.publicKey,
+          3 * LAMPORTS_PER_SOL
+        );
+        await provider.connection.confirmTransaction(sig);
+      }
+
+      await program.methods
+        .deposit(new BN(2 * LAMPORTS_PER_SOL))
+        .accounts({ signer: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      await program.methods
+        .setDelegate(new BN(LAMPORTS_PER_SOL / 2), new BN(3600), [payee])
+        .accounts({ signer: owner.publicKey, delegate: bot.publicKey })
+        .signers([owner])
+        .rpc();
+    });
+
+    it("Withdraws within the allowance", async () => {
+      await delegateWithdraw(LAMPORTS_PER_SOL / 4);
+
+      assert.equal(
+        await provider.connection.getBalance(payee),
+        LAMPORTS_PER_SOL / 4
+      );
+      const state = await program.account.delegateAllowance.fetch(allowance);
+      assert.equal(state.spent.toNumber(), LAMPORTS_PER_SOL / 4);
+    });
+
+    it("Rejects withdrawals over the remaining allowance", async () => {
+      try {
+        await delegateWithdraw(LAMPORTS_PER_SOL / 2);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "AllowanceExceeded");
+      }
+    });
+
+    it("Rejects destinations that aren't allowed", async () => {
+      try {
+        await delegateWithdraw(1_000_000, Keypair.generate().publicKey);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "DestinationNotAllowed");
+      }
+    });
+
+    it("Can't withdraw once revoked", async () => {
+      await program.methods
+        .revokeDelegate()
+        .accountsPartial({ signer: owner.publicKey, allowance })
+        .signers([owner])
+        .rpc();
+      assert.isNull(await provider.connection.getAccountInfo(allowance));
+
+      try {
+        await delegateWithdraw(1_000_000);
+        assert.fail("Should have thrown");
+      } catch (e: any) {
+        assert.include(e.message, "AccountNotInitialized");
+      }
+    });
+  });
 });
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::state::{UserDeposit, VaultManager};
+
+/// The accounts `deposit_many` and `withdraw_many` take per mint, in
+/// remaining accounts order
+pub struct MintAccounts<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub user_deposit: Account<'info, UserDeposit>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> MintAccounts<'info> {
+    pub const LEN: usize = 5;
+
+    /// Split remaining accounts into one group per amount, running the same
+    /// checks as the single-mint instructions' constraints
+    ///
+    /// The vault and the user's deposit record must already exist; the first
+    /// deposit of a mint goes through `deposit`.
+    pub fn load_all(
+        remaining_accounts: &'info [AccountInfo<'info>],
+        user: &Pubkey,
+        manager: &Pubkey,
+        count: usize,
+    ) -> Result<Vec<Self>> {
+        require!(
+            count > 0 && remaining_accounts.len() == count * Self::LEN,
+            VaultError::InvalidBatch
+        );
+
+        let groups = remaining_accounts
+            .chunks_exact(Self::LEN)
+            .map(|accounts| Self::load(accounts, user, manager))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Every group holds its own copy of its `UserDeposit`, written back
+        // with `exit`. A repeated mint would debit or credit a stale copy and
+        // the last write would win, so each mint may appear only once.
+        for (i, group) in groups.iter().enumerate() {
+            require!(
+                groups[..i].iter().all(|other| {
+                    other.mint.key() != group.mint.key()
+                        && other.user_deposit.key() != group.user_deposit.key()
+                }),
+                VaultError::DuplicateBatchMint
+            );
+        }
+
+        Ok(groups)
+    }
+
+    fn load(
+        accounts: &'info [AccountInfo<'info>],
+        user: &Pubkey,
+        manager: &Pubkey,
+    ) -> Result<Self> {
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[0])?;
+        let vault = InterfaceAccount::<TokenAccount>::try_from(&accounts[1])?;
+        let user_deposit = Account::<UserDeposit>::try_from(&accounts[2])?;
+        let user_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[3])?;
+        let token_program = Interface::<TokenInterface>::try_from(&accounts[4])?;
+
+        require_keys_eq!(
+            *accounts[0].owner,
+            token_program.key(),
+            VaultError::BatchAccountMismatch
+        );
+
+        let (vault_key, _) = Pubkey::find_program_address(
+            &[VaultManager::VAULT_SEED, mint.key().as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(vault.key(), vault_key, VaultError::BatchAccountMismatch);
+        require_keys_eq!(vault.owner, *manager, VaultError::BatchAccountMismatch);
+
+        require!(accounts[2].is_writable, VaultError::BatchAccountMismatch);
+        require_keys_eq!(user_deposit.owner, *user, VaultError::BatchAccountMismatch);
+        require_keys_eq!(
+            user_deposit.mint,
+            mint.key(),
+            VaultError::BatchAccountMismatch
+        );
+
+        require_keys_eq!(
+            user_token_account.mint,
+            mint.key(),
+            VaultError::BatchAccountMismatch
+        );
+
+        Ok(Self {
+            mint,
+            vault,
+            user_deposit,
+            user_token_account,
+            token_program,
+        })
+    }
+
+    /// Withdrawals always go to the user's own ATA
+    pub fn require_user_ata(&self, user: &Pubkey) -> Result<()> {
+        let ata = get_associated_token_address_with_program_id(
+            user,
+            &self.mint.key(),
+            &self.token_program.key(),
+        );
+        require_keys_eq!(
+            self.user_token_account.key(),
+            ata,
+            VaultError::BatchAccountMismatch
+        );
+        Ok(())
+    }
+}
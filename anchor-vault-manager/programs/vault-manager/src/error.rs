@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Insufficient Funds in Wallet!")]
+    InsufficientFunds,
+    #[msg("Withdrawal exceeds the deposited balance")]
+    InsufficientDeposit,
+    #[msg("Only the vault manager admin can do this")]
+    Unauthorized,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Remaining accounts must hold one group of accounts per amount")]
+    InvalidBatch,
+    #[msg("Remaining accounts don't match the mint they are grouped with")]
+    BatchAccountMismatch,
+    #[msg("A mint can only appear once in a batch")]
+    DuplicateBatchMint,
+}
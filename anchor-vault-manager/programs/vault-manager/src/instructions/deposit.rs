@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{UserDeposit, VaultManager};
+use crate::transfer::deposit_to_vault;
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    #[account(
+        init_if_needed,
+        seeds = [VaultManager::VAULT_SEED, mint_account.key().as_ref()],
+        token::mint = mint_account,
+        token::authority = token_account_owner_pda,
+        token::token_program = token_program,
+        payer = signer,
+        bump
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = 8 + UserDeposit::INIT_SPACE,
+        seeds = [UserDeposit::SEED, signer.key().as_ref(), mint_account.key().as_ref()],
+        bump
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    #[account(mut, token::mint = mint_account, token::token_program = token_program)]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Deposit<'info> {
+    pub fn deposit(&mut self, amount: u64, bumps: &DepositBumps) -> Result<()> {
+        msg!("Amount Deposting: {}", amount); //logs the amount deposited
+
+        // display balance in senders account
+        msg!(
+            "Senders account balance: {}",
+            self.sender_token_account.amount
+        );
+
+        let received = deposit_to_vault(
+            &self.mint_account,
+            &self.sender_token_account,
+            &mut self.vault,
+            &self.signer,
+            &self.token_program,
+            amount,
+        )?;
+
+        // credit the depositor's record, creating it on their first deposit
+        let user_deposit = &mut self.user_deposit;
+        if user_deposit.owner == Pubkey::default() {
+            user_deposit.owner = self.signer.key();
+            user_deposit.mint = self.mint_account.key();
+            user_deposit.bump = bumps.user_deposit;
+        }
+        user_deposit.credit(received)?;
+
+        msg!("Deposited balance: {}", user_deposit.amount);
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::batch::MintAccounts;
+use crate::state::VaultManager;
+use crate::transfer::deposit_to_vault;
+
+/// Deposit several mints at once. Each amount takes a `MintAccounts` group
+/// from remaining accounts:
+/// `[mint, vault, user_deposit, sender_token_account, token_program]`.
+#[derive(Accounts)]
+pub struct DepositMany<'info> {
+    #[account(
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    pub signer: Signer<'info>,
+}
+
+impl<'info> DepositMany<'info> {
+    pub fn deposit_many(
+        &mut self,
+        amounts: &[u64],
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let groups = MintAccounts::load_all(
+            remaining_accounts,
+            &self.signer.key(),
+            &self.token_account_owner_pda.key(),
+            amounts.len(),
+        )?;
+
+        for (mut group, &amount) in groups.into_iter().zip(amounts) {
+            let received = deposit_to_vault(
+                &group.mint,
+                &group.user_token_account,
+                &mut group.vault,
+                &self.signer,
+                &group.token_program,
+                amount,
+            )?;
+            group.user_deposit.credit(received)?;
+            group.user_deposit.exit(&crate::ID)?;
+
+            msg!("Deposited {} of {}", received, group.mint.key());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::error::VaultError;
+use crate::state::VaultManager;
+use crate::transfer::pay_from_vault;
+
+#[derive(Accounts)]
+pub struct EmergencySweep<'info> {
+    #[account(
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump,
+        has_one = admin @ VaultError::Unauthorized
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    #[account(
+        mut,
+        seeds = [VaultManager::VAULT_SEED, mint_account.key().as_ref()],
+        bump,
+        token::mint = mint_account,
+        token::authority = token_account_owner_pda,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = mint_account, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+
+    pub admin: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> EmergencySweep<'info> {
+    /// Move a mint's whole vault to `destination`. User deposit records are
+    /// left untouched so they can be made whole afterwards.
+    pub fn emergency_sweep(&mut self) -> Result<()> {
+        let amount = self.vault.amount;
+        msg!("Sweeping {} tokens to {}", amount, self.destination.key());
+
+        pay_from_vault(
+            &self.token_account_owner_pda,
+            &self.mint_account,
+            &self.vault,
+            self.destination.to_account_info(),
+            &self.token_program,
+            amount,
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VaultManager;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = signer,
+        space = 8 + VaultManager::INIT_SPACE,
+        seeds = [VaultManager::SEED],
+        bump
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    /// Becomes the vault manager admin
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Initialize<'info> {
+    pub fn initialize(&mut self, bumps: &InitializeBumps) -> Result<()> {
+        self.token_account_owner_pda.set_inner(VaultManager {
+            admin: self.signer.key(),
+            bump: bumps.token_account_owner_pda,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+pub mod deposit;
+pub mod deposit_many;
+pub mod emergency_sweep;
+pub mod initialize;
+pub mod set_admin;
+pub mod withdraw;
+pub mod withdraw_many;
+
+pub use deposit::*;
+pub use deposit_many::*;
+pub use emergency_sweep::*;
+pub use initialize::*;
+pub use set_admin::*;
+pub use withdraw::*;
+pub use withdraw_many::*;
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+use crate::state::VaultManager;
+
+#[derive(Accounts)]
+pub struct SetAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump,
+        has_one = admin @ VaultError::Unauthorized
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    pub admin: Signer<'info>,
+}
+
+impl<'info> SetAdmin<'info> {
+    pub fn set_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.token_account_owner_pda.admin = new_admin;
+
+        msg!("Admin set to {}", new_admin);
+        Ok(())
+    }
+}
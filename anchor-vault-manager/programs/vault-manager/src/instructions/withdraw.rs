@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::state::{UserDeposit, VaultManager};
+use crate::transfer::pay_from_vault;
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    #[account(
+        mut,
+        seeds = [VaultManager::VAULT_SEED, mint_account.key().as_ref()],
+        bump,
+        token::mint = mint_account,
+        token::authority = token_account_owner_pda,
+        token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Only the signer's own deposit record can be debited
+    #[account(
+        mut,
+        seeds = [UserDeposit::SEED, signer.key().as_ref(), mint_account.key().as_ref()],
+        bump = user_deposit.bump
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+
+    /// Withdrawals always go to the depositor's own ATA
+    #[account(
+        mut,
+        associated_token::mint = mint_account,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program,
+    )]
+    pub receiver_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint_account: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        msg!("Amount Withdrawing: {}", amount);
+
+        // checks the signer deposited at least that much
+        self.user_deposit.debit(amount)?;
+
+        //Vaults balance
+        msg!("Vaults Balance: {}", self.vault.amount);
+
+        pay_from_vault(
+            &self.token_account_owner_pda,
+            &self.mint_account,
+            &self.vault,
+            self.receiver_token_account.to_account_info(),
+            &self.token_program,
+            amount,
+        )?;
+
+        msg!("Withdrawal successful");
+        Ok(())
+    }
+}
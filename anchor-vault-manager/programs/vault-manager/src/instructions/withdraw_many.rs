@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::batch::MintAccounts;
+use crate::state::VaultManager;
+use crate::transfer::pay_from_vault;
+
+/// Withdraw several mints at once. Each amount takes a `MintAccounts` group
+/// from remaining accounts:
+/// `[mint, vault, user_deposit, receiver_ata, token_program]`.
+#[derive(Accounts)]
+pub struct WithdrawMany<'info> {
+    #[account(
+        seeds = [VaultManager::SEED],
+        bump = token_account_owner_pda.bump
+    )]
+    pub token_account_owner_pda: Account<'info, VaultManager>,
+
+    pub signer: Signer<'info>,
+}
+
+impl<'info> WithdrawMany<'info> {
+    pub fn withdraw_many(
+        &mut self,
+        amounts: &[u64],
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let groups = MintAccounts::load_all(
+            remaining_accounts,
+            &self.signer.key(),
+            &self.token_account_owner_pda.key(),
+            amounts.len(),
+        )?;
+
+        for (mut group, &amount) in groups.into_iter().zip(amounts) {
+            group.require_user_ata(&self.signer.key())?;
+
+            group.user_deposit.debit(amount)?;
+            pay_from_vault(
+                &self.token_account_owner_pda,
+                &group.mint,
+                &group.vault,
+                group.user_token_account.to_account_info(),
+                &group.token_program,
+                amount,
+            )?;
+            group.user_deposit.exit(&crate::ID)?;
+
+            msg!("Withdrew {} of {}", amount, group.mint.key());
+        }
+
+        Ok(())
+    }
+}
@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+
+pub mod batch;
+pub mod error;
+pub mod instructions;
+pub mod state;
+pub mod transfer;
+
+use instructions::*;
 
 declare_id!("2eH4VtkkB5X5592hmuQqFQvQ9QKaTEmRZyvQgf9EWyxp");
 
@@ -7,161 +14,47 @@ declare_id!("2eH4VtkkB5X5592hmuQqFQvQ9QKaTEmRZyvQgf9EWyxp");
 pub mod vault_manager {
     use super::*;
 
+    /// Create the vault manager PDA; the signer becomes its admin
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        msg!("Valut Manager Initialized | Greetings from: {:?}", ctx.program_id);
-        Ok(())
-    }
-
-    pub fn deposit(
-        ctx: Context<Deposit>,
-        amount: u64,
-    ) -> Result<()> {
-        msg!("Amount Deposting: {}", amount); //logs the amount deposited
-        //creates reference to the senders's token amount from ctx.accounts
-        let sender_token_account: &Account<TokenAccount> = &ctx.accounts.sender_token_account;
-
-        //checks the senders has enough tokens to send
-        if sender_token_account.amount < amount{
-            return Err(VaultError::InsufficientFunds.into());
-        }
-
-        // display balance in senders account
-        msg!("Senders account balance: {}", sender_token_account.amount);
-
-        // Creats a transfer instruction for SPL token program
-        let tx_instruct: anchor_spl::token::Transfer = Transfer {
-            from: ctx.accounts.sender_token_account.to_account_info(),
-            to: ctx.accounts.vault.to_account_info(),
-            authority: ctx.accounts.signer.to_account_info(),
-        };  
-
-        //creates a CPI cross platform invocation Context
-        //it combines the token program account with the transfer instruction
-        // READ MORE: https://solana.com/docs/core/cpi
-        let cpi_ctx: CpiContext<anchor_spl::token::Transfer> = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            tx_instruct
+        msg!(
+            "Valut Manager Initialized | Greetings from: {:?}",
+            ctx.program_id
         );
-
-        //executes transfer instruction usign CPI Context
-        // ? shows any errors the may occur during transfer
-        anchor_spl::token::transfer(cpi_ctx, amount)?;
-        Ok(()) // if no erros are thrown is returns Ok(())
+        ctx.accounts.initialize(&ctx.bumps)
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()>{
-        msg!("Amount Withdrawing: {}", amount);
-        
-        // checks vault has enough tokens 
-        let vault = &ctx.accounts.vault;
-        if vault.amount < amount {
-            return Err(VaultError::InsufficientFunds.into());
-        }
-
-        //Vaults balance
-        msg!("Vaults Balance: {}", vault.amount);
-
-        let seeds = &[b"VAULT_MANAGER".as_ref(), &[ctx.bumps.token_account_owner_pda]];
-        let signer_seeds = &[&seeds[..]];
-
-        let tx_instruct = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.receiver_token_account.to_account_info(),
-            authority: ctx.accounts.token_account_owner_pda.to_account_info(),
-        };
-
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        transfer(CpiContext::new_with_signer(cpi_program, tx_instruct, signer_seeds), amount)?;
-
-        msg!("Withdrawal successful");
-        Ok(())
+    /// Deposit tokens and credit them to the signer's deposit record
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        ctx.accounts.deposit(amount, &ctx.bumps)
     }
-       
-}
-
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(
-		init_if_needed,
-		payer = signer,
-		seeds = [b"VAULT_MANAGER"],
-		bump,
-		space = 8
-	)]
-	/// CHECK: Struct field "token_account_owner_pda" is unsafe, but is not documented.
-	token_account_owner_pda: AccountInfo<'info>,
 
-	#[account(mut)]
-	signer: Signer<'info>,
-
-	system_program: Program<'info, System>,
-	token_program:  Program<'info, Token>,
-	rent: Sysvar<'info, Rent>,
-}
-
-#[derive(Accounts)]
-pub struct Deposit<'info>{
-	#[account(mut,
-		seeds=[b"VAULT_MANAGER"],
-		bump
-	)]
-	token_account_owner_pda: AccountInfo<'info>,
-	#[account(
-		init_if_needed,
-		seeds = [
-			b"VAULT_MANAGER_PDA_VAULT",
-			mint_account.key().as_ref()
-		],
-		token::mint      = mint_account,
-		token::authority = token_account_owner_pda,
-		payer            = signer,
-		bump
-	)]
-	pub vault: Account<'info, TokenAccount>,
-
-	#[account(mut)]
-	pub signer: Signer<'info>,
-
-	pub mint_account: Account<'info, Mint>,
-
-	#[account(mut)]
-	pub sender_token_account: Account<'info, TokenAccount>,
-
-	pub token_program:  Program<'info, Token>,
-	pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct Withdraw<'info>{
-    #[account(mut,
-    seeds = [b"VAULT_MANAGER"],
-    bump
-    )]
-    pub token_account_owner_pda: AccountInfo<'info>,
-
-    #[account(mut, 
-    seeds=[b"VAULT_MANAGER_PDA_VAULT", mint_account.key().as_ref()],
-    bump,
-    token::mint = mint_account,
-    token::authority = token_account_owner_pda,
-    )]
-    pub vault: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub receiver_token_account: Account<'info, TokenAccount>,
+    /// Withdraw previously deposited tokens to the signer's ATA
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount)
+    }
 
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    /// Deposit several mints at once, passed as remaining accounts
+    pub fn deposit_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositMany<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.deposit_many(&amounts, ctx.remaining_accounts)
+    }
 
-    pub mint_account: Account<'info, Mint>,
+    /// Withdraw several mints at once, passed as remaining accounts
+    pub fn withdraw_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawMany<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_many(&amounts, ctx.remaining_accounts)
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    /// Admin-only: move a mint's whole vault out in an emergency
+    pub fn emergency_sweep(ctx: Context<EmergencySweep>) -> Result<()> {
+        ctx.accounts.emergency_sweep()
+    }
 
-#[error_code]
-pub enum VaultError {
-	#[msg("Insufficient Funds in Wallet!")]
-	InsufficientFunds,
+    pub fn set_admin(ctx: Context<SetAdmin>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.set_admin(new_admin)
+    }
 }
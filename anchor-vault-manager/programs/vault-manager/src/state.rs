@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::error::VaultError;
+
+/// The global PDA that owns every mint's vault token account
+#[account]
+#[derive(InitSpace)]
+pub struct VaultManager {
+    /// May sweep vaults in an emergency
+    pub admin: Pubkey,
+    pub bump: u8,
+}
+
+impl VaultManager {
+    pub const SEED: &'static [u8] = b"VAULT_MANAGER";
+    pub const VAULT_SEED: &'static [u8] = b"VAULT_MANAGER_PDA_VAULT";
+}
+
+/// How much of a mint a user has deposited and not yet withdrawn
+#[account]
+#[derive(InitSpace)]
+pub struct UserDeposit {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl UserDeposit {
+    pub const SEED: &'static [u8] = b"USER_DEPOSIT";
+
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(VaultError::Overflow)?;
+        Ok(())
+    }
+
+    pub fn debit(&mut self, amount: u64) -> Result<()> {
+        if self.amount < amount {
+            return Err(VaultError::InsufficientDeposit.into());
+        }
+        self.amount -= amount;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::error::VaultError;
+use crate::state::VaultManager;
+
+/// Move `amount` from `from` into `vault` and return what the vault actually
+/// received
+///
+/// Token-2022 transfer fees are withheld from the destination, so the
+/// vault's balance delta, not `amount`, is what a deposit is worth.
+pub fn deposit_to_vault<'info>(
+    mint: &InterfaceAccount<'info, Mint>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    vault: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<u64> {
+    //checks the senders has enough tokens to send
+    if from.amount < amount {
+        return Err(VaultError::InsufficientFunds.into());
+    }
+
+    let balance_before = vault.amount;
+
+    let tx_instruct = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: vault.to_account_info(),
+        authority: authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), tx_instruct);
+    transfer_checked(cpi_ctx, amount, mint.decimals)?;
+
+    vault.reload()?;
+    vault
+        .amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::Overflow.into())
+}
+
+/// Move `amount` out of a vault, signed by the vault manager PDA
+pub fn pay_from_vault<'info>(
+    manager: &Account<'info, VaultManager>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    // checks vault has enough tokens (an emergency sweep may have emptied it)
+    if vault.amount < amount {
+        return Err(VaultError::InsufficientFunds.into());
+    }
+
+    let seeds = &[VaultManager::SEED, &[manager.bump]];
+    let signer_seeds = &[&seeds[..]];
+
+    let tx_instruct = TransferChecked {
+        from: vault.to_account_info(),
+        mint: mint.to_account_info(),
+        to,
+        authority: manager.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), tx_instruct, signer_seeds);
+    transfer_checked(cpi_ctx, amount, mint.decimals)
+}
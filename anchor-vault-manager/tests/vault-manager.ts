@@ -5,11 +5,17 @@ import { PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import {
   Account,
+  createAssociatedTokenAccount,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
   createMint,
+  ExtensionType,
   getAccount,
+  getMintLen,
   getOrCreateAssociatedTokenAccount,
   mintTo,
   setAuthority,
+  TOKEN_2022_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 
@@ -185,7 +191,7 @@ describe("Vault Manager 💰 🕴️", () => {
     try {
       let initVaultTx = await program.methods
         .initialize()
-        .accounts({
+        .accountsPartial({
           tokenAccountOwnerPda: tokenAccountOwnerPda,
           signer: program.provider.publicKey,
         })
@@ -211,7 +217,7 @@ describe("Vault Manager 💰 🕴️", () => {
 
     let tx = await program.methods
       .deposit(new anchor.BN(3))
-      .accounts({
+      .accountsPartial({
         tokenAccountOwnerPda: tokenAccountOwnerPda,
         vault: pda.pubkey,
         signer: walletAlice.publicKey,
@@ -232,7 +238,7 @@ describe("Vault Manager 💰 🕴️", () => {
 
     tx = await program.methods
       .deposit(new anchor.BN(2))
-      .accounts({
+      .accountsPartial({
         tokenAccountOwnerPda: tokenAccountOwnerPda,
         vault: pda.pubkey,
         signer: walletAlice.publicKey,
@@ -282,7 +288,7 @@ describe("Vault Manager 💰 🕴️", () => {
 
     let tx = await program.methods
       .withdraw(new anchor.BN(2))
-      .accounts({
+      .accountsPartial({
         tokenAccountOwnerPda: tokenAccountOwnerPda,
         vault: pda.pubkey,
         receiverTokenAccount: ataAlice.address,
@@ -318,6 +324,381 @@ describe("Vault Manager 💰 🕴️", () => {
       console.log(err);
     }
   });
+
+  describe("access control", () => {
+    let walletBob: anchor.web3.Keypair;
+    let ataBob: PublicKey;
+
+    before(async () => {
+      walletBob = (await createAccount(2))[0];
+      ataBob = await createAssociatedTokenAccount(
+        connection,
+        walletBob,
+        mintAlice,
+        walletBob.publicKey
+      );
+    });
+
+    const withdraw = (
+      signer: anchor.web3.Keypair,
+      receiver: PublicKey,
+      amount: number
+    ) =>
+      program.methods
+        .withdraw(new anchor.BN(amount))
+        .accountsPartial({
+          receiverTokenAccount: receiver,
+          signer: signer.publicKey,
+          mintAccount: mintAlice,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer])
+        .rpc();
+
+    it("Rejects withdrawing more than was deposited", async () => {
+      // Alice deposited 5 and withdrew 2
+      try {
+        await withdraw(walletAlice as anchor.web3.Keypair, ataAlice.address, 4);
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("InsufficientDeposit");
+      }
+    });
+
+    it("Rejects withdrawing to someone else's account", async () => {
+      try {
+        await withdraw(walletAlice as anchor.web3.Keypair, ataBob, 1);
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("ConstraintTokenOwner");
+      }
+    });
+
+    it("Rejects withdrawing another user's deposit", async () => {
+      try {
+        await withdraw(walletBob, ataBob, 1);
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("AccountNotInitialized");
+      }
+    });
+
+    it("Only the admin can sweep a vault", async () => {
+      try {
+        await program.methods
+          .emergencySweep()
+          .accountsPartial({
+            destination: ataBob,
+            admin: walletBob.publicKey,
+            mintAccount: mintAlice,
+          })
+          .signers([walletBob])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("Unauthorized");
+      }
+    });
+
+    it("Admin sweeps a vault and deposit records are kept", async () => {
+      const vault = await getVaultPda(
+        program,
+        "VAULT_MANAGER_PDA_VAULT",
+        mintAlice
+      );
+      const [userDeposit] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("USER_DEPOSIT"),
+          walletAlice.publicKey.toBuffer(),
+          mintAlice.toBuffer(),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .emergencySweep()
+        .accountsPartial({
+          destination: ataBob,
+          admin: provider.wallet.publicKey,
+          mintAccount: mintAlice,
+        })
+        .rpc();
+
+      expect((await getAccount(connection, vault.pubkey)).amount).to.equal(
+        BigInt(0)
+      );
+      expect((await getAccount(connection, ataBob)).amount).to.equal(
+        BigInt(3)
+      );
+      const record = await program.account.userDeposit.fetch(userDeposit);
+      expect(record.amount.toNumber()).to.equal(3);
+    });
+  });
+
+  describe("token interface", () => {
+    let walletCarol: anchor.web3.Keypair;
+
+    // A Token-2022 mint charging `feeBps` on every transfer
+    async function createFeeMint(feeBps: number) {
+      const mint = anchor.web3.Keypair.generate();
+      const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+      const lamports = await connection.getMinimumBalanceForRentExemption(
+        mintLen
+      );
+      const tx = new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.createAccount({
+          fromPubkey: walletCarol.publicKey,
+          newAccountPubkey: mint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mint.publicKey,
+          walletCarol.publicKey,
+          walletCarol.publicKey,
+          feeBps,
+          BigInt(1_000_000),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          mint.publicKey,
+          decimals,
+          walletCarol.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      );
+      await anchor.web3.sendAndConfirmTransaction(connection, tx, [
+        walletCarol,
+        mint,
+      ]);
+      return mint.publicKey;
+    }
+
+    async function fundedAta(mint: PublicKey, tokenProgram: PublicKey) {
+      const ata = await createAssociatedTokenAccount(
+        connection,
+        walletCarol,
+        mint,
+        walletCarol.publicKey,
+        undefined,
+        tokenProgram
+      );
+      await mintTo(
+        connection,
+        walletCarol,
+        mint,
+        ata,
+        walletCarol,
+        10_000,
+        [],
+        undefined,
+        tokenProgram
+      );
+      return ata;
+    }
+
+    const deposit = (
+      mint: PublicKey,
+      from: PublicKey,
+      tokenProgram: PublicKey,
+      amount: number
+    ) =>
+      program.methods
+        .deposit(new anchor.BN(amount))
+        .accountsPartial({
+          signer: walletCarol.publicKey,
+          mintAccount: mint,
+          senderTokenAccount: from,
+          tokenProgram,
+        })
+        .signers([walletCarol])
+        .rpc();
+
+    const userDepositPda = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("USER_DEPOSIT"),
+          walletCarol.publicKey.toBuffer(),
+          mint.toBuffer(),
+        ],
+        program.programId
+      )[0];
+
+    // mint, vault, user deposit, user token account, token program
+    const mintGroup = (
+      mint: PublicKey,
+      tokenAccount: PublicKey,
+      tokenProgram: PublicKey
+    ) => [
+      { pubkey: mint, isSigner: false, isWritable: false },
+      {
+        pubkey: PublicKey.findProgramAddressSync(
+          [Buffer.from("VAULT_MANAGER_PDA_VAULT"), mint.toBuffer()],
+          program.programId
+        )[0],
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: userDepositPda(mint), isSigner: false, isWritable: true },
+      { pubkey: tokenAccount, isSigner: false, isWritable: true },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    ];
+
+    before(async () => {
+      walletCarol = (await createAccount(5))[0];
+    });
+
+    it("Credits what the vault received from a fee-on-transfer mint", async () => {
+      const mint = await createFeeMint(100); // 1%
+      const ata = await fundedAta(mint, TOKEN_2022_PROGRAM_ID);
+
+      await deposit(mint, ata, TOKEN_2022_PROGRAM_ID, 1_000);
+
+      const record = await program.account.userDeposit.fetch(
+        userDepositPda(mint)
+      );
+      expect(record.amount.toNumber()).to.equal(990);
+    });
+
+    it("Moves several mints in one instruction", async () => {
+      const classic = await createMint(
+        connection,
+        walletCarol,
+        walletCarol.publicKey,
+        null,
+        decimals
+      );
+      const classicAta = await fundedAta(classic, TOKEN_PROGRAM_ID);
+      const feeMint = await createFeeMint(0);
+      const feeAta = await fundedAta(feeMint, TOKEN_2022_PROGRAM_ID);
+
+      // The first deposit of each mint creates its vault and record
+      await deposit(classic, classicAta, TOKEN_PROGRAM_ID, 1);
+      await deposit(feeMint, feeAta, TOKEN_2022_PROGRAM_ID, 1);
+
+      await program.methods
+        .depositMany([new anchor.BN(100), new anchor.BN(200)])
+        .accounts({ signer: walletCarol.publicKey })
+        .remainingAccounts([
+          ...mintGroup(classic, classicAta, TOKEN_PROGRAM_ID),
+          ...mintGroup(feeMint, feeAta, TOKEN_2022_PROGRAM_ID),
+        ])
+        .signers([walletCarol])
+        .rpc();
+
+      expect(
+        (
+          await program.account.userDeposit.fetch(userDepositPda(classic))
+        ).amount.toNumber()
+      ).to.equal(101);
+      expect(
+        (
+          await program.account.userDeposit.fetch(userDepositPda(feeMint))
+        ).amount.toNumber()
+      ).to.equal(201);
+
+      await program.methods
+        .withdrawMany([new anchor.BN(101), new anchor.BN(201)])
+        .accounts({ signer: walletCarol.publicKey })
+        .remainingAccounts([
+          ...mintGroup(classic, classicAta, TOKEN_PROGRAM_ID),
+          ...mintGroup(feeMint, feeAta, TOKEN_2022_PROGRAM_ID),
+        ])
+        .signers([walletCarol])
+        .rpc();
+
+      expect((await getAccount(connection, classicAta)).amount).to.equal(
+        BigInt(10_000)
+      );
+      expect(
+        (
+          await getAccount(
+            connection,
+            feeAta,
+            undefined,
+            TOKEN_2022_PROGRAM_ID
+          )
+        ).amount
+      ).to.equal(BigInt(10_000));
+    });
+
+    it("Rejects a batch withdrawal to someone else's account", async () => {
+      const mint = await createMint(
+        connection,
+        walletCarol,
+        walletCarol.publicKey,
+        null,
+        decimals
+      );
+      const ata = await fundedAta(mint, TOKEN_PROGRAM_ID);
+      await deposit(mint, ata, TOKEN_PROGRAM_ID, 10);
+
+      const stranger = anchor.web3.Keypair.generate().publicKey;
+      const strangerAta = await createAssociatedTokenAccount(
+        connection,
+        walletCarol,
+        mint,
+        stranger
+      );
+
+      try {
+        await program.methods
+          .withdrawMany([new anchor.BN(10)])
+          .accounts({ signer: walletCarol.publicKey })
+          .remainingAccounts(mintGroup(mint, strangerAta, TOKEN_PROGRAM_ID))
+          .signers([walletCarol])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("BatchAccountMismatch");
+      }
+    });
+
+    it("Rejects a batch that repeats a mint", async () => {
+      const mint = await createMint(
+        connection,
+        walletCarol,
+        walletCarol.publicKey,
+        null,
+        decimals
+      );
+      const ata = await fundedAta(mint, TOKEN_PROGRAM_ID);
+      await deposit(mint, ata, TOKEN_PROGRAM_ID, 10);
+      const group = mintGroup(mint, ata, TOKEN_PROGRAM_ID);
+
+      // Each copy would debit the same stale balance of 10
+      try {
+        await program.methods
+          .withdrawMany([new anchor.BN(10), new anchor.BN(10)])
+          .accounts({ signer: walletCarol.publicKey })
+          .remainingAccounts([...group, ...group])
+          .signers([walletCarol])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("DuplicateBatchMint");
+      }
+
+      try {
+        await program.methods
+          .depositMany([new anchor.BN(5), new anchor.BN(5)])
+          .accounts({ signer: walletCarol.publicKey })
+          .remainingAccounts([...group, ...group])
+          .signers([walletCarol])
+          .rpc();
+        expect.fail("Should have thrown");
+      } catch (e: any) {
+        expect(e.message).to.include("DuplicateBatchMint");
+      }
+
+      const record = await program.account.userDeposit.fetch(
+        userDepositPda(mint)
+      );
+      expect(record.amount.toNumber()).to.equal(10);
+    });
+  });
 });
 
 async function logTransaction(connection, txHash) {
@@ -0,0 +1,25 @@
+use core::fmt;
+
+/// Mirrors the on-chain `MerkleError` cases the client can hit locally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeError {
+    ZeroLeaf,
+    TreeFull,
+    EmptyBatch,
+    IndexOutOfRange,
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            TreeError::ZeroLeaf => "Leaf cannot be zero",
+            TreeError::TreeFull => "Merkle tree is full",
+            TreeError::EmptyBatch => "Batch must contain at least one leaf",
+            TreeError::IndexOutOfRange => "Leaf index has not been inserted",
+        };
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TreeError {}
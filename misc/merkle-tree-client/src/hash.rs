@@ -0,0 +1,71 @@
+use sha2::{Digest, Sha256};
+use sha2_const_stable::Sha256 as ConstSha256;
+
+const fn make_zero_hashes() -> [[u8; 32]; 21] {
+    let mut h = [[0u8; 32]; 21];
+    h[0] = ConstSha256::new().update(&[0u8; 32]).finalize();
+    let mut i = 1usize;
+    while i <= 20 {
+        let mut buf = [0u8; 64];
+        let mut j = 0usize;
+        while j < 32 {
+            buf[j] = h[i - 1][j];
+            buf[32 + j] = h[i - 1][j];
+            j += 1;
+        }
+        h[i] = ConstSha256::new().update(&buf).finalize();
+        i += 1;
+    }
+    h
+}
+
+pub const ZERO_HASHES: [[u8; 32]; 21] = make_zero_hashes();
+
+#[inline(always)]
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Folds `leaf` up through `proof`, the same walk `verify` does on-chain.
+pub fn compute_root(leaf: &[u8; 32], index: u32, proof: &[[u8; 32]]) -> [u8; 32] {
+    let mut current_hash = *leaf;
+    let mut current_index = index;
+
+    for sibling in proof {
+        current_hash = if current_index.is_multiple_of(2) {
+            hash_pair(&current_hash, sibling)
+        } else {
+            hash_pair(sibling, &current_hash)
+        };
+        current_index >>= 1;
+    }
+
+    current_hash
+}
+
+pub fn verify_proof(root: &[u8; 32], leaf: &[u8; 32], index: u32, proof: &[[u8; 32]]) -> bool {
+    compute_root(leaf, index, proof) == *root
+}
+
+/// Leaf that only `owner` can `consume`: the program rebuilds it from the
+/// signer and the `leaf_data` argument.
+pub fn owned_leaf(owner: &[u8; 32], data: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"leaf");
+    hasher.update(owner);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Seed of the `consume` nullifier PDA:
+/// `[b"nullifier", merkle_tree, nullifier_hash(leaf, domain)]`.
+pub fn nullifier_hash(leaf: &[u8; 32], domain: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"nullifier");
+    hasher.update(leaf);
+    hasher.update(domain);
+    hasher.finalize().into()
+}
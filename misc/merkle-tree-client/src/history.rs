@@ -0,0 +1,82 @@
+use alloc::vec::Vec;
+
+use crate::{error::TreeError, tree::MerkleTree};
+
+const ANCHOR_IX_INSERT: [u8; 8] = [205, 174, 245, 70, 145, 250, 39, 168];
+const ANCHOR_IX_INSERT_BATCH: [u8; 8] = [111, 61, 13, 213, 159, 132, 216, 185];
+const ANCHOR_IX_REPLACE_LEAF: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+const ANCHOR_IX_REMOVE_LEAF: [u8; 8] = [216, 163, 144, 125, 67, 113, 134, 160];
+
+const QUASAR_IX_INSERT: u8 = 1;
+
+/// A state-changing tree instruction recovered from transaction history.
+///
+/// Only feed instructions from successful transactions: a failed
+/// `replace_leaf` never touched the account, but replaying it here would.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeOp {
+    Insert([u8; 32]),
+    InsertBatch(Vec<[u8; 32]>),
+    ReplaceLeaf { index: u32, new_leaf: [u8; 32] },
+    RemoveLeaf { index: u32 },
+}
+
+impl TreeOp {
+    /// Decodes `anchor-merkle-tree-incremental` instruction data. Returns
+    /// `None` for read-only or unknown instructions (`initialize`, `verify`).
+    pub fn from_anchor_ix(data: &[u8]) -> Option<Self> {
+        let (discriminator, args) = data.split_first_chunk::<8>()?;
+        match *discriminator {
+            ANCHOR_IX_INSERT => Some(Self::Insert(read_leaf(args, 0)?)),
+            ANCHOR_IX_INSERT_BATCH => {
+                let len = u32::from_le_bytes(*args.first_chunk::<4>()?) as usize;
+                let leaves = (0..len)
+                    .map(|i| read_leaf(args, 4 + i * 32))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(Self::InsertBatch(leaves))
+            }
+            // index, old_leaf, new_leaf, proof
+            ANCHOR_IX_REPLACE_LEAF => Some(Self::ReplaceLeaf {
+                index: u32::from_le_bytes(*args.first_chunk::<4>()?),
+                new_leaf: read_leaf(args, 36)?,
+            }),
+            ANCHOR_IX_REMOVE_LEAF => Some(Self::RemoveLeaf {
+                index: u32::from_le_bytes(*args.first_chunk::<4>()?),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Decodes `quasar-merkle-tree` instruction data (one-byte discriminator).
+    pub fn from_quasar_ix(data: &[u8]) -> Option<Self> {
+        let (discriminator, args) = data.split_first()?;
+        match *discriminator {
+            QUASAR_IX_INSERT => Some(Self::Insert(read_leaf(args, 0)?)),
+            _ => None,
+        }
+    }
+}
+
+fn read_leaf(args: &[u8], offset: usize) -> Option<[u8; 32]> {
+    args.get(offset..offset + 32)?.try_into().ok()
+}
+
+impl MerkleTree {
+    pub fn apply(&mut self, op: &TreeOp) -> Result<(), TreeError> {
+        match op {
+            TreeOp::Insert(leaf) => self.insert(*leaf).map(|_| ()),
+            TreeOp::InsertBatch(leaves) => self.insert_batch(leaves),
+            TreeOp::ReplaceLeaf { index, new_leaf } => self.replace_leaf(*index, *new_leaf),
+            TreeOp::RemoveLeaf { index } => self.remove_leaf(*index),
+        }
+    }
+
+    /// Rebuilds a tree from its instructions, oldest first.
+    pub fn replay<'a>(ops: impl IntoIterator<Item = &'a TreeOp>) -> Result<Self, TreeError> {
+        let mut tree = Self::new();
+        for op in ops {
+            tree.apply(op)?;
+        }
+        Ok(tree)
+    }
+}
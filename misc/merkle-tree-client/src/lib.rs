@@ -0,0 +1,20 @@
+//! Off-chain mirror of `anchor-merkle-tree-incremental` and `quasar-merkle-tree`.
+//!
+//! Both programs share one tree layout: depth 20, SHA-256 over `left || right`
+//! and `ZERO_HASHES[i]` for empty subtrees. [`MerkleTree`] replays the same
+//! insert algorithm, keeps the leaves around so it can hand out the
+//! `[[u8; 32]; 20]` proof for any index, and [`TreeOp`] decodes the program
+//! instructions so a tree can be rebuilt from transaction history.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod error;
+mod hash;
+mod history;
+mod tree;
+
+pub use error::TreeError;
+pub use hash::{compute_root, hash_pair, nullifier_hash, owned_leaf, verify_proof, ZERO_HASHES};
+pub use history::TreeOp;
+pub use tree::{proof_bytes, MerkleTree, Proof, DEPTH};
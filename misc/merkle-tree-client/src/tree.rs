@@ -0,0 +1,173 @@
+use alloc::vec::Vec;
+
+use crate::{
+    error::TreeError,
+    hash::{hash_pair, ZERO_HASHES},
+};
+
+pub const DEPTH: usize = 20;
+
+/// Sibling hashes from the leaf level up, as `verify` expects them.
+pub type Proof = [[u8; 32]; DEPTH];
+
+/// Client-side copy of the on-chain `MerkleTree` account plus every leaf.
+///
+/// `current_root`, `next_index` and `filled_subtrees` are updated with the
+/// exact on-chain algorithm, so after replaying the same instructions they
+/// match the account byte for byte.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    leaves: Vec<[u8; 32]>,
+    current_root: [u8; 32],
+    filled_subtrees: [[u8; 32]; DEPTH],
+}
+
+impl Default for MerkleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self {
+            leaves: Vec::new(),
+            current_root: ZERO_HASHES[DEPTH],
+            filled_subtrees: core::array::from_fn(|i| ZERO_HASHES[i]),
+        }
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        self.current_root
+    }
+
+    pub fn next_index(&self) -> u32 {
+        self.leaves.len() as u32
+    }
+
+    pub fn filled_subtrees(&self) -> &[[u8; 32]; DEPTH] {
+        &self.filled_subtrees
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.leaves
+    }
+
+    pub fn leaf(&self, index: u32) -> Option<[u8; 32]> {
+        self.leaves.get(index as usize).copied()
+    }
+
+    /// Index of the first leaf equal to `leaf`, for looking up your own entry.
+    pub fn position(&self, leaf: &[u8; 32]) -> Option<u32> {
+        self.leaves.iter().position(|l| l == leaf).map(|i| i as u32)
+    }
+
+    pub fn insert(&mut self, leaf: [u8; 32]) -> Result<u32, TreeError> {
+        if leaf == [0u8; 32] {
+            return Err(TreeError::ZeroLeaf);
+        }
+        if self.leaves.len() >= 1usize << DEPTH {
+            return Err(TreeError::TreeFull);
+        }
+
+        let index = self.next_index();
+        let mut current_index = index;
+        let mut current_hash = leaf;
+
+        for (filled, zero) in self.filled_subtrees.iter_mut().zip(&ZERO_HASHES) {
+            if current_index.is_multiple_of(2) {
+                *filled = current_hash;
+                current_hash = hash_pair(&current_hash, zero);
+            } else {
+                current_hash = hash_pair(filled, &current_hash);
+            }
+            current_index >>= 1;
+        }
+
+        self.current_root = current_hash;
+        self.leaves.push(leaf);
+        Ok(index)
+    }
+
+    /// Same checks as the on-chain `insert_batch`: the whole batch is
+    /// rejected before any leaf is added.
+    pub fn insert_batch(&mut self, leaves: &[[u8; 32]]) -> Result<(), TreeError> {
+        if leaves.is_empty() {
+            return Err(TreeError::EmptyBatch);
+        }
+        if leaves.contains(&[0u8; 32]) {
+            return Err(TreeError::ZeroLeaf);
+        }
+        if self.leaves.len() + leaves.len() > 1usize << DEPTH {
+            return Err(TreeError::TreeFull);
+        }
+
+        for leaf in leaves {
+            self.insert(*leaf)?;
+        }
+        Ok(())
+    }
+
+    pub fn replace_leaf(&mut self, index: u32, new_leaf: [u8; 32]) -> Result<(), TreeError> {
+        if new_leaf == [0u8; 32] {
+            return Err(TreeError::ZeroLeaf);
+        }
+        let proof = self.proof(index)?;
+
+        let last = self.next_index() - 1;
+        let mut current_hash = new_leaf;
+        let mut current_index = index;
+
+        for (i, sibling) in proof.iter().enumerate() {
+            if current_index == (last >> i) & !1 {
+                self.filled_subtrees[i] = current_hash;
+            }
+            current_hash = if current_index.is_multiple_of(2) {
+                hash_pair(&current_hash, sibling)
+            } else {
+                hash_pair(sibling, &current_hash)
+            };
+            current_index >>= 1;
+        }
+
+        self.current_root = current_hash;
+        self.leaves[index as usize] = new_leaf;
+        Ok(())
+    }
+
+    pub fn remove_leaf(&mut self, index: u32) -> Result<(), TreeError> {
+        self.replace_leaf(index, ZERO_HASHES[0])
+    }
+
+    pub fn proof(&self, index: u32) -> Result<Proof, TreeError> {
+        let index = index as usize;
+        if index >= self.leaves.len() {
+            return Err(TreeError::IndexOutOfRange);
+        }
+
+        let mut proof = [[0u8; 32]; DEPTH];
+        let mut level = self.leaves.clone();
+        let mut position = index;
+
+        for (i, sibling) in proof.iter_mut().enumerate() {
+            *sibling = *level.get(position ^ 1).unwrap_or(&ZERO_HASHES[i]);
+            level = level
+                .chunks(2)
+                .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&ZERO_HASHES[i])))
+                .collect();
+            position >>= 1;
+        }
+
+        Ok(proof)
+    }
+}
+
+/// Flattens a proof into the `proof_bytes: [u8; 640]` argument of the Quasar
+/// `verify` instruction.
+pub fn proof_bytes(proof: &Proof) -> [u8; 32 * DEPTH] {
+    let mut bytes = [0u8; 32 * DEPTH];
+    for (chunk, sibling) in bytes.chunks_exact_mut(32).zip(proof) {
+        chunk.copy_from_slice(sibling);
+    }
+    bytes
+}
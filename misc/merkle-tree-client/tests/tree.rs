@@ -0,0 +1,209 @@
+use merkle_tree_client::{
+    compute_root, hash_pair, proof_bytes, verify_proof, MerkleTree, TreeError, TreeOp, ZERO_HASHES,
+};
+use proptest::prelude::*;
+
+/// Root of the depth-20 tree over `leaves` computed level by level, without
+/// the incremental `filled_subtrees` bookkeeping.
+fn naive_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return ZERO_HASHES[20];
+    }
+    let mut level = leaves.to_vec();
+    for zero in ZERO_HASHES.iter().take(20) {
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(zero)))
+            .collect();
+    }
+    level[0]
+}
+
+fn anchor_insert_ix(leaf: [u8; 32]) -> Vec<u8> {
+    let mut data = vec![205, 174, 245, 70, 145, 250, 39, 168];
+    data.extend_from_slice(&leaf);
+    data
+}
+
+fn anchor_insert_batch_ix(leaves: &[[u8; 32]]) -> Vec<u8> {
+    let mut data = vec![111, 61, 13, 213, 159, 132, 216, 185];
+    data.extend_from_slice(&(leaves.len() as u32).to_le_bytes());
+    for leaf in leaves {
+        data.extend_from_slice(leaf);
+    }
+    data
+}
+
+fn anchor_replace_ix(index: u32, old_leaf: [u8; 32], new_leaf: [u8; 32]) -> Vec<u8> {
+    let mut data = vec![204, 165, 76, 100, 73, 147, 0, 128];
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&old_leaf);
+    data.extend_from_slice(&new_leaf);
+    data.extend_from_slice(&[0u8; 640]);
+    data
+}
+
+fn leaf_strategy() -> impl Strategy<Value = [u8; 32]> {
+    any::<[u8; 32]>().prop_filter("zero leaf", |leaf| *leaf != [0u8; 32])
+}
+
+#[test]
+fn empty_tree_matches_initialize() {
+    let tree = MerkleTree::new();
+    assert_eq!(tree.root(), ZERO_HASHES[20]);
+    assert_eq!(tree.next_index(), 0);
+    assert_eq!(tree.proof(0), Err(TreeError::IndexOutOfRange));
+}
+
+#[test]
+fn single_insert_matches_program_test_vector() {
+    let leaf = [1u8; 32];
+    let mut expected = leaf;
+    for zero in ZERO_HASHES.iter().take(20) {
+        expected = hash_pair(&expected, zero);
+    }
+
+    let mut tree = MerkleTree::new();
+    tree.insert(leaf).unwrap();
+    assert_eq!(tree.root(), expected);
+    assert_eq!(
+        tree.proof(0).unwrap(),
+        std::array::from_fn(|i| ZERO_HASHES[i])
+    );
+}
+
+#[test]
+fn rejects_zero_leaf_and_empty_batch() {
+    let mut tree = MerkleTree::new();
+    assert_eq!(tree.insert([0u8; 32]), Err(TreeError::ZeroLeaf));
+    assert_eq!(tree.insert_batch(&[]), Err(TreeError::EmptyBatch));
+    assert_eq!(
+        tree.insert_batch(&[[1u8; 32], [0u8; 32]]),
+        Err(TreeError::ZeroLeaf)
+    );
+    assert_eq!(tree.next_index(), 0, "failed batch must not insert");
+}
+
+#[test]
+fn proof_bytes_is_flattened_proof() {
+    let proof: [[u8; 32]; 20] = std::array::from_fn(|i| [i as u8; 32]);
+    let bytes = proof_bytes(&proof);
+    for (i, sibling) in proof.iter().enumerate() {
+        assert_eq!(&bytes[i * 32..(i + 1) * 32], sibling);
+    }
+}
+
+#[test]
+fn decodes_quasar_insert() {
+    let mut data = vec![1u8];
+    data.extend_from_slice(&[7u8; 32]);
+    assert_eq!(
+        TreeOp::from_quasar_ix(&data),
+        Some(TreeOp::Insert([7u8; 32]))
+    );
+    assert_eq!(TreeOp::from_quasar_ix(&[0u8]), None);
+    assert_eq!(TreeOp::from_quasar_ix(&data[..20]), None);
+}
+
+proptest! {
+    #[test]
+    fn root_matches_naive_tree(leaves in prop::collection::vec(leaf_strategy(), 1..48)) {
+        let mut tree = MerkleTree::new();
+        for leaf in &leaves {
+            tree.insert(*leaf).unwrap();
+        }
+        prop_assert_eq!(tree.root(), naive_root(&leaves));
+    }
+
+    #[test]
+    fn every_proof_verifies(leaves in prop::collection::vec(leaf_strategy(), 1..32)) {
+        let mut tree = MerkleTree::new();
+        tree.insert_batch(&leaves).unwrap();
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index as u32).unwrap();
+            prop_assert!(verify_proof(&tree.root(), leaf, index as u32, &proof));
+            prop_assert_eq!(compute_root(&[0xEEu8; 32], index as u32, &proof) == tree.root(), *leaf == [0xEEu8; 32]);
+        }
+    }
+
+    #[test]
+    fn replace_keeps_incremental_state_consistent(
+        leaves in prop::collection::vec(leaf_strategy(), 1..24),
+        more in prop::collection::vec(leaf_strategy(), 0..8),
+        index in any::<prop::sample::Index>(),
+        new_leaf in leaf_strategy(),
+        remove in any::<bool>(),
+    ) {
+        let mut tree = MerkleTree::new();
+        tree.insert_batch(&leaves).unwrap();
+
+        let mut expected = leaves.clone();
+        let index = index.index(leaves.len());
+        if remove {
+            tree.remove_leaf(index as u32).unwrap();
+            expected[index] = ZERO_HASHES[0];
+        } else {
+            tree.replace_leaf(index as u32, new_leaf).unwrap();
+            expected[index] = new_leaf;
+        }
+        prop_assert_eq!(tree.root(), naive_root(&expected));
+
+        // Continuing to insert exercises the updated filled_subtrees.
+        for leaf in &more {
+            tree.insert(*leaf).unwrap();
+        }
+        expected.extend_from_slice(&more);
+        prop_assert_eq!(tree.root(), naive_root(&expected));
+    }
+
+    #[test]
+    fn replay_from_anchor_history(
+        first in leaf_strategy(),
+        batch in prop::collection::vec(leaf_strategy(), 1..10),
+        new_leaf in leaf_strategy(),
+    ) {
+        let mut direct = MerkleTree::new();
+        direct.insert(first).unwrap();
+        direct.insert_batch(&batch).unwrap();
+        direct.replace_leaf(0, new_leaf).unwrap();
+
+        let history = [
+            vec![175, 175, 109, 31, 13, 152, 155, 237],
+            anchor_insert_ix(first),
+            anchor_insert_batch_ix(&batch),
+            anchor_replace_ix(0, first, new_leaf),
+        ];
+        let ops: Vec<TreeOp> = history
+            .iter()
+            .filter_map(|data| TreeOp::from_anchor_ix(data))
+            .collect();
+        prop_assert_eq!(ops.len(), 3);
+
+        let replayed = MerkleTree::replay(&ops).unwrap();
+        prop_assert_eq!(replayed, direct);
+    }
+}
+
+#[test]
+fn nullifier_hash_is_domain_separated() {
+    use merkle_tree_client::nullifier_hash;
+
+    let leaf = [3u8; 32];
+    assert_ne!(
+        nullifier_hash(&leaf, &[1u8; 32]),
+        nullifier_hash(&leaf, &[2u8; 32])
+    );
+    assert_ne!(
+        nullifier_hash(&leaf, &[1u8; 32]),
+        hash_pair(&leaf, &[1u8; 32])
+    );
+}
+
+#[test]
+fn owned_leaf_binds_the_owner() {
+    use merkle_tree_client::owned_leaf;
+
+    let data = [3u8; 32];
+    assert_ne!(owned_leaf(&[1u8; 32], &data), owned_leaf(&[2u8; 32], &data));
+    assert_ne!(owned_leaf(&[1u8; 32], &data), hash_pair(&[1u8; 32], &data));
+}
@@ -0,0 +1,21 @@
+use pinocchio::program_error::ProgramError;
+
+// Same variants, in the same order, as the Anchor AMM's `AmmError`
+#[derive(Clone, Copy, PartialEq)]
+pub enum AmmError {
+    InvalidFee,
+    InvalidMintOrder,
+    InvalidAmount,
+    PoolLocked,
+    SlippageExceeded,
+    NoLiquidity,
+    Unauthorized,
+    Overflow,
+}
+
+impl From<AmmError> for ProgramError {
+    // Anchor numbers custom errors from 6000; match its codes
+    fn from(e: AmmError) -> Self {
+        Self::Custom(6000 + e as u32)
+    }
+}
@@ -0,0 +1,188 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::create_program_address,
+    ProgramResult,
+};
+use pinocchio_token::{
+    instructions::{MintTo, Transfer},
+    state::Mint,
+};
+
+use crate::curve::deposit_amounts;
+use crate::instructions::helpers::{check_token_account, load_config, vault_amount};
+use crate::{AmmError, Config};
+
+// Mint `amount` LP tokens for at most `max_x` and `max_y` deposited.
+// Accounts: [user, mint_lp, vault_x, vault_y, user_x, user_y, user_lp,
+// config, token_program]. Withdraw takes the same accounts.
+
+pub struct LiquidityAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub user_x: &'a AccountInfo,
+    pub user_y: &'a AccountInfo,
+    pub user_lp: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub state: Config,
+    pub supply: u64,
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for LiquidityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, mint_lp, vault_x, vault_y, user_x, user_y, user_lp, config, _token_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !user.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let state = load_config(config)?;
+
+        let lp_key = create_program_address(
+            &[Config::LP_SEED, config.key().as_ref(), &[state.lp_bump]],
+            &crate::ID,
+        )?;
+        if mint_lp.key().ne(&lp_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let supply = Mint::from_account_info(mint_lp)?.supply();
+
+        let reserve_x = vault_amount(vault_x, config.key(), &state.mint_x)?;
+        let reserve_y = vault_amount(vault_y, config.key(), &state.mint_y)?;
+
+        check_token_account(user_x, &state.mint_x, user.key())?;
+        check_token_account(user_y, &state.mint_y, user.key())?;
+        check_token_account(user_lp, mint_lp.key(), user.key())?;
+
+        Ok(Self {
+            user,
+            mint_lp,
+            vault_x,
+            vault_y,
+            user_x,
+            user_y,
+            user_lp,
+            config,
+            state,
+            supply,
+            reserve_x,
+            reserve_y,
+        })
+    }
+}
+
+// amount: u64 followed by two u64 slippage limits; shared with withdraw
+pub struct LiquidityInstructionData {
+    pub amount: u64,
+    pub limit_x: u64,
+    pub limit_y: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for LiquidityInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() * 3 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        if amount.eq(&0) {
+            return Err(AmmError::InvalidAmount.into());
+        }
+
+        Ok(Self {
+            amount,
+            limit_x: u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            limit_y: u64::from_le_bytes(data[16..24].try_into().unwrap()),
+        })
+    }
+}
+
+pub struct Deposit<'a> {
+    pub accounts: LiquidityAccounts<'a>,
+    pub instruction_datas: LiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Deposit<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = LiquidityAccounts::try_from(accounts)?;
+        let instruction_datas = LiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> Deposit<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &1;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_datas;
+
+        if accounts.state.locked {
+            return Err(AmmError::PoolLocked.into());
+        }
+
+        // The first deposit sets the price; later ones match the reserves
+        let (x, y) = match accounts.supply {
+            0 => (data.limit_x, data.limit_y),
+            supply => deposit_amounts(data.amount, accounts.reserve_x, accounts.reserve_y, supply)?,
+        };
+        if x == 0 || y == 0 {
+            return Err(AmmError::InvalidAmount.into());
+        }
+        if x > data.limit_x || y > data.limit_y {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Transfer {
+            from: accounts.user_x,
+            to: accounts.vault_x,
+            authority: accounts.user,
+            amount: x,
+        }
+        .invoke()?;
+        Transfer {
+            from: accounts.user_y,
+            to: accounts.vault_y,
+            authority: accounts.user,
+            amount: y,
+        }
+        .invoke()?;
+
+        let bump = [accounts.state.config_bump];
+        let seeds = [
+            Seed::from(Config::SEED_PREFIX),
+            Seed::from(accounts.state.mint_x.as_ref()),
+            Seed::from(accounts.state.mint_y.as_ref()),
+            Seed::from(&bump),
+        ];
+        MintTo {
+            mint: accounts.mint_lp,
+            account: accounts.user_lp,
+            mint_authority: accounts.config,
+            amount: data.amount,
+        }
+        .invoke_signed(&[Signer::from(&seeds)])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+};
+use pinocchio_token::state::TokenAccount;
+
+use crate::Config;
+
+// Read the pool config, which only this program can have written
+pub fn load_config(config: &AccountInfo) -> Result<Config, ProgramError> {
+    if !config.is_owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Config::load(&config.try_borrow_data()?)
+}
+
+// Check `vault` is the pool's associated token account for `mint` and
+// return its balance. Any account can name the config as its owner, so only
+// the ATA address proves it's the pool's vault.
+pub fn vault_amount(
+    vault: &AccountInfo,
+    config: &Pubkey,
+    mint: &Pubkey,
+) -> Result<u64, ProgramError> {
+    let (vault_key, _) = find_program_address(
+        &[config.as_ref(), pinocchio_token::ID.as_ref(), mint.as_ref()],
+        &pinocchio_associated_token_account::ID,
+    );
+    if vault.key().ne(&vault_key) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(TokenAccount::from_account_info(vault)?.amount())
+}
+
+// Check a user's token account holds `mint` and belongs to `owner`
+pub fn check_token_account(
+    account: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<(), ProgramError> {
+    let token_account = TokenAccount::from_account_info(account)?;
+    if token_account.mint().ne(mint) || token_account.owner().ne(owner) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
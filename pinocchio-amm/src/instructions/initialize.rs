@@ -0,0 +1,213 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+    ProgramResult,
+};
+use pinocchio_associated_token_account::instructions::CreateIdempotent;
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token::{instructions::InitializeMint2, state::Mint};
+
+use crate::instructions::helpers::{check_token_account, vault_amount};
+use crate::{AmmError, Config, LP_DECIMALS, MAX_FEE_BPS};
+
+// Create the pool config, LP mint and both vaults for a mint pair.
+// Accounts: [initializer, mint_x, mint_y, config, mint_lp, vault_x, vault_y,
+// token_program, associated_token_program, system_program]
+
+pub struct InitializeAccounts<'a> {
+    pub initializer: &'a AccountInfo,
+    pub mint_x: &'a AccountInfo,
+    pub mint_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub mint_lp: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub config_bump: [u8; 1],
+    pub lp_bump: [u8; 1],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [initializer, mint_x, mint_y, config, mint_lp, vault_x, vault_y, token_program, _associated_token_program, system_program] =
+            accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !initializer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        // Both must be SPL Token mints
+        Mint::from_account_info(mint_x)?;
+        Mint::from_account_info(mint_y)?;
+
+        // One pool per pair: the ordering makes (x, y) and (y, x) the same pool
+        if mint_x.key() >= mint_y.key() {
+            return Err(AmmError::InvalidMintOrder.into());
+        }
+
+        let (config_key, config_bump) = find_program_address(
+            &[
+                Config::SEED_PREFIX,
+                mint_x.key().as_ref(),
+                mint_y.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if config.key().ne(&config_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let (lp_key, lp_bump) =
+            find_program_address(&[Config::LP_SEED, config.key().as_ref()], &crate::ID);
+        if mint_lp.key().ne(&lp_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            initializer,
+            mint_x,
+            mint_y,
+            config,
+            mint_lp,
+            vault_x,
+            vault_y,
+            token_program,
+            system_program,
+            config_bump: [config_bump],
+            lp_bump: [lp_bump],
+        })
+    }
+}
+
+pub struct InitializeInstructionData {
+    pub fee: u16,
+    pub authority: Pubkey,
+}
+
+impl<'a> TryFrom<&'a [u8]> for InitializeInstructionData {
+    type Error = ProgramError;
+
+    // fee: u16, authority: Pubkey
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u16>() + size_of::<Pubkey>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let fee = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        if fee > MAX_FEE_BPS {
+            return Err(AmmError::InvalidFee.into());
+        }
+
+        Ok(Self {
+            fee,
+            authority: data[2..34].try_into().unwrap(),
+        })
+    }
+}
+
+pub struct Initialize<'a> {
+    pub accounts: InitializeAccounts<'a>,
+    pub instruction_datas: InitializeInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Initialize<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = InitializeAccounts::try_from(accounts)?;
+        let instruction_datas = InitializeInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> Initialize<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &0;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let rent = Rent::get()?;
+
+        let config_seeds = [
+            Seed::from(Config::SEED_PREFIX),
+            Seed::from(self.accounts.mint_x.key().as_ref()),
+            Seed::from(self.accounts.mint_y.key().as_ref()),
+            Seed::from(&self.accounts.config_bump),
+        ];
+        CreateAccount {
+            from: self.accounts.initializer,
+            to: self.accounts.config,
+            lamports: rent.minimum_balance(Config::LEN),
+            space: Config::LEN as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[Signer::from(&config_seeds)])?;
+
+        let lp_seeds = [
+            Seed::from(Config::LP_SEED),
+            Seed::from(self.accounts.config.key().as_ref()),
+            Seed::from(&self.accounts.lp_bump),
+        ];
+        CreateAccount {
+            from: self.accounts.initializer,
+            to: self.accounts.mint_lp,
+            lamports: rent.minimum_balance(Mint::LEN),
+            space: Mint::LEN as u64,
+            owner: &pinocchio_token::ID,
+        }
+        .invoke_signed(&[Signer::from(&lp_seeds)])?;
+
+        InitializeMint2 {
+            mint: self.accounts.mint_lp,
+            decimals: LP_DECIMALS,
+            mint_authority: self.accounts.config.key(),
+            freeze_authority: None,
+        }
+        .invoke()?;
+
+        // The vaults' addresses are known from the mints, so anyone can create
+        // them first; an existing one is accepted once its mint and owner check out
+        for (vault, mint) in [
+            (self.accounts.vault_x, self.accounts.mint_x),
+            (self.accounts.vault_y, self.accounts.mint_y),
+        ] {
+            CreateIdempotent {
+                funding_account: self.accounts.initializer,
+                account: vault,
+                wallet: self.accounts.config,
+                mint,
+                system_program: self.accounts.system_program,
+                token_program: self.accounts.token_program,
+            }
+            .invoke()?;
+
+            vault_amount(vault, self.accounts.config.key(), mint.key())?;
+            check_token_account(vault, mint.key(), self.accounts.config.key())?;
+        }
+
+        Config {
+            authority: self.instruction_datas.authority,
+            mint_x: *self.accounts.mint_x.key(),
+            mint_y: *self.accounts.mint_y.key(),
+            fee: self.instruction_datas.fee,
+            locked: false,
+            config_bump: self.accounts.config_bump[0],
+            lp_bump: self.accounts.lp_bump[0],
+        }
+        .store(&mut self.accounts.config.try_borrow_mut_data()?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+use crate::instructions::helpers::load_config;
+use crate::{AmmError, Config};
+
+// Authority-only: stop or resume deposits and swaps.
+// Accounts: [authority, config]
+
+pub struct SetLockedAccounts<'a> {
+    pub config: &'a AccountInfo,
+    pub state: Config,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetLockedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [authority, config] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !authority.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let state = load_config(config)?;
+        if state.authority.ne(authority.key()) {
+            return Err(AmmError::Unauthorized.into());
+        }
+
+        Ok(Self { config, state })
+    }
+}
+
+pub struct SetLocked<'a> {
+    pub accounts: SetLockedAccounts<'a>,
+    pub locked: bool,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetLocked<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetLockedAccounts::try_from(accounts)?;
+        let locked = match data {
+            [0] => false,
+            [1] => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { accounts, locked })
+    }
+}
+
+impl<'a> SetLocked<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        self.accounts.state.locked = self.locked;
+        self.accounts
+            .state
+            .store(&mut self.accounts.config.try_borrow_mut_data()?);
+
+        Ok(())
+    }
+}
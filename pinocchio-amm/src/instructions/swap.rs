@@ -0,0 +1,181 @@
+use core::mem::size_of;
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+use pinocchio_token::instructions::Transfer;
+
+use crate::curve::swap_out;
+use crate::instructions::helpers::{check_token_account, load_config, vault_amount};
+use crate::{AmmError, Config};
+
+// Swap `amount` of x for y (or y for x), receiving at least `min_out`.
+// Accounts: [user, vault_x, vault_y, user_x, user_y, config, token_program]
+
+pub struct SwapAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub vault_x: &'a AccountInfo,
+    pub vault_y: &'a AccountInfo,
+    pub user_x: &'a AccountInfo,
+    pub user_y: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub state: Config,
+    pub reserve_x: u64,
+    pub reserve_y: u64,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SwapAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, vault_x, vault_y, user_x, user_y, config, _token_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if !user.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let state = load_config(config)?;
+        let reserve_x = vault_amount(vault_x, config.key(), &state.mint_x)?;
+        let reserve_y = vault_amount(vault_y, config.key(), &state.mint_y)?;
+
+        check_token_account(user_x, &state.mint_x, user.key())?;
+        check_token_account(user_y, &state.mint_y, user.key())?;
+
+        Ok(Self {
+            user,
+            vault_x,
+            vault_y,
+            user_x,
+            user_y,
+            config,
+            state,
+            reserve_x,
+            reserve_y,
+        })
+    }
+}
+
+pub struct SwapInstructionData {
+    pub is_x: bool,
+    pub amount: u64,
+    pub min_out: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SwapInstructionData {
+    type Error = ProgramError;
+
+    // is_x: bool, amount: u64, min_out: u64
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != 1 + size_of::<u64>() * 2 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let is_x = match data[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        let amount = u64::from_le_bytes(data[1..9].try_into().unwrap());
+        if amount.eq(&0) {
+            return Err(AmmError::InvalidAmount.into());
+        }
+
+        Ok(Self {
+            is_x,
+            amount,
+            min_out: u64::from_le_bytes(data[9..17].try_into().unwrap()),
+        })
+    }
+}
+
+pub struct Swap<'a> {
+    pub accounts: SwapAccounts<'a>,
+    pub instruction_datas: SwapInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Swap<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SwapAccounts::try_from(accounts)?;
+        let instruction_datas = SwapInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> Swap<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_datas;
+
+        if accounts.state.locked {
+            return Err(AmmError::PoolLocked.into());
+        }
+
+        let (user_in, vault_in, reserve_in, vault_out, user_out, reserve_out) = if data.is_x {
+            (
+                accounts.user_x,
+                accounts.vault_x,
+                accounts.reserve_x,
+                accounts.vault_y,
+                accounts.user_y,
+                accounts.reserve_y,
+            )
+        } else {
+            (
+                accounts.user_y,
+                accounts.vault_y,
+                accounts.reserve_y,
+                accounts.vault_x,
+                accounts.user_x,
+                accounts.reserve_x,
+            )
+        };
+        if reserve_in == 0 || reserve_out == 0 {
+            return Err(AmmError::NoLiquidity.into());
+        }
+
+        let out = swap_out(data.amount, reserve_in, reserve_out, accounts.state.fee)?;
+        if out == 0 {
+            return Err(AmmError::InvalidAmount.into());
+        }
+        if out < data.min_out {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Transfer {
+            from: user_in,
+            to: vault_in,
+            authority: accounts.user,
+            amount: data.amount,
+        }
+        .invoke()?;
+
+        let bump = [accounts.state.config_bump];
+        let seeds = [
+            Seed::from(Config::SEED_PREFIX),
+            Seed::from(accounts.state.mint_x.as_ref()),
+            Seed::from(accounts.state.mint_y.as_ref()),
+            Seed::from(&bump),
+        ];
+        Transfer {
+            from: vault_out,
+            to: user_out,
+            authority: accounts.config,
+            amount: out,
+        }
+        .invoke_signed(&[Signer::from(&seeds)])?;
+
+        Ok(())
+    }
+}
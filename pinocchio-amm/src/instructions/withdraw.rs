@@ -0,0 +1,91 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+use pinocchio_token::instructions::{Burn, Transfer};
+
+use crate::curve::withdraw_amounts;
+use crate::instructions::deposit::{LiquidityAccounts, LiquidityInstructionData};
+use crate::{AmmError, Config};
+
+// Burn `amount` LP tokens for at least `min_x` and `min_y` back. Takes the
+// same accounts as deposit. Withdrawals stay open while the pool is locked,
+// so LPs can always exit.
+
+pub struct Withdraw<'a> {
+    pub accounts: LiquidityAccounts<'a>,
+    pub instruction_datas: LiquidityInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Withdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = LiquidityAccounts::try_from(accounts)?;
+        let instruction_datas = LiquidityInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> Withdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let accounts = &self.accounts;
+        let data = &self.instruction_datas;
+
+        if accounts.supply == 0 {
+            return Err(AmmError::NoLiquidity.into());
+        }
+
+        let (x, y) = withdraw_amounts(
+            data.amount,
+            accounts.reserve_x,
+            accounts.reserve_y,
+            accounts.supply,
+        )?;
+        if x < data.limit_x || y < data.limit_y {
+            return Err(AmmError::SlippageExceeded.into());
+        }
+
+        Burn {
+            account: accounts.user_lp,
+            mint: accounts.mint_lp,
+            authority: accounts.user,
+            amount: data.amount,
+        }
+        .invoke()?;
+
+        let bump = [accounts.state.config_bump];
+        let seeds = [
+            Seed::from(Config::SEED_PREFIX),
+            Seed::from(accounts.state.mint_x.as_ref()),
+            Seed::from(accounts.state.mint_y.as_ref()),
+            Seed::from(&bump),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        Transfer {
+            from: accounts.vault_x,
+            to: accounts.user_x,
+            authority: accounts.config,
+            amount: x,
+        }
+        .invoke_signed(&signers)?;
+        Transfer {
+            from: accounts.vault_y,
+            to: accounts.user_y,
+            authority: accounts.config,
+            amount: y,
+        }
+        .invoke_signed(&signers)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+#![no_std]
+
+use pinocchio::{
+    account_info::AccountInfo, entrypoint, nostd_panic_handler, program_error::ProgramError,
+    pubkey::Pubkey, ProgramResult,
+};
+
+pub mod curve;
+pub mod errors;
+pub mod instructions;
+pub mod state;
+
+pub use errors::*;
+pub use instructions::*;
+pub use state::*;
+
+entrypoint!(process_instruction);
+nostd_panic_handler!();
+
+// 2m1VvswgrjLWy2Sa3UfeKHGGWs3HUskhMTwZRQjk1uVq
+pub const ID: Pubkey = [
+    0x1a, 0x21, 0xce, 0x76, 0x43, 0xe6, 0xaf, 0x4e, 0x14, 0x37, 0xdd, 0xbb, 0x48, 0x06, 0x61, 0xb0,
+    0x4f, 0xf4, 0x67, 0x66, 0x38, 0x2c, 0x55, 0xac, 0xcd, 0xda, 0xa8, 0x6e, 0x0d, 0xaf, 0xff, 0x88,
+];
+
+// Instruction data matches the Anchor AMM's (1-byte discriminator, then
+// Borsh-encoded args), and so do the accounts, so either program accepts the
+// same instructions.
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match instruction_data.split_first() {
+        Some((Initialize::DISCRIMINATOR, data)) => {
+            Initialize::try_from((data, accounts))?.process()
+        }
+        Some((Deposit::DISCRIMINATOR, data)) => Deposit::try_from((data, accounts))?.process(),
+        Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
+        Some((Swap::DISCRIMINATOR, data)) => Swap::try_from((data, accounts))?.process(),
+        Some((SetLocked::DISCRIMINATOR, data)) => SetLocked::try_from((data, accounts))?.process(),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,58 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+// Upper bound on the swap fee, 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+// Decimals of every pool's LP mint
+pub const LP_DECIMALS: u8 = 6;
+
+// A pool for one mint pair, stored at ["config", mint_x, mint_y]. Same layout
+// as the Anchor AMM's `Config`, minus its 8-byte discriminator.
+//
+// Offset 0-31:  authority (default pubkey: nobody can lock the pool)
+// Offset 32-63: mint_x
+// Offset 64-95: mint_y
+// Offset 96-97: fee (u16, basis points)
+// Offset 98:    locked (bool)
+// Offset 99:    config_bump
+// Offset 100:   lp_bump
+pub struct Config {
+    pub authority: Pubkey,
+    pub mint_x: Pubkey,
+    pub mint_y: Pubkey,
+    pub fee: u16,
+    pub locked: bool,
+    pub config_bump: u8,
+    pub lp_bump: u8,
+}
+
+impl Config {
+    pub const LEN: usize = 32 + 32 + 32 + 2 + 1 + 1 + 1;
+    pub const SEED_PREFIX: &'static [u8] = b"config";
+    pub const LP_SEED: &'static [u8] = b"lp";
+
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            authority: data[0..32].try_into().unwrap(),
+            mint_x: data[32..64].try_into().unwrap(),
+            mint_y: data[64..96].try_into().unwrap(),
+            fee: u16::from_le_bytes(data[96..98].try_into().unwrap()),
+            locked: data[98] != 0,
+            config_bump: data[99],
+            lp_bump: data[100],
+        })
+    }
+
+    pub fn store(&self, data: &mut [u8]) {
+        data[0..32].copy_from_slice(&self.authority);
+        data[32..64].copy_from_slice(&self.mint_x);
+        data[64..96].copy_from_slice(&self.mint_y);
+        data[96..98].copy_from_slice(&self.fee.to_le_bytes());
+        data[98] = self.locked as u8;
+        data[99] = self.config_bump;
+        data[100] = self.lp_bump;
+    }
+}
@@ -8,9 +8,10 @@ use pinocchio::{
     nostd_panic_handler,
     program_error::ProgramError,
     pubkey::{find_program_address, Pubkey},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
-use pinocchio_system::instructions::Transfer;
+use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
 
 entrypoint!(process_instruction);
 nostd_panic_handler!();
@@ -27,13 +28,25 @@ fn process_instruction(
 ) -> ProgramResult {
     match instruction_data.split_first() {
         Some((Deposit::DISCRIMINATOR, data)) => Deposit::try_from((data, accounts))?.process(),
-        Some((Withdraw::DISCRIMINATOR, _)) => Withdraw::try_from(accounts)?.process(),
+        Some((Withdraw::DISCRIMINATOR, data)) => Withdraw::try_from((data, accounts))?.process(),
+        Some((DepositLocked::DISCRIMINATOR, data)) => {
+            DepositLocked::try_from((data, accounts))?.process()
+        }
+        Some((SetDelegate::DISCRIMINATOR, data)) => {
+            SetDelegate::try_from((data, accounts))?.process()
+        }
+        Some((RevokeDelegate::DISCRIMINATOR, _)) => RevokeDelegate::try_from(accounts)?.process(),
+        Some((DelegateWithdraw::DISCRIMINATOR, data)) => {
+            DelegateWithdraw::try_from((data, accounts))?.process()
+        }
         _ => Err(ProgramError::InvalidInstructionData),
     }
 }
 
-// Create an empty vault PDA for the caller if it doesn’t exist.
+// Create a vault PDA for the caller if it doesn’t exist, or top it up.
 // Move lamports from the signer to that vault.
+// Instruction data matches the Anchor vault's (1-byte discriminator, then
+// Borsh-encoded args), so either program accepts the same instructions.
 
 pub struct DepositAccounts<'a> {
     pub owner: &'a AccountInfo,
@@ -57,10 +70,6 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             return Err(ProgramError::InvalidAccountOwner);
         }
 
-        if vault.lamports().ne(&0) {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
         let (vault_key, _) = find_program_address(&[b"vault", owner.key()], &crate::ID);
         if vault.key().ne(&vault_key) {
             return Err(ProgramError::InvalidAccountOwner);
@@ -117,6 +126,13 @@ impl<'a> Deposit<'a> {
     pub const DISCRIMINATOR: &'a u8 = &0;
 
     pub fn process(&mut self) -> ProgramResult {
+        // A new vault must start out rent exempt
+        if self.accounts.vault.lamports().eq(&0)
+            && self.instruction_datas.amount <= Rent::get()?.minimum_balance(0)
+        {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
         Transfer {
             from: self.accounts.owner,
             to: self.accounts.vault,
@@ -131,6 +147,8 @@ impl<'a> Deposit<'a> {
 pub struct WithdrawAccounts<'a> {
     pub owner: &'a AccountInfo,
     pub vault: &'a AccountInfo,
+    pub lock: &'a AccountInfo,
+    pub depositor: Option<&'a AccountInfo>,
     pub bumps: [u8; 1],
 }
 
@@ -139,7 +157,10 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
     type Error = ProgramError;
 
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
-        let [owner, vault, _system_program] = accounts else {
+        // The lock is always required (empty when the vault isn't locked),
+        // so a locked vault can't be drained by leaving it out. The lock's
+        // depositor may follow, to be refunded when the lock closes.
+        let [owner, vault, _system_program, lock, rest @ ..] = accounts else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
@@ -157,25 +178,60 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             return Err(ProgramError::InvalidAccountOwner);
         }
 
+        let (lock_key, _) = find_program_address(&[b"lock", vault.key().as_ref()], &crate::ID);
+        if &lock_key != lock.key() {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
         Ok(Self {
             owner,
             vault,
+            lock,
+            depositor: rest.first(),
             bumps: [bump],
         })
     }
 }
 
+pub struct WithdrawInstructionData {
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data.try_into().unwrap());
+
+        // Instruction Checks
+        if amount.eq(&0) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { amount })
+    }
+}
+
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
+    pub instruction_datas: WithdrawInstructionData,
 }
 
-impl<'a> TryFrom<&'a [AccountInfo]> for Withdraw<'a> {
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for Withdraw<'a> {
     type Error = ProgramError;
 
-    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = WithdrawAccounts::try_from(accounts)?;
+        let instruction_datas = WithdrawInstructionData::try_from(data)?;
 
-        Ok(Self { accounts })
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
     }
 }
 
@@ -191,13 +247,777 @@ impl<'a> Withdraw<'a> {
         ];
         let signers = [Signer::from(&seeds)];
 
+        let balance = self.accounts.vault.lamports();
+        let lock = if self.accounts.lock.data_is_empty() {
+            None
+        } else {
+            if !self.accounts.lock.is_owned_by(&crate::ID) {
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+            Some(VaultLock::load(&self.accounts.lock.try_borrow_data()?)?)
+        };
+
+        let amount = self.instruction_datas.amount;
+        if amount > balance {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // The vault is either left rent exempt or emptied entirely
+        let rent_minimum = Rent::get()?.minimum_balance(0);
+        if amount.ne(&balance) && balance - amount < rent_minimum {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        if let Some(lock) = &lock {
+            let available =
+                lock.withdrawable_from(balance, Clock::get()?.unix_timestamp, rent_minimum);
+            if amount > available {
+                return Err(ProgramError::InsufficientFunds);
+            }
+        }
+
         Transfer {
             from: self.accounts.vault,
             to: self.accounts.owner,
-            lamports: self.accounts.vault.lamports(),
+            lamports: amount,
         }
         .invoke_signed(&signers)?;
 
+        if let Some(mut lock) = lock {
+            if amount == balance {
+                // Drained: close the lock so the vault can be reused, refunding
+                // its rent to whoever paid it
+                let refund_to = if &lock.depositor == self.accounts.owner.key() {
+                    self.accounts.owner
+                } else {
+                    match self.accounts.depositor {
+                        Some(depositor) if depositor.key() == &lock.depositor => depositor,
+                        _ => return Err(ProgramError::InvalidArgument),
+                    }
+                };
+                *refund_to.try_borrow_mut_lamports()? += self.accounts.lock.lamports();
+                self.accounts.lock.close()?;
+            } else {
+                lock.withdrawn += amount;
+                lock.store(&mut self.accounts.lock.try_borrow_mut_data()?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Create a program-owned PDA of `space` bytes. CreateAccount fails once
+// anyone has sent lamports to the address, so a funded one is topped up to
+// rent exemption, then allocated and assigned instead.
+fn create_pda(
+    payer: &AccountInfo,
+    account: &AccountInfo,
+    space: usize,
+    signers: &[Signer],
+) -> ProgramResult {
+    let rent = Rent::get()?.minimum_balance(space);
+    if account.lamports() == 0 {
+        return CreateAccount {
+            from: payer,
+            to: account,
+            lamports: rent,
+            space: space as u64,
+            owner: &crate::ID,
+        }
+        .invoke_signed(signers);
+    }
+
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        Transfer {
+            from: payer,
+            to: account,
+            lamports: top_up,
+        }
+        .invoke()?;
+    }
+    Allocate {
+        account,
+        space: space as u64,
+    }
+    .invoke_signed(signers)?;
+    Assign {
+        account,
+        owner: &crate::ID,
+    }
+    .invoke_signed(signers)
+}
+
+// Lock a deposit into `beneficiary`'s vault under a cliff or linear schedule.
+// The schedule lives in a lock PDA ["lock", vault] that `Withdraw` enforces.
+
+pub struct DepositLockedAccounts<'a> {
+    pub depositor: &'a AccountInfo,
+    pub beneficiary: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub lock: &'a AccountInfo,
+    pub lock_bump: [u8; 1],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositLockedAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [depositor, beneficiary, vault, lock, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Accounts Checks
+        // The beneficiary signs too, so nobody can lock a vault they don't own
+        if !depositor.is_signer() || !beneficiary.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !vault.is_owned_by(&pinocchio_system::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        if vault.lamports().ne(&0) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (vault_key, _) = find_program_address(&[b"vault", beneficiary.key()], &crate::ID);
+        if vault.key().ne(&vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        if !lock.data_is_empty() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        let (lock_key, lock_bump) = find_program_address(&[b"lock", vault.key()], &crate::ID);
+        if lock.key().ne(&lock_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self {
+            depositor,
+            beneficiary,
+            vault,
+            lock,
+            lock_bump: [lock_bump],
+        })
+    }
+}
+
+pub struct DepositLockedInstructionData {
+    pub amount: u64,
+    pub schedule: LockSchedule,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DepositLockedInstructionData {
+    type Error = ProgramError;
+
+    // amount: u64, kind: u8, start: i64, end: i64 (little-endian)
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<u64>() + LockSchedule::LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let schedule = LockSchedule::load(&data[8..])?;
+
+        // Instruction Checks
+        if amount.eq(&0) {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { amount, schedule })
+    }
+}
+
+pub struct DepositLocked<'a> {
+    pub accounts: DepositLockedAccounts<'a>,
+    pub instruction_datas: DepositLockedInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DepositLocked<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DepositLockedAccounts::try_from(accounts)?;
+        let instruction_datas = DepositLockedInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> DepositLocked<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &2;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let seeds = [
+            Seed::from(b"lock"),
+            Seed::from(self.accounts.vault.key().as_ref()),
+            Seed::from(&self.accounts.lock_bump),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        create_pda(
+            self.accounts.depositor,
+            self.accounts.lock,
+            VaultLock::LEN,
+            &signers,
+        )?;
+
+        Transfer {
+            from: self.accounts.depositor,
+            to: self.accounts.vault,
+            lamports: self.instruction_datas.amount,
+        }
+        .invoke()?;
+
+        VaultLock {
+            depositor: *self.accounts.depositor.key(),
+            schedule: self.instruction_datas.schedule,
+            total: self.instruction_datas.amount,
+            withdrawn: 0,
+            bump: self.accounts.lock_bump[0],
+        }
+        .store(&mut self.accounts.lock.try_borrow_mut_data()?);
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum LockKind {
+    // Everything unlocks at `end`
+    Cliff = 0,
+    // Unlocks linearly from `start` to `end`
+    Linear = 1,
+}
+
+#[derive(Clone, Copy)]
+pub struct LockSchedule {
+    pub kind: LockKind,
+    pub start: i64,
+    pub end: i64,
+}
+
+impl LockSchedule {
+    pub const LEN: usize = 1 + 8 + 8;
+
+    // Parse and validate kind: u8, start: i64, end: i64
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        let kind = match data[0] {
+            0 => LockKind::Cliff,
+            1 => LockKind::Linear,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+        let start = i64::from_le_bytes(data[1..9].try_into().unwrap());
+        let end = i64::from_le_bytes(data[9..17].try_into().unwrap());
+
+        let valid = match kind {
+            LockKind::Cliff => end >= start,
+            LockKind::Linear => end > start,
+        };
+        if !valid {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { kind, start, end })
+    }
+
+    pub fn store(&self, data: &mut [u8]) {
+        data[0] = self.kind as u8;
+        data[1..9].copy_from_slice(&self.start.to_le_bytes());
+        data[9..17].copy_from_slice(&self.end.to_le_bytes());
+    }
+
+    // How much of `total` has unlocked at `now`
+    pub fn vested(&self, total: u64, now: i64) -> u64 {
+        if now >= self.end {
+            return total;
+        }
+        match self.kind {
+            LockKind::Cliff => 0,
+            LockKind::Linear if now <= self.start => 0,
+            LockKind::Linear => {
+                // In i128 so extreme timestamps can't overflow the subtraction
+                let elapsed = (now as i128 - self.start as i128) as u128;
+                let duration = (self.end as i128 - self.start as i128) as u128;
+                (total as u128 * elapsed / duration) as u64
+            }
+        }
+    }
+}
+
+// Lock schedule of a vault, stored at ["lock", vault]. Same layout as the
+// Anchor vault's `VaultLock`, minus its 8-byte discriminator.
+pub struct VaultLock {
+    pub depositor: Pubkey,
+    pub schedule: LockSchedule,
+    pub total: u64,
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl VaultLock {
+    pub const LEN: usize = 32 + LockSchedule::LEN + 8 + 8 + 1;
+
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            depositor: data[0..32].try_into().unwrap(),
+            schedule: LockSchedule::load(&data[32..49])?,
+            total: u64::from_le_bytes(data[49..57].try_into().unwrap()),
+            withdrawn: u64::from_le_bytes(data[57..65].try_into().unwrap()),
+            bump: data[65],
+        })
+    }
+
+    pub fn store(&self, data: &mut [u8]) {
+        data[0..32].copy_from_slice(&self.depositor);
+        self.schedule.store(&mut data[32..49]);
+        data[49..57].copy_from_slice(&self.total.to_le_bytes());
+        data[57..65].copy_from_slice(&self.withdrawn.to_le_bytes());
+        data[65] = self.bump;
+    }
+
+    // Lamports that can leave a vault holding `balance` at `now`.
+    // Until everything has vested, the vault keeps its rent-exempt minimum.
+    pub fn withdrawable_from(&self, balance: u64, now: i64, rent_exempt_minimum: u64) -> u64 {
+        let vested = self.schedule.vested(self.total, now);
+        if vested == self.total {
+            return balance;
+        }
+        (vested - self.withdrawn).min(balance.saturating_sub(rent_exempt_minimum))
+    }
+}
+
+// Grant a delegate a capped allowance on the owner's vault: at most
+// `max_per_period` lamports per rolling `period` seconds, only to allowed
+// destinations. The allowance lives in a PDA ["delegate", vault, delegate];
+// setting it again replaces it and clears what it has spent.
+
+pub struct SetDelegateAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub delegate: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub allowance: &'a AccountInfo,
+    pub allowance_bump: [u8; 1],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, delegate, vault, allowance, _system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Accounts Checks
+        if !owner.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (vault_key, _) = find_program_address(&[b"vault", owner.key()], &crate::ID);
+        if vault.key().ne(&vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let (allowance_key, allowance_bump) =
+            find_program_address(&[b"delegate", vault.key(), delegate.key()], &crate::ID);
+        if allowance.key().ne(&allowance_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if !allowance.data_is_empty() && !allowance.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        Ok(Self {
+            owner,
+            delegate,
+            vault,
+            allowance,
+            allowance_bump: [allowance_bump],
+        })
+    }
+}
+
+pub struct SetDelegateInstructionData {
+    pub max_per_period: u64,
+    pub period: i64,
+    pub destination_count: u8,
+    pub destinations: [Pubkey; MAX_DESTINATIONS],
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetDelegateInstructionData {
+    type Error = ProgramError;
+
+    // max_per_period: u64, period: i64, destinations: u32 length + pubkeys
+    // (little-endian, Borsh-style like the Anchor vault)
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() < 20 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_per_period = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let period = i64::from_le_bytes(data[8..16].try_into().unwrap());
+        let count = u32::from_le_bytes(data[16..20].try_into().unwrap()) as usize;
+
+        // Instruction Checks
+        if max_per_period.eq(&0) || period <= 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if count == 0 || count > MAX_DESTINATIONS || data.len() != 20 + count * 32 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let mut destinations = [[0u8; 32]; MAX_DESTINATIONS];
+        for (destination, key) in destinations.iter_mut().zip(data[20..].chunks_exact(32)) {
+            destination.copy_from_slice(key);
+        }
+
+        Ok(Self {
+            max_per_period,
+            period,
+            destination_count: count as u8,
+            destinations,
+        })
+    }
+}
+
+pub struct SetDelegate<'a> {
+    pub accounts: SetDelegateAccounts<'a>,
+    pub instruction_datas: SetDelegateInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for SetDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetDelegateAccounts::try_from(accounts)?;
+        let instruction_datas = SetDelegateInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> SetDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &3;
+
+    pub fn process(&mut self) -> ProgramResult {
+        if self.accounts.allowance.data_is_empty() {
+            let seeds = [
+                Seed::from(b"delegate"),
+                Seed::from(self.accounts.vault.key().as_ref()),
+                Seed::from(self.accounts.delegate.key().as_ref()),
+                Seed::from(&self.accounts.allowance_bump),
+            ];
+            let signers = [Signer::from(&seeds)];
+
+            create_pda(
+                self.accounts.owner,
+                self.accounts.allowance,
+                DelegateAllowance::LEN,
+                &signers,
+            )?;
+        }
+
+        DelegateAllowance {
+            owner: *self.accounts.owner.key(),
+            delegate: *self.accounts.delegate.key(),
+            max_per_period: self.instruction_datas.max_per_period,
+            period: self.instruction_datas.period,
+            last_spend: Clock::get()?.unix_timestamp,
+            spent: 0,
+            destination_count: self.instruction_datas.destination_count,
+            destinations: self.instruction_datas.destinations,
+            bump: self.accounts.allowance_bump[0],
+        }
+        .store(&mut self.accounts.allowance.try_borrow_mut_data()?);
+
+        Ok(())
+    }
+}
+
+// Close a delegate's allowance, returning its rent to the owner.
+
+pub struct RevokeDelegateAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub allowance: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeDelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, vault, allowance] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Accounts Checks
+        if !owner.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        let (vault_key, _) = find_program_address(&[b"vault", owner.key()], &crate::ID);
+        if vault.key().ne(&vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        if !allowance.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let delegate = DelegateAllowance::load(&allowance.try_borrow_data()?)?.delegate;
+        let (allowance_key, _) =
+            find_program_address(&[b"delegate", vault.key(), &delegate], &crate::ID);
+        if allowance.key().ne(&allowance_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        Ok(Self { owner, allowance })
+    }
+}
+
+pub struct RevokeDelegate<'a> {
+    pub accounts: RevokeDelegateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RevokeDelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RevokeDelegateAccounts::try_from(accounts)?;
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> RevokeDelegate<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &4;
+
+    pub fn process(&mut self) -> ProgramResult {
+        *self.accounts.owner.try_borrow_mut_lamports()? += self.accounts.allowance.lamports();
+        self.accounts.allowance.close()
+    }
+}
+
+// Move lamports out of the owner's vault as a delegate, within its allowance
+// and only to an allowed destination. The lock account comes last, as in
+// Withdraw; delegates can't withdraw from locked vaults.
+
+pub struct DelegateWithdrawAccounts<'a> {
+    pub owner: &'a AccountInfo,
+    pub vault: &'a AccountInfo,
+    pub allowance: &'a AccountInfo,
+    pub destination: &'a AccountInfo,
+    pub bumps: [u8; 1],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DelegateWithdrawAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [delegate, owner, vault, allowance, destination, _system_program, lock] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        // Accounts Checks
+        if !delegate.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        if !vault.is_owned_by(&pinocchio_system::ID) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let (vault_key, bump) = find_program_address(&[b"vault", owner.key()], &crate::ID);
+        if vault.key().ne(&vault_key) {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let (allowance_key, _) =
+            find_program_address(&[b"delegate", vault.key(), delegate.key()], &crate::ID);
+        if allowance.key().ne(&allowance_key) || !allowance.is_owned_by(&crate::ID) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        let (lock_key, _) = find_program_address(&[b"lock", vault.key()], &crate::ID);
+        if lock.key().ne(&lock_key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        if !lock.data_is_empty() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self {
+            owner,
+            vault,
+            allowance,
+            destination,
+            bumps: [bump],
+        })
+    }
+}
+
+pub struct DelegateWithdraw<'a> {
+    pub accounts: DelegateWithdrawAccounts<'a>,
+    pub instruction_datas: WithdrawInstructionData,
+}
+
+impl<'a> TryFrom<(&'a [u8], &'a [AccountInfo])> for DelegateWithdraw<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&'a [u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DelegateWithdrawAccounts::try_from(accounts)?;
+        let instruction_datas = WithdrawInstructionData::try_from(data)?;
+
+        Ok(Self {
+            accounts,
+            instruction_datas,
+        })
+    }
+}
+
+impl<'a> DelegateWithdraw<'a> {
+    pub const DISCRIMINATOR: &'a u8 = &5;
+
+    pub fn process(&mut self) -> ProgramResult {
+        let mut allowance = DelegateAllowance::load(&self.accounts.allowance.try_borrow_data()?)?;
+        if allowance.owner.ne(self.accounts.owner.key()) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        if !allowance.allows(self.accounts.destination.key()) {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let amount = self.instruction_datas.amount;
+        let balance = self.accounts.vault.lamports();
+        if amount > balance {
+            return Err(ProgramError::InsufficientFunds);
+        }
+
+        // The vault is either left rent exempt or emptied entirely
+        if amount.ne(&balance) && balance - amount < Rent::get()?.minimum_balance(0) {
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        allowance.spend(amount, Clock::get()?.unix_timestamp)?;
+        allowance.store(&mut self.accounts.allowance.try_borrow_mut_data()?);
+
+        let seeds = [
+            Seed::from(b"vault"),
+            Seed::from(self.accounts.owner.key().as_ref()),
+            Seed::from(&self.accounts.bumps),
+        ];
+        let signers = [Signer::from(&seeds)];
+
+        Transfer {
+            from: self.accounts.vault,
+            to: self.accounts.destination,
+            lamports: amount,
+        }
+        .invoke_signed(&signers)?;
+
+        Ok(())
+    }
+}
+
+// Most destinations a delegate can be allowed to send to
+pub const MAX_DESTINATIONS: usize = 4;
+
+// A delegate's allowance on a vault, stored at ["delegate", vault, delegate].
+// Same layout as the Anchor vault's `DelegateAllowance`, minus its 8-byte
+// discriminator.
+pub struct DelegateAllowance {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub max_per_period: u64,
+    // Seconds it takes a fully used allowance to free up again
+    pub period: i64,
+    pub last_spend: i64,
+    // Lamports still counted against the allowance as of `last_spend`
+    pub spent: u64,
+    pub destination_count: u8,
+    pub destinations: [Pubkey; MAX_DESTINATIONS],
+    pub bump: u8,
+}
+
+impl DelegateAllowance {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 8 + 1 + 32 * MAX_DESTINATIONS + 1;
+
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut destinations = [[0u8; 32]; MAX_DESTINATIONS];
+        for (destination, key) in destinations.iter_mut().zip(data[97..225].chunks_exact(32)) {
+            destination.copy_from_slice(key);
+        }
+
+        Ok(Self {
+            owner: data[0..32].try_into().unwrap(),
+            delegate: data[32..64].try_into().unwrap(),
+            max_per_period: u64::from_le_bytes(data[64..72].try_into().unwrap()),
+            period: i64::from_le_bytes(data[72..80].try_into().unwrap()),
+            last_spend: i64::from_le_bytes(data[80..88].try_into().unwrap()),
+            spent: u64::from_le_bytes(data[88..96].try_into().unwrap()),
+            destination_count: data[96],
+            destinations,
+            bump: data[225],
+        })
+    }
+
+    pub fn store(&self, data: &mut [u8]) {
+        data[0..32].copy_from_slice(&self.owner);
+        data[32..64].copy_from_slice(&self.delegate);
+        data[64..72].copy_from_slice(&self.max_per_period.to_le_bytes());
+        data[72..80].copy_from_slice(&self.period.to_le_bytes());
+        data[80..88].copy_from_slice(&self.last_spend.to_le_bytes());
+        data[88..96].copy_from_slice(&self.spent.to_le_bytes());
+        data[96] = self.destination_count;
+        for (key, destination) in data[97..225].chunks_exact_mut(32).zip(&self.destinations) {
+            key.copy_from_slice(destination);
+        }
+        data[225] = self.bump;
+    }
+
+    pub fn allows(&self, destination: &Pubkey) -> bool {
+        self.destinations[..self.destination_count as usize].contains(destination)
+    }
+
+    // Records `amount` against the allowance. Spending decays linearly,
+    // freeing `max_per_period` lamports every `period` seconds, so a full
+    // allowance only comes back a whole period after it was used and can't be
+    // doubled up around a period boundary.
+    pub fn spend(&mut self, amount: u64, now: i64) -> Result<(), ProgramError> {
+        let elapsed = now.saturating_sub(self.last_spend).max(0) as u128;
+        let recovered = self.max_per_period as u128 * elapsed / self.period as u128;
+        let recovered = u64::try_from(recovered).unwrap_or(u64::MAX);
+        self.spent = self.spent.saturating_sub(recovered);
+        self.last_spend = now;
+
+        self.spent = self
+            .spent
+            .checked_add(amount)
+            .filter(|spent| *spent <= self.max_per_period)
+            .ok_or(ProgramError::InsufficientFunds)?;
+
         Ok(())
     }
 }
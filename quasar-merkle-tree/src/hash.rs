@@ -48,3 +48,58 @@ pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
         hasher.finalize().into()
     }
 }
+
+/// Leaf that only `owner` can consume; matches `anchor-merkle-tree-incremental`.
+pub fn owned_leaf(owner: &[u8; 32], data: &[u8; 32]) -> [u8; 32] {
+    #[cfg(any(target_os = "solana", target_arch = "bpf"))]
+    {
+        let slices: [&[u8]; 3] = [b"leaf", owner.as_ref(), data.as_ref()];
+        let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            sol_sha256(
+                slices.as_ptr() as *const u8,
+                3u64,
+                hash.as_mut_ptr() as *mut u8,
+            );
+            hash.assume_init()
+        }
+    }
+
+    #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+    {
+        use sha2::Digest;
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(b"leaf");
+        hasher.update(owner);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+}
+
+/// Seed of the nullifier PDA for `leaf` in `domain`; matches
+/// `anchor-merkle-tree-incremental`.
+pub fn nullifier_hash(leaf: &[u8; 32], domain: &[u8; 32]) -> [u8; 32] {
+    #[cfg(any(target_os = "solana", target_arch = "bpf"))]
+    {
+        let slices: [&[u8]; 3] = [b"nullifier", leaf.as_ref(), domain.as_ref()];
+        let mut hash = core::mem::MaybeUninit::<[u8; 32]>::uninit();
+        unsafe {
+            sol_sha256(
+                slices.as_ptr() as *const u8,
+                3u64,
+                hash.as_mut_ptr() as *mut u8,
+            );
+            hash.assume_init()
+        }
+    }
+
+    #[cfg(not(any(target_os = "solana", target_arch = "bpf")))]
+    {
+        use sha2::Digest;
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(b"nullifier");
+        hasher.update(leaf);
+        hasher.update(domain);
+        hasher.finalize().into()
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    hash::{hash_pair, nullifier_hash, owned_leaf},
+    state::{MerkleTree, Nullifier},
+};
+use quasar_lang::prelude::*;
+use solana_instruction_view::{
+    cpi::{invoke_signed_with_bounds, Seed, Signer as CpiSigner},
+    InstructionAccount, InstructionView,
+};
+
+/// Accounts forwarded to the hook after the nullifier PDA.
+pub const MAX_HOOK_ACCOUNTS: usize = 8;
+/// Size of the fixed `hook_data` argument; `hook_data_len` bytes are sent.
+pub const MAX_HOOK_DATA: usize = 64;
+
+#[derive(Accounts)]
+#[instruction(leaf_data: [u8; 32], domain: [u8; 32], nullifier_hash: [u8; 32])]
+pub struct Consume<'info> {
+    /// Consumer; the leaf must be `owned_leaf(payer, leaf_data)`.
+    pub payer: &'info mut Signer,
+
+    pub merkle_tree: &'info Account<MerkleTree>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"nullifier", merkle_tree, nullifier_hash],
+        bump,
+    )]
+    pub nullifier: &'info mut Account<Nullifier>,
+
+    pub clock: &'info Sysvar<Clock>,
+
+    pub system_program: &'info Program<System>,
+}
+
+impl<'info> Consume<'info> {
+    /// Verifies `owned_leaf(payer, leaf_data)` like `verify`, then fills the
+    /// freshly created nullifier PDA. `init` fails if the PDA exists, so each
+    /// `(leaf, domain)` can be consumed once. `nullifier_hash` is passed in
+    /// for the seeds and checked against the leaf and `domain` here.
+    ///
+    /// Because the leaf commits to the signer, a proof copied from a pending
+    /// transaction fails for anyone but the owner.
+    #[inline(always)]
+    pub fn consume(
+        &mut self,
+        leaf_data: [u8; 32],
+        domain: [u8; 32],
+        expected_hash: [u8; 32],
+        index: u32,
+        proof_bytes: [u8; 640],
+        bumps: &ConsumeBumps,
+    ) -> Result<(), ProgramError> {
+        let leaf = owned_leaf(&self.payer.address().to_bytes(), &leaf_data);
+        if nullifier_hash(&leaf, &domain) != expected_hash {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let tree = &self.merkle_tree;
+        if index >= tree.next_index {
+            return Err(ProgramError::Custom(2));
+        }
+
+        let mut current_hash = leaf;
+        let mut current_index = index;
+
+        let depth = tree.depth as usize;
+        let mut i = 0usize;
+        while i < depth {
+            let sibling: &[u8; 32] = proof_bytes[i * 32..(i + 1) * 32]
+                .try_into()
+                .map_err(|_| ProgramError::InvalidArgument)?;
+
+            current_hash = if current_index.is_multiple_of(2) {
+                hash_pair(&current_hash, sibling)
+            } else {
+                hash_pair(sibling, &current_hash)
+            };
+            current_index >>= 1;
+            i += 1;
+        }
+
+        if current_hash != tree.current_root {
+            return Err(ProgramError::Custom(2));
+        }
+
+        self.nullifier.set_inner(
+            *self.merkle_tree.address(),
+            expected_hash,
+            *self.payer.address(),
+            self.clock.slot,
+            bumps.nullifier,
+        );
+        Ok(())
+    }
+
+    /// Calls the optional hook once the nullifier is written, like the Anchor
+    /// program's `hook_program`. The first remaining account is the hook
+    /// program; it receives the nullifier PDA as its first (signer) account,
+    /// followed by the other remaining accounts.
+    pub fn call_hook(
+        &self,
+        remaining: &[AccountView],
+        expected_hash: [u8; 32],
+        hook_data: &[u8],
+        bumps: &ConsumeBumps,
+    ) -> Result<(), ProgramError> {
+        let Some((hook_program, forwarded)) = remaining.split_first() else {
+            return Ok(());
+        };
+        if !hook_program.executable() {
+            return Err(ProgramError::Custom(3));
+        }
+        if forwarded.len() > MAX_HOOK_ACCOUNTS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Unused slots repeat the nullifier / hook program and are sliced off.
+        let nullifier = self.nullifier.to_account_view();
+        let accounts: [InstructionAccount; MAX_HOOK_ACCOUNTS + 1] =
+            core::array::from_fn(|i| match i {
+                0 => InstructionAccount::readonly_signer(nullifier.address()),
+                _ => forwarded.get(i - 1).map_or_else(
+                    || InstructionAccount::readonly(nullifier.address()),
+                    InstructionAccount::from,
+                ),
+            });
+        let views: [&AccountView; MAX_HOOK_ACCOUNTS + 2] = core::array::from_fn(|i| match i {
+            0 => nullifier,
+            _ => forwarded.get(i - 1).unwrap_or(hook_program),
+        });
+
+        let bump = [bumps.nullifier];
+        let seeds = [
+            Seed::from(b"nullifier"),
+            Seed::from(self.merkle_tree.address().as_ref()),
+            Seed::from(&expected_hash),
+            Seed::from(&bump),
+        ];
+        invoke_signed_with_bounds::<{ MAX_HOOK_ACCOUNTS + 2 }>(
+            &InstructionView {
+                program_id: hook_program.address(),
+                accounts: &accounts[..forwarded.len() + 1],
+                data: hook_data,
+            },
+            &views[..forwarded.len() + 2],
+            &[CpiSigner::from(&seeds)],
+        )
+    }
+}
@@ -1,7 +1,9 @@
+pub mod consume;
 pub mod init;
 pub mod insert;
 pub mod verify;
 
+pub use consume::*;
 pub use init::*;
 pub use insert::*;
 pub use verify::*;
@@ -32,6 +32,31 @@ mod merkle {
     ) -> Result<(), ProgramError> {
         ctx.accounts.verify(leaf, index, proof_bytes)
     }
+
+    #[instruction(discriminator = 3)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn consume(
+        ctx: CtxWithRemaining<Consume>,
+        leaf_data: [u8; 32],
+        domain: [u8; 32],
+        nullifier_hash: [u8; 32],
+        index: u32,
+        proof_bytes: [u8; 640],
+        hook_data_len: u8,
+        hook_data: [u8; MAX_HOOK_DATA],
+    ) -> Result<(), ProgramError> {
+        let hook_data = hook_data
+            .get(..hook_data_len as usize)
+            .ok_or(ProgramError::InvalidArgument)?;
+        ctx.accounts
+            .consume(leaf_data, domain, nullifier_hash, index, proof_bytes, &ctx.bumps)?;
+        ctx.accounts.call_hook(
+            ctx.remaining_accounts(),
+            nullifier_hash,
+            hook_data,
+            &ctx.bumps,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -8,3 +8,13 @@ pub struct MerkleTree {
     pub current_root: [u8; 32],
     pub filled_subtrees: [[u8; 32]; 20],
 }
+
+/// Marks a leaf as spent for one domain; see `Consume`.
+#[account(discriminator = [0x4E, 0x55, 0x4C, 0x4C])]
+pub struct Nullifier {
+    pub merkle_tree: Address,
+    pub nullifier_hash: [u8; 32],
+    pub consumed_by: Address,
+    pub slot: u64,
+    pub bump: u8,
+}
@@ -1,6 +1,7 @@
 use quasar_svm::{Account, AccountMeta, Instruction, Pubkey, QuasarSvm};
 
 const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID.to_bytes());
+const CLOCK_SYSVAR: Pubkey = Pubkey::from_str_const("SysvarC1ock11111111111111111111111111111111");
 
 fn setup() -> QuasarSvm {
     let elf = include_bytes!("../target/deploy/merkle.so");
@@ -50,6 +51,55 @@ fn ix_verify(merkle_tree: Pubkey, leaf: [u8; 32], index: u32, proof: [u8; 640])
     }
 }
 
+fn nullifier_pda(merkle_tree: &Pubkey, hash: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(&[b"nullifier", merkle_tree.as_ref(), hash], &PROGRAM_ID).0
+}
+
+fn owned(owner: &Pubkey, data: &[u8; 32]) -> [u8; 32] {
+    crate::hash::owned_leaf(&owner.to_bytes(), data)
+}
+
+/// `consume` without a hook: no remaining accounts and empty hook data.
+fn ix_consume(
+    payer: Pubkey,
+    merkle_tree: Pubkey,
+    leaf_data: [u8; 32],
+    domain: [u8; 32],
+    index: u32,
+    proof: [u8; 640],
+) -> Instruction {
+    let hash = crate::hash::nullifier_hash(&owned(&payer, &leaf_data), &domain);
+    let mut data = vec![3];
+    data.extend_from_slice(&leaf_data);
+    data.extend_from_slice(&domain);
+    data.extend_from_slice(&hash);
+    data.extend_from_slice(&index.to_le_bytes());
+    data.extend_from_slice(&proof);
+    data.push(0);
+    data.extend_from_slice(&[0u8; crate::ix::MAX_HOOK_DATA]);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(merkle_tree, false),
+            AccountMeta::new(nullifier_pda(&merkle_tree, &hash), false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+            AccountMeta::new_readonly(quasar_svm::system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+fn empty_account(address: Pubkey) -> Account {
+    Account {
+        address,
+        lamports: 0,
+        data: vec![],
+        owner: quasar_svm::system_program::ID,
+        executable: false,
+    }
+}
+
 fn authority_account(address: Pubkey) -> Account {
     Account {
         address,
@@ -165,3 +215,150 @@ fn test_insert_and_verify() {
     result.assert_success();
     println!("verify CU: {}", result.compute_units_consumed);
 }
+
+#[test]
+fn test_client_proofs_verify_on_chain() {
+    use merkle_tree_client::{proof_bytes, MerkleTree as ClientTree};
+
+    let mut svm = setup();
+    let authority = Pubkey::new_unique();
+    let merkle_tree = merkle_tree_pda(&authority);
+
+    let empty_tree = Account {
+        address: merkle_tree,
+        lamports: 0,
+        data: vec![],
+        owner: quasar_svm::system_program::ID,
+        executable: false,
+    };
+    let init_result = svm.process_instruction(
+        &ix_initialize(authority),
+        &[authority_account(authority), empty_tree],
+    );
+    init_result.assert_success();
+    let mut tree_account = init_result
+        .account(&merkle_tree)
+        .expect("merkle_tree not in result")
+        .clone();
+
+    let mut client = ClientTree::new();
+    for n in 1..=7u8 {
+        let leaf = [n; 32];
+        let insert_result = svm.process_instruction(
+            &ix_insert(authority, leaf),
+            &[authority_account(authority), tree_account],
+        );
+        insert_result.assert_success();
+        tree_account = insert_result
+            .account(&merkle_tree)
+            .expect("merkle_tree not in result")
+            .clone();
+        client.insert(leaf).unwrap();
+    }
+
+    // Every client proof must fold up to the root the program stored.
+    for index in 0..client.next_index() {
+        let proof = proof_bytes(&client.proof(index).unwrap());
+        let leaf = client.leaf(index).unwrap();
+        svm.process_instruction(
+            &ix_verify(merkle_tree, leaf, index, proof),
+            &[tree_account.clone()],
+        )
+        .assert_success();
+    }
+}
+
+#[test]
+fn test_consume_once_per_domain() {
+    use merkle_tree_client::{proof_bytes, MerkleTree as ClientTree};
+
+    let mut svm = setup();
+    let authority = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let merkle_tree = merkle_tree_pda(&authority);
+
+    let init_result = svm.process_instruction(
+        &ix_initialize(authority),
+        &[authority_account(authority), empty_account(merkle_tree)],
+    );
+    init_result.assert_success();
+    let mut tree_account = init_result
+        .account(&merkle_tree)
+        .expect("merkle_tree not in result")
+        .clone();
+
+    // Leaf 1 belongs to `payer`
+    let data = [2u8; 32];
+    let leaf = owned(&payer, &data);
+    let mut client = ClientTree::new();
+    for inserted in [[1u8; 32], leaf, [3u8; 32]] {
+        let insert_result = svm.process_instruction(
+            &ix_insert(authority, inserted),
+            &[authority_account(authority), tree_account],
+        );
+        insert_result.assert_success();
+        tree_account = insert_result
+            .account(&merkle_tree)
+            .expect("merkle_tree not in result")
+            .clone();
+        client.insert(inserted).unwrap();
+    }
+
+    let airdrop = [1u8; 32];
+    let proof = proof_bytes(&client.proof(1).unwrap());
+    let nullifier = nullifier_pda(&merkle_tree, &crate::hash::nullifier_hash(&leaf, &airdrop));
+
+    // Someone replaying the owner's data and proof builds a different leaf.
+    let thief = Pubkey::new_unique();
+    let thief_nullifier = nullifier_pda(
+        &merkle_tree,
+        &crate::hash::nullifier_hash(&owned(&thief, &data), &airdrop),
+    );
+    let result = svm.process_instruction(
+        &ix_consume(thief, merkle_tree, data, airdrop, 1, proof),
+        &[
+            authority_account(thief),
+            tree_account.clone(),
+            empty_account(thief_nullifier),
+        ],
+    );
+    assert!(result.raw_result.is_err(), "front-run consume must fail");
+
+    let result = svm.process_instruction(
+        &ix_consume(payer, merkle_tree, data, airdrop, 1, proof),
+        &[
+            authority_account(payer),
+            tree_account.clone(),
+            empty_account(nullifier),
+        ],
+    );
+    result.assert_success();
+    println!("consume CU: {}", result.compute_units_consumed);
+    let nullifier_account = result
+        .account(&nullifier)
+        .expect("nullifier not in result")
+        .clone();
+
+    // The nullifier now exists, so the same (leaf, domain) is rejected.
+    let result = svm.process_instruction(
+        &ix_consume(payer, merkle_tree, data, airdrop, 1, proof),
+        &[
+            authority_account(payer),
+            tree_account.clone(),
+            nullifier_account,
+        ],
+    );
+    assert!(result.raw_result.is_err(), "double consume must fail");
+
+    let vote = [2u8; 32];
+    let vote_nullifier = nullifier_pda(&merkle_tree, &crate::hash::nullifier_hash(&leaf, &vote));
+    svm.process_instruction(
+        &ix_consume(payer, merkle_tree, data, vote, 1, proof),
+        &[
+            authority_account(payer),
+            tree_account,
+            empty_account(vote_nullifier),
+        ],
+    )
+    .assert_success();
+}